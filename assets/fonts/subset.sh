@@ -0,0 +1,25 @@
+#!/bin/sh
+# 生成随程序发布的中文字体 assets/fonts/cjk.ttf 和授权文件 OFL.txt
+# 下载 Noto Sans SC（SIL Open Font License 1.1），只保留中文界面和题库用到的字符
+# 需要 curl 和 fonttools（pip install fonttools）
+set -eu
+
+cd "$(dirname "$0")"
+BASE=https://raw.githubusercontent.com/google/fonts/main/ofl/notosanssc
+work=$(mktemp -d)
+trap 'rm -rf "$work"' EXIT
+
+curl -fL -o "$work/NotoSansSC.ttf" "$BASE/NotoSansSC%5Bwght%5D.ttf"
+curl -fL -o OFL.txt "$BASE/OFL.txt"
+
+# 可变字体先固定为常规字重，macroquad 只读取默认的字形
+fonttools varLib.instancer "$work/NotoSansSC.ttf" wght=400 -o "$work/regular.ttf"
+
+# 中文文本表、成就和题库中出现的所有字符，再加上全部可打印 ASCII 字符
+cat ../../src/i18n/zh.rs ../*.json ../packs/* > "$work/chars.txt"
+pyftsubset "$work/regular.ttf" \
+    --text-file="$work/chars.txt" \
+    --unicodes=U+0020-007E,U+00B7,U+2013-2014,U+2018-201D,U+2026,U+3000-303F,U+FF01-FF5E \
+    --output-file=cjk.ttf
+
+echo "已生成 $(pwd)/cjk.ttf，修改中文文本后需要重新运行"
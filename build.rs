@@ -0,0 +1,11 @@
+// 发布前放入 assets/fonts/cjk.ttf 的中文字体会被编译进程序，玩家的系统里没有中文字体也能显示中文界面
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(bundled_cjk_font)");
+    println!("cargo::rerun-if-changed=assets/fonts");
+    if std::path::Path::new("assets/fonts/cjk.ttf").is_file() {
+        println!("cargo::rustc-cfg=bundled_cjk_font");
+    } else {
+        // 没有内置字体时中文界面依赖玩家系统中的字体，发布前应先运行 assets/fonts/subset.sh
+        println!("cargo::warning=assets/fonts/cjk.ttf 不存在，中文界面将依赖系统字体；运行 assets/fonts/subset.sh 生成内置字体");
+    }
+}
\ No newline at end of file
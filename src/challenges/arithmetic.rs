@@ -0,0 +1,75 @@
+use super::choices::{draw_choices, update_choice, ChoiceLayout};
+use super::{center_y, draw_centered, draw_input_box, Challenge, ChallengeInput, ChallengeKind};
+use crate::i18n::{tr, Text};
+use crate::math_challenge::{MathChallenge, Problem};
+use ::rand::thread_rng;
+use macroquad::prelude::*;
+
+impl Challenge for MathChallenge {
+    fn kind(&self) -> ChallengeKind {
+        ChallengeKind::Arithmetic
+    }
+    
+    fn subtitle(&self) -> String {
+        self.problem.topic.name().to_string()
+    }
+    
+    fn prompt(&self) -> &str {
+        tr(Text::PromptMath)
+    }
+    
+    fn update(&mut self, input: &ChallengeInput, _dt: f32) -> Option<bool> {
+        // 手柄无法输入数字，使用手柄时改为选择题
+        if input.pad_used && !self.is_multiple_choice() {
+            self.offer_choices(&mut thread_rng());
+            return None;
+        }
+        
+        if self.is_multiple_choice() {
+            let index = update_choice(&mut self.selected, self.choices.len(), ChoiceLayout::Grid, input)?;
+            return Some(self.choose(index));
+        }
+        
+        // 数字、负号、小数点和分数线
+        for &c in &input.chars {
+            self.add_char(c);
+        }
+        if input.backspace {
+            self.remove_char();
+        }
+        
+        // 还没有输入或无法解析时忽略，避免误触直接结束游戏
+        if input.confirm && !self.get_user_answer().is_empty() {
+            let correct = self.submit_answer();
+            return self.is_completed.then_some(correct);
+        }
+        None
+    }
+    
+    fn draw(&self) -> f32 {
+        draw_centered(self.get_question_text(), center_y(-10.0), 32.0, YELLOW);
+        if self.is_multiple_choice() {
+            let options: Vec<String> = self.choices.iter().map(|choice| choice.to_string()).collect();
+            draw_choices(&options, self.selected, ChoiceLayout::Grid)
+        } else {
+            draw_input_box(self.get_user_answer(), center_y(30.0));
+            center_y(95.0)
+        }
+    }
+    
+    fn hint(&self) -> &str {
+        if self.is_multiple_choice() {
+            tr(Text::HintChoices)
+        } else {
+            tr(Text::HintMathTyped)
+        }
+    }
+    
+    fn correct_answer(&self) -> String {
+        self.problem.answer.to_string()
+    }
+    
+    fn learning_record(&self) -> Option<(&Problem, &str)> {
+        Some((&self.problem, self.get_user_answer()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,124 @@
+use super::{center_y, ChallengeInput};
+use crate::ui::{Anchor, Layout};
+use macroquad::prelude::*;
+
+// 选项的排列方式：短答案用 2×2 网格，较长的文字（例如释义）用单列
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChoiceLayout {
+    Grid,
+    List,
+}
+
+// 选项区域相对屏幕中线的位置，以及选项和下方提示之间的距离
+const CHOICES_TOP: f32 = 25.0;
+const CHOICES_BOTTOM: f32 = 25.0;
+
+// 第 index 个选项的位置，绘制和鼠标点击共用
+pub fn choice_rect(layout: ChoiceLayout, index: usize) -> Rect {
+    choice_rect_in(&Layout::current(), layout, index)
+}
+
+// 选项相对屏幕中心排列，尺寸以参考分辨率给出
+fn choice_rect_in(screen: &Layout, layout: ChoiceLayout, index: usize) -> Rect {
+    let gap = 12.0;
+    match layout {
+        ChoiceLayout::Grid => {
+            let (width, height) = (180.0, 40.0);
+            let column = (index % 2) as f32;
+            let row = (index / 2) as f32;
+            let offset = vec2((column - 0.5) * (width + gap), CHOICES_TOP + height / 2.0 + row * (height + gap));
+            screen.place(Anchor::Center, width, height, offset)
+        }
+        ChoiceLayout::List => {
+            let (width, height) = ((screen.size().x - 80.0).min(560.0), 34.0);
+            let offset = vec2(0.0, CHOICES_TOP + height / 2.0 + index as f32 * (height + 8.0));
+            screen.place(Anchor::Center, width, height, offset)
+        }
+    }
+}
+
+// 处理选择题的输入：数字键直接作答，方向键和十字键移动选中项，鼠标悬停选中、点击作答，确认键提交选中项
+pub fn update_choice(
+    selected: &mut usize,
+    count: usize,
+    layout: ChoiceLayout,
+    input: &ChallengeInput,
+) -> Option<usize> {
+    let mut chosen = input
+        .chars
+        .iter()
+        .filter_map(|c| c.to_digit(10).and_then(|digit| (digit as usize).checked_sub(1)))
+        .find(|&index| index < count);
+    
+    let columns = if layout == ChoiceLayout::Grid { 2 } else { 1 };
+    if input.left && columns == 2 && *selected % 2 == 1 {
+        *selected -= 1;
+    } else if input.right && columns == 2 && selected.is_multiple_of(2) && *selected + 1 < count {
+        *selected += 1;
+    } else if input.up && *selected >= columns {
+        *selected -= columns;
+    } else if input.down && *selected + columns < count {
+        *selected += columns;
+    }
+    
+    if input.mouse_moved || input.clicked {
+        if let Some(index) = (0..count).find(|&index| choice_rect(layout, index).contains(input.mouse)) {
+            if input.mouse_moved {
+                *selected = index;
+            }
+            if input.clicked {
+                chosen = chosen.or(Some(index));
+            }
+        }
+    }
+    
+    if input.confirm {
+        chosen = chosen.or(Some(*selected));
+    }
+    if let Some(index) = chosen {
+        *selected = index;
+    }
+    chosen
+}
+
+// 绘制选项，返回下方提示的 y 坐标
+pub fn draw_choices(options: &[String], selected: usize, layout: ChoiceLayout) -> f32 {
+    let screen = Layout::current();
+    let mut bottom = center_y(0.0);
+    for (i, option) in options.iter().enumerate() {
+        let rect = choice_rect_in(&screen, layout, i);
+        let is_selected = i == selected;
+        let fill = if is_selected {
+            Color::new(0.3, 0.3, 0.1, 1.0)
+        } else {
+            DARKGRAY
+        };
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, fill);
+        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2.0, if is_selected { GOLD } else { WHITE });
+        let number_size = screen.px(20.0);
+        draw_text(
+            format!("{}.", i + 1),
+            rect.x + screen.px(10.0),
+            rect.y + rect.h / 2.0 + number_size / 3.0,
+            number_size,
+            LIGHTGRAY,
+        );
+        
+        // 文字太长时缩小字号
+        let mut font_size = if layout == ChoiceLayout::Grid { 24.0 } else { 20.0 };
+        let mut dims = measure_text(option, None, screen.px(font_size) as u16, 1.0);
+        while dims.width > rect.w - screen.px(50.0) && font_size > 12.0 {
+            font_size -= 2.0;
+            dims = measure_text(option, None, screen.px(font_size) as u16, 1.0);
+        }
+        draw_text(
+            option,
+            rect.x + rect.w / 2.0 - dims.width / 2.0 + screen.px(10.0),
+            rect.y + rect.h / 2.0 + screen.px(font_size) / 3.0,
+            screen.px(font_size),
+            WHITE,
+        );
+        bottom = bottom.max(rect.y + rect.h);
+    }
+    bottom + screen.px(CHOICES_BOTTOM)
+}
\ No newline at end of file
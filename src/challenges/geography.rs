@@ -0,0 +1,101 @@
+use super::choices::{draw_choices, update_choice, ChoiceLayout};
+use super::{center_y, draw_centered, Challenge, ChallengeInput, ChallengeKind};
+use crate::i18n::{tr, trf, Text};
+use ::rand::seq::SliceRandom;
+use ::rand::Rng;
+use macroquad::prelude::*;
+use serde::Deserialize;
+
+// 国家和首都，按知名程度排列；难度档位越高，可能出现的国家越多
+const CAPITALS: &str = include_str!("../../assets/geography.json");
+
+#[derive(Clone, Debug, Deserialize)]
+struct Capital {
+    country: String,
+    capital: String,
+}
+
+fn load_capitals() -> Vec<Capital> {
+    serde_json::from_str(CAPITALS).expect("assets/geography.json 格式错误")
+}
+
+// 地理：选出国家的首都
+pub struct GeographyChallenge {
+    country: String,
+    options: Vec<String>,
+    answer: usize,
+    selected: usize,
+}
+
+impl GeographyChallenge {
+    pub fn new(rng: &mut impl Rng, tier: u32) -> Self {
+        let capitals = load_capitals();
+        let pool = &capitals[..(12 + 4 * tier as usize).min(capitals.len())];
+        let picked: Vec<&Capital> = pool.choose_multiple(rng, 4).collect();
+        let target = picked[0];
+        let mut options: Vec<String> = picked.iter().map(|entry| entry.capital.clone()).collect();
+        options.shuffle(rng);
+        let answer = options.iter().position(|option| *option == target.capital).unwrap_or(0);
+        Self {
+            country: target.country.clone(),
+            options,
+            answer,
+            selected: 0,
+        }
+    }
+}
+
+impl Challenge for GeographyChallenge {
+    fn kind(&self) -> ChallengeKind {
+        ChallengeKind::Geography
+    }
+    
+    fn subtitle(&self) -> String {
+        tr(Text::SubtitleCapitals).to_string()
+    }
+    
+    fn prompt(&self) -> &str {
+        tr(Text::PromptGeography)
+    }
+    
+    fn update(&mut self, input: &ChallengeInput, _dt: f32) -> Option<bool> {
+        let index = update_choice(&mut self.selected, self.options.len(), ChoiceLayout::Grid, input)?;
+        Some(index == self.answer)
+    }
+    
+    fn draw(&self) -> f32 {
+        let question = trf(Text::GeographyQuestion, &[&self.country]);
+        draw_centered(&question, center_y(-10.0), 28.0, YELLOW);
+        draw_choices(&self.options, self.selected, ChoiceLayout::Grid)
+    }
+    
+    fn hint(&self) -> &str {
+        tr(Text::HintChoices)
+    }
+    
+    fn correct_answer(&self) -> String {
+        self.options[self.answer].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+    
+    #[test]
+    fn options_are_distinct_and_include_the_capital() {
+        let mut rng = StdRng::seed_from_u64(8);
+        for tier in 0..10 {
+            let challenge = GeographyChallenge::new(&mut rng, tier);
+            assert_eq!(challenge.options.len(), 4);
+            let capital = load_capitals().into_iter().find(|entry| entry.country == challenge.country).unwrap().capital;
+            assert_eq!(challenge.options[challenge.answer], capital);
+            let mut sorted = challenge.options.clone();
+            sorted.sort();
+            sorted.dedup();
+            assert_eq!(sorted.len(), 4);
+        }
+    }
+}
\ No newline at end of file
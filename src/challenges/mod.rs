@@ -0,0 +1,265 @@
+mod arithmetic;
+mod choices;
+mod geography;
+mod pack;
+mod reflex;
+mod times_table;
+mod words;
+
+pub use geography::GeographyChallenge;
+pub use pack::{PackChallenge, PackError, QuestionPack, SAMPLE_PACK};
+pub use reflex::ReflexChallenge;
+pub use times_table::TimesTableChallenge;
+pub use words::{SpellingChallenge, VocabularyChallenge};
+
+use crate::i18n::{tr, trf, Text};
+use crate::math_challenge::Problem;
+use crate::ui::{Anchor, Layout};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 复活血量比例：不限时为默认值，限时时按答题速度在最低和最高之间插值
+const DEFAULT_REVIVE: f32 = 0.5;
+const MIN_REVIVE: f32 = 0.25;
+const MAX_REVIVE: f32 = 0.75;
+// 连续答对时每次额外恢复的比例和叠加上限
+const STREAK_BONUS: f32 = 0.05;
+const MAX_STREAK_STEPS: u32 = 5;
+
+// 复活时可以出现的挑战类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChallengeKind {
+    Arithmetic,
+    TimesTables,
+    Spelling,
+    Vocabulary,
+    Geography,
+    Reflex,
+}
+
+impl ChallengeKind {
+    pub const ALL: [ChallengeKind; 6] = [
+        ChallengeKind::Arithmetic,
+        ChallengeKind::TimesTables,
+        ChallengeKind::Spelling,
+        ChallengeKind::Vocabulary,
+        ChallengeKind::Geography,
+        ChallengeKind::Reflex,
+    ];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChallengeKind::Arithmetic => tr(Text::KindMath),
+            ChallengeKind::TimesTables => tr(Text::KindTimesTables),
+            ChallengeKind::Spelling => tr(Text::KindSpelling),
+            ChallengeKind::Vocabulary => tr(Text::KindVocabulary),
+            ChallengeKind::Geography => tr(Text::KindGeography),
+            ChallengeKind::Reflex => tr(Text::KindReflex),
+        }
+    }
+}
+
+// 一帧中答题界面的输入，由游戏统一读取后交给挑战处理
+#[derive(Clone, Debug, Default)]
+pub struct ChallengeInput {
+    pub chars: Vec<char>, // 本帧输入的字符（包括小键盘）
+    pub backspace: bool,
+    pub confirm: bool,
+    pub left: bool,
+    pub right: bool,
+    pub up: bool,
+    pub down: bool,
+    pub pad_used: bool, // 本帧按下了手柄按钮
+    pub mouse: Vec2,
+    pub mouse_moved: bool,
+    pub clicked: bool,
+}
+
+// 复活挑战：每种挑战自己处理输入和绘制题目，外框、倒计时和复活规则由 ChallengeSession 统一处理
+pub trait Challenge {
+    fn kind(&self) -> ChallengeKind;
+    
+    // 挑战界面的标题
+    fn title(&self) -> String {
+        trf(Text::ChallengeTitle, &[&self.kind().name()])
+    }
+    
+    // 标题下方的小字，例如题型
+    fn subtitle(&self) -> String {
+        String::new()
+    }
+    
+    fn prompt(&self) -> &str;
+    
+    // 处理一帧输入，作答后返回是否正确
+    fn update(&mut self, input: &ChallengeInput, dt: f32) -> Option<bool>;
+    
+    // 在屏幕中线附近绘制题目和作答区域，返回下方说明文字的 y 坐标
+    fn draw(&self) -> f32;
+    
+    // 作答方式的说明
+    fn hint(&self) -> &str;
+    
+    // 正确答案，答错后显示
+    fn correct_answer(&self) -> String;
+    
+    // 学习记录中的题目和玩家的答案；不是数学题时为 None
+    fn learning_record(&self) -> Option<(&Problem, &str)> {
+        None
+    }
+}
+
+// 一次复活挑战：具体的挑战加上倒计时
+pub struct ChallengeSession {
+    pub challenge: Box<dyn Challenge>,
+    pub time_limit: Option<f32>, // 秒，None 表示不限时
+    pub elapsed: f32,
+}
+
+impl ChallengeSession {
+    pub fn new(challenge: Box<dyn Challenge>, time_limit: Option<f32>) -> Self {
+        Self {
+            challenge,
+            time_limit,
+            elapsed: 0.0,
+        }
+    }
+    
+    // 推进计时，超时返回 true
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.elapsed += dt;
+        self.time_left() == Some(0.0)
+    }
+    
+    pub fn time_left(&self) -> Option<f32> {
+        self.time_limit.map(|limit| (limit - self.elapsed).max(0.0))
+    }
+    
+    // 答对时恢复的血量比例：答得越快越多，连续答对再额外增加
+    pub fn revive_fraction(&self, streak: u32) -> f32 {
+        let base = match self.time_limit {
+            Some(limit) if limit > 0.0 => {
+                let speed = 1.0 - (self.elapsed / limit).clamp(0.0, 1.0);
+                MIN_REVIVE + (MAX_REVIVE - MIN_REVIVE) * speed
+            }
+            _ => DEFAULT_REVIVE,
+        };
+        (base + STREAK_BONUS * streak.min(MAX_STREAK_STEPS) as f32).min(1.0)
+    }
+}
+
+// 题目区域以屏幕中线为基准，offset 为参考分辨率下相对中线的距离
+fn center_y(offset: f32) -> f32 {
+    let screen = Layout::current();
+    screen.center().y + screen.px(offset)
+}
+
+// 居中绘制一行文字，字号为参考分辨率下的大小
+fn draw_centered(text: &str, y: f32, font_size: f32, color: Color) {
+    let font_size = Layout::current().px(font_size);
+    let dims = measure_text(text, None, font_size as u16, 1.0);
+    draw_text(text, screen_width() / 2.0 - dims.width / 2.0, y, font_size, color);
+}
+
+// 带光标的输入框
+fn draw_input_box(text: &str, y: f32) {
+    let screen = Layout::current();
+    let rect = screen.place(Anchor::Top, 240.0, 40.0, Vec2::ZERO);
+    let (x, width, height) = (rect.x, rect.w, rect.h);
+    let font_size = screen.px(24.0);
+    let padding = screen.px(10.0);
+    draw_rectangle(x, y, width, height, DARKGRAY);
+    draw_rectangle_lines(x, y, width, height, 2.0, WHITE);
+    // 输入太长时只显示末尾放得下的部分
+    let mut text = text;
+    while measure_text(text, None, font_size as u16, 1.0).width > width - 2.4 * padding {
+        let mut chars = text.chars();
+        chars.next();
+        text = chars.as_str();
+    }
+    let dims = measure_text(text, None, font_size as u16, 1.0);
+    draw_text(text, x + padding, y + height * 0.7, font_size, WHITE);
+    let cursor_x = x + padding + dims.width;
+    draw_line(cursor_x, y + height * 0.2, cursor_x, y + height * 0.8, 2.0, WHITE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_challenge::{MathChallenge, Topic};
+    
+    fn session(time_limit: Option<f32>) -> ChallengeSession {
+        ChallengeSession::new(Box::new(MathChallenge::generate(&[Topic::Addition], 0)), time_limit)
+    }
+    
+    #[test]
+    fn faster_answers_revive_with_more_health() {
+        let mut session = session(Some(20.0));
+        let instant = session.revive_fraction(0);
+        assert!(!session.tick(15.0));
+        let slow = session.revive_fraction(0);
+        assert!(instant > slow);
+        assert!(session.revive_fraction(3) > slow);
+        assert!(session.tick(10.0));
+        assert_eq!(session.time_left(), Some(0.0));
+        assert_eq!(self::session(None).revive_fraction(0), DEFAULT_REVIVE);
+    }
+    
+    fn typed(chars: &str) -> ChallengeInput {
+        ChallengeInput {
+            chars: chars.chars().collect(),
+            ..Default::default()
+        }
+    }
+    
+    fn confirm() -> ChallengeInput {
+        ChallengeInput {
+            confirm: true,
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn typed_answers_are_checked_on_confirm() {
+        let mut math = MathChallenge::generate(&[Topic::Multiplication], 0);
+        let answer = math.problem.answer.to_string();
+        assert_eq!(math.update(&typed(&answer), 0.1), None);
+        assert_eq!(math.update(&confirm(), 0.1), Some(true));
+        
+        let mut spelling = SpellingChallenge::new(&mut ::rand::thread_rng(), 0);
+        let word = spelling.correct_answer();
+        spelling.update(&typed(&word.to_uppercase()), 0.1);
+        assert_eq!(spelling.update(&confirm(), 0.1), Some(true));
+    }
+    
+    #[test]
+    fn reflex_fails_when_pressed_early() {
+        let mut reflex = ReflexChallenge::new(&mut ::rand::thread_rng(), 0);
+        // 刚进入挑战时的按键被忽略
+        assert_eq!(reflex.update(&confirm(), 0.01), None);
+        assert_eq!(reflex.update(&ChallengeInput::default(), 0.6), None);
+        assert_eq!(reflex.update(&confirm(), 0.01), Some(false));
+        
+        let mut reflex = ReflexChallenge::new(&mut ::rand::thread_rng(), 0);
+        assert_eq!(reflex.update(&ChallengeInput::default(), 10.0), None);
+        assert_eq!(reflex.update(&confirm(), 0.1), Some(true));
+    }
+    
+    #[test]
+    fn gamepad_switches_typed_challenges_to_choices() {
+        let pad = ChallengeInput {
+            pad_used: true,
+            ..Default::default()
+        };
+        let mut spelling = SpellingChallenge::new(&mut ::rand::thread_rng(), 3);
+        assert_eq!(spelling.update(&pad, 0.1), None);
+        let correct = spelling.correct_answer();
+        let index = spelling.choices.iter().position(|choice| *choice == correct).unwrap();
+        assert_eq!(spelling.choices.len(), 4);
+        let pick = ChallengeInput {
+            chars: vec![char::from_digit(index as u32 + 1, 10).unwrap()],
+            ..Default::default()
+        };
+        assert_eq!(spelling.update(&pick, 0.1), Some(true));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,365 @@
+use super::choices::{draw_choices, update_choice, ChoiceLayout};
+use super::{center_y, draw_centered, draw_input_box, Challenge, ChallengeInput, ChallengeKind};
+use crate::i18n::{tr, trf, Text};
+use crate::math_challenge::Answer;
+use crate::ui::{wrap_text, Layout};
+use ::rand::seq::SliceRandom;
+use ::rand::Rng;
+use macroquad::prelude::*;
+use serde::Deserialize;
+use std::fmt;
+
+// 随程序附带的示例题库，也作为编写题库的模板
+pub const SAMPLE_PACK: (&str, &str) = ("sample.toml", include_str!("../../assets/packs/sample.toml"));
+
+pub const MAX_DIFFICULTY: u32 = 5;
+const MAX_PROMPT_LEN: usize = 160;
+const MAX_ANSWER_LEN: usize = 32;
+const MAX_CHOICES: usize = 6;
+
+// 老师提供的题库文件
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuestionPack {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub questions: Vec<PackQuestion>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackQuestion {
+    pub prompt: String,
+    pub answers: Vec<String>, // 可以接受的答案，不区分大小写；数字答案按数值比较
+    #[serde(default)]
+    pub choices: Vec<String>, // 为空时输入答案，否则以选择题形式出题
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default = "default_difficulty")]
+    pub difficulty: u32, // 1 ~ MAX_DIFFICULTY
+}
+
+fn default_difficulty() -> u32 {
+    1
+}
+
+// 题库无法使用的原因，file 为题库文件名
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackError {
+    pub file: String,
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.file, self.problems.first().map(String::as_str).unwrap_or("invalid pack"))?;
+        if self.problems.len() > 1 {
+            write!(f, " (and {} more)", self.problems.len() - 1)?;
+        }
+        Ok(())
+    }
+}
+
+// 比较答案时忽略大小写和多余的空格
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+// 两个答案是否相同：数字按数值比较（"0.5" 和 "1/2" 相同），其他按文字比较
+fn same_answer(a: &str, b: &str) -> bool {
+    match (Answer::parse(a), Answer::parse(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => normalize(a) == normalize(b),
+    }
+}
+
+impl QuestionPack {
+    // 按扩展名解析 .json 或 .toml 题库并检查内容
+    pub fn parse(file: &str, contents: &str) -> Result<Self, PackError> {
+        let error = |message: String| PackError {
+            file: file.to_string(),
+            problems: vec![message],
+        };
+        let pack: QuestionPack = if file.ends_with(".toml") {
+            toml::from_str(contents).map_err(|err| error(err.message().to_string()))?
+        } else if file.ends_with(".json") {
+            serde_json::from_str(contents).map_err(|err| error(err.to_string()))?
+        } else {
+            return Err(error("unsupported file type, use .json or .toml".to_string()));
+        };
+        let problems = pack.problems();
+        if problems.is_empty() {
+            Ok(pack)
+        } else {
+            Err(PackError {
+                file: file.to_string(),
+                problems,
+            })
+        }
+    }
+    
+    // 检查题库内容，返回所有问题；每条问题都指出是第几题
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.name.trim().is_empty() {
+            problems.push("the pack needs a name".to_string());
+        }
+        if self.questions.is_empty() {
+            problems.push("the pack has no questions".to_string());
+        }
+        for (i, question) in self.questions.iter().enumerate() {
+            let mut report = |message: &str| problems.push(format!("question {}: {}", i + 1, message));
+            let prompt = question.prompt.trim();
+            if prompt.is_empty() {
+                report("the prompt is empty");
+            } else if prompt.chars().count() > MAX_PROMPT_LEN {
+                report(&format!("the prompt is longer than {} characters", MAX_PROMPT_LEN));
+            }
+            if question.answers.is_empty() {
+                report("no accepted answers");
+            }
+            if question.answers.iter().any(|answer| answer.trim().is_empty()) {
+                report("an accepted answer is empty");
+            }
+            if question.answers.iter().any(|answer| answer.chars().count() > MAX_ANSWER_LEN) {
+                report(&format!("answers must be at most {} characters", MAX_ANSWER_LEN));
+            }
+            if !(1..=MAX_DIFFICULTY).contains(&question.difficulty) {
+                report(&format!("difficulty must be between 1 and {}", MAX_DIFFICULTY));
+            }
+            if !question.choices.is_empty() {
+                if !(2..=MAX_CHOICES).contains(&question.choices.len()) {
+                    report(&format!("give between 2 and {} choices", MAX_CHOICES));
+                }
+                let correct = question.choices.iter().filter(|choice| question.accepts(choice)).count();
+                if correct == 0 {
+                    report("none of the choices is an accepted answer");
+                } else if correct > 1 {
+                    report("more than one choice is an accepted answer");
+                }
+                let mut seen: Vec<String> = Vec::new();
+                for choice in &question.choices {
+                    if seen.contains(&normalize(choice)) {
+                        report(&format!("the choice \"{}\" appears twice", choice));
+                    }
+                    seen.push(normalize(choice));
+                }
+            }
+        }
+        problems
+    }
+    
+    // 按难度档位选题：优先选择难度最接近的题目
+    pub fn pick(&self, tier: u32, rng: &mut impl Rng) -> &PackQuestion {
+        let target = (1 + tier / 2).min(MAX_DIFFICULTY);
+        let distance = |question: &PackQuestion| question.difficulty.abs_diff(target);
+        let best = self.questions.iter().map(distance).min().unwrap_or(0);
+        let pool: Vec<&PackQuestion> = self.questions.iter().filter(|question| distance(question) == best).collect();
+        pool.choose(rng).copied().unwrap_or(&self.questions[0])
+    }
+}
+
+impl PackQuestion {
+    pub fn accepts(&self, given: &str) -> bool {
+        self.answers.iter().any(|answer| same_answer(answer, given))
+    }
+}
+
+// 题库题目：有选项的题目为选择题，否则输入答案
+pub struct PackChallenge {
+    pack_name: String,
+    question: PackQuestion,
+    input: String,
+    choices: Vec<String>,
+    selected: usize,
+    pool: Vec<String>, // 同一题库中其他题目的答案，使用手柄时用作错误选项
+}
+
+impl PackChallenge {
+    pub fn new(pack: &QuestionPack, tier: u32, multiple_choice: bool, rng: &mut impl Rng) -> Self {
+        let question = pack.pick(tier, rng).clone();
+        let mut pool: Vec<String> = Vec::new();
+        for other in &pack.questions {
+            let answer = &other.answers[0];
+            if !question.accepts(answer) && !pool.iter().any(|existing| same_answer(existing, answer)) {
+                pool.push(answer.clone());
+            }
+        }
+        let mut challenge = Self {
+            pack_name: pack.name.clone(),
+            choices: question.choices.clone(),
+            question,
+            input: String::new(),
+            selected: 0,
+            pool,
+        };
+        if multiple_choice {
+            challenge.offer_choices(rng);
+        }
+        challenge
+    }
+    
+    // 没有给出选项的题目，用题库中其他题目的答案凑成选择题；答案不够时仍然输入答案
+    fn offer_choices(&mut self, rng: &mut impl Rng) {
+        if !self.choices.is_empty() || self.pool.len() < 3 {
+            return;
+        }
+        let mut choices: Vec<String> = self.pool.choose_multiple(rng, 3).cloned().collect();
+        choices.push(self.question.answers[0].clone());
+        choices.shuffle(rng);
+        self.choices = choices;
+    }
+    
+    fn layout(&self) -> ChoiceLayout {
+        if self.choices.iter().all(|choice| choice.chars().count() <= 12) {
+            ChoiceLayout::Grid
+        } else {
+            ChoiceLayout::List
+        }
+    }
+}
+
+impl Challenge for PackChallenge {
+    fn kind(&self) -> ChallengeKind {
+        ChallengeKind::Arithmetic
+    }
+    
+    fn title(&self) -> String {
+        self.pack_name.clone()
+    }
+    
+    fn subtitle(&self) -> String {
+        let mut subtitle = trf(Text::SubtitleLevel, &[&self.question.difficulty]);
+        if !self.question.tags.is_empty() {
+            subtitle.push_str(&format!("  |  {}", self.question.tags.join(", ")));
+        }
+        subtitle
+    }
+    
+    fn prompt(&self) -> &str {
+        tr(Text::PromptPack)
+    }
+    
+    fn update(&mut self, input: &ChallengeInput, _dt: f32) -> Option<bool> {
+        if input.pad_used && self.choices.is_empty() {
+            self.offer_choices(&mut ::rand::thread_rng());
+            if !self.choices.is_empty() {
+                return None;
+            }
+        }
+        
+        if !self.choices.is_empty() {
+            let layout = self.layout();
+            let index = update_choice(&mut self.selected, self.choices.len(), layout, input)?;
+            return Some(self.question.accepts(&self.choices[index]));
+        }
+        
+        for &c in &input.chars {
+            if !c.is_control() && self.input.chars().count() < MAX_ANSWER_LEN {
+                self.input.push(c);
+            }
+        }
+        if input.backspace {
+            self.input.pop();
+        }
+        if input.confirm && !self.input.trim().is_empty() {
+            return Some(self.question.accepts(&self.input));
+        }
+        None
+    }
+    
+    fn draw(&self) -> f32 {
+        let screen = Layout::current();
+        // 题目较长时折成两行，仍然放不下再缩小字号
+        let max_width = screen.px((screen.size().x - 80.0).min(640.0));
+        let mut font_size = 28.0;
+        let mut lines = wrap_text(&self.question.prompt, max_width, screen.px(font_size));
+        while lines.len() > 2 && font_size > 16.0 {
+            font_size -= 2.0;
+            lines = wrap_text(&self.question.prompt, max_width, screen.px(font_size));
+        }
+        let line_height = font_size + 4.0;
+        let first_y = -10.0 - (lines.len() as f32 - 1.0) * line_height;
+        for (i, line) in lines.iter().enumerate() {
+            draw_centered(line, center_y(first_y + i as f32 * line_height), font_size, YELLOW);
+        }
+        
+        if self.choices.is_empty() {
+            draw_input_box(&self.input, center_y(30.0));
+            center_y(95.0)
+        } else {
+            draw_choices(&self.choices, self.selected, self.layout())
+        }
+    }
+    
+    fn hint(&self) -> &str {
+        if self.choices.is_empty() {
+            tr(Text::HintPackTyped)
+        } else {
+            tr(Text::HintPackChoices)
+        }
+    }
+    
+    fn correct_answer(&self) -> String {
+        self.question.answers[0].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn sample_pack_is_valid() {
+        let pack = QuestionPack::parse(SAMPLE_PACK.0, SAMPLE_PACK.1).unwrap();
+        assert!(pack.questions.len() >= 4);
+    }
+    
+    #[test]
+    fn json_packs_report_every_problem_with_its_question() {
+        let json = r#"{
+            "name": "Broken",
+            "questions": [
+                { "prompt": "2 + 2", "answers": ["4"] },
+                { "prompt": "", "answers": [] },
+                { "prompt": "Pick", "answers": ["a"], "choices": ["b", "c"], "difficulty": 9 }
+            ]
+        }"#;
+        let err = QuestionPack::parse("broken.json", json).unwrap_err();
+        assert_eq!(
+            err.problems,
+            vec![
+                "question 2: the prompt is empty",
+                "question 2: no accepted answers",
+                "question 3: difficulty must be between 1 and 5",
+                "question 3: none of the choices is an accepted answer",
+            ]
+        );
+        assert!(err.to_string().starts_with("broken.json: question 2"));
+    }
+    
+    #[test]
+    fn misspelled_fields_are_rejected() {
+        let toml = "name = \"Typo\"\n[[questions]]\nprompt = \"1 + 1\"\nanswer = [\"2\"]\n";
+        let err = QuestionPack::parse("typo.toml", toml).unwrap_err();
+        assert!(err.problems[0].contains("unknown field `answer`"), "{}", err);
+        assert!(QuestionPack::parse("notes.txt", "").is_err());
+    }
+    
+    #[test]
+    fn answers_ignore_case_and_compare_numbers_by_value() {
+        let question = PackQuestion {
+            prompt: "?".to_string(),
+            answers: vec!["Carbon  Dioxide".to_string(), "0.5".to_string()],
+            choices: Vec::new(),
+            tags: Vec::new(),
+            difficulty: 1,
+        };
+        assert!(question.accepts(" carbon dioxide "));
+        assert!(question.accepts("1/2"));
+        assert!(!question.accepts("oxygen"));
+        // 超出范围的数字不会溢出，按文字比较
+        assert!(!question.accepts("999999999999999999.5"));
+    }
+}
\ No newline at end of file
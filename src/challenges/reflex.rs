@@ -0,0 +1,92 @@
+use super::{center_y, draw_centered, Challenge, ChallengeInput, ChallengeKind};
+use crate::i18n::{tr, trf, Text};
+use crate::ui::Layout;
+use ::rand::Rng;
+use macroquad::prelude::*;
+
+// 开始的一小段时间内忽略按键，避免死亡时还按着的射击键直接判负
+const GRACE_PERIOD: f32 = 0.5;
+
+// 反应小游戏：等信号灯变绿后尽快按下确认键
+pub struct ReflexChallenge {
+    delay: f32,    // 距离变绿的剩余时间
+    waited: f32,   // 已经等待的时间
+    reaction: f32, // 变绿后经过的时间
+    window: f32,   // 允许的反应时间
+}
+
+impl ReflexChallenge {
+    pub fn new(rng: &mut impl Rng, tier: u32) -> Self {
+        Self {
+            delay: rng.gen_range(1.0..3.0),
+            waited: 0.0,
+            reaction: 0.0,
+            window: (0.9 - 0.05 * tier as f32).max(0.45),
+        }
+    }
+    
+    fn is_green(&self) -> bool {
+        self.delay <= 0.0
+    }
+}
+
+impl Challenge for ReflexChallenge {
+    fn kind(&self) -> ChallengeKind {
+        ChallengeKind::Reflex
+    }
+    
+    fn subtitle(&self) -> String {
+        trf(Text::SubtitleReactionWindow, &[&format!("{:.2}", self.window)])
+    }
+    
+    fn prompt(&self) -> &str {
+        tr(Text::PromptReflex)
+    }
+    
+    fn update(&mut self, input: &ChallengeInput, dt: f32) -> Option<bool> {
+        let pressed = input.confirm || input.clicked;
+        if !self.is_green() {
+            self.waited += dt;
+            if pressed && self.waited > GRACE_PERIOD {
+                // 抢跑
+                return Some(false);
+            }
+            // 变绿之后才开始计算反应时间，玩家要等下一帧才能看到绿灯
+            self.delay -= dt;
+            return None;
+        }
+        
+        self.reaction += dt;
+        if pressed {
+            Some(self.reaction <= self.window)
+        } else if self.reaction > self.window {
+            Some(false)
+        } else {
+            None
+        }
+    }
+    
+    fn draw(&self) -> f32 {
+        let screen = Layout::current();
+        let (color, label) = if self.is_green() {
+            (GREEN, tr(Text::ReflexNow))
+        } else if self.waited < GRACE_PERIOD {
+            (ORANGE, tr(Text::ReflexGetReady))
+        } else {
+            (RED, tr(Text::ReflexWait))
+        };
+        let (x, radius) = (screen.center().x, screen.px(40.0));
+        draw_circle(x, center_y(20.0), radius, color);
+        draw_circle_lines(x, center_y(20.0), radius, 3.0, WHITE);
+        draw_centered(label, center_y(90.0), 28.0, color);
+        center_y(120.0)
+    }
+    
+    fn hint(&self) -> &str {
+        tr(Text::HintReflex)
+    }
+    
+    fn correct_answer(&self) -> String {
+        trf(Text::ReflexCorrectAnswer, &[&format!("{:.2}", self.window)])
+    }
+}
\ No newline at end of file
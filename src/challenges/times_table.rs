@@ -0,0 +1,67 @@
+use super::{center_y, draw_centered, Challenge, ChallengeInput, ChallengeKind};
+use crate::i18n::{tr, trf, Text};
+use crate::math_challenge::{Answer, MathChallenge, Problem, Topic};
+use ::rand::Rng;
+use macroquad::prelude::*;
+
+// 乘法口诀：从某一张乘法表中出题，并提示前两行帮助玩家接着往下数
+pub struct TimesTableChallenge {
+    table: i64,
+    factor: i64,
+    math: MathChallenge,
+}
+
+impl TimesTableChallenge {
+    pub fn new(rng: &mut impl Rng, tier: u32, multiple_choice: bool) -> Self {
+        let table = rng.gen_range(2..=(5 + tier as i64).min(12));
+        let factor = rng.gen_range(3..=12);
+        let mut math = MathChallenge::new(Problem {
+            topic: Topic::Multiplication,
+            tier,
+            text: format!("{} * {} = ?", table, factor),
+            answer: Answer::integer(table * factor),
+        });
+        if multiple_choice {
+            math.offer_choices(rng);
+        }
+        Self { table, factor, math }
+    }
+}
+
+impl Challenge for TimesTableChallenge {
+    fn kind(&self) -> ChallengeKind {
+        ChallengeKind::TimesTables
+    }
+    
+    fn subtitle(&self) -> String {
+        trf(Text::SubtitleTimesTable, &[&self.table])
+    }
+    
+    fn prompt(&self) -> &str {
+        tr(Text::PromptTimesTable)
+    }
+    
+    fn update(&mut self, input: &ChallengeInput, dt: f32) -> Option<bool> {
+        self.math.update(input, dt)
+    }
+    
+    fn draw(&self) -> f32 {
+        let rows: Vec<String> = (self.factor - 2..self.factor)
+            .map(|factor| format!("{} * {} = {}", self.table, factor, self.table * factor))
+            .collect();
+        draw_centered(&rows.join("    "), center_y(-38.0), 18.0, GRAY);
+        self.math.draw()
+    }
+    
+    fn hint(&self) -> &str {
+        self.math.hint()
+    }
+    
+    fn correct_answer(&self) -> String {
+        self.math.correct_answer()
+    }
+    
+    fn learning_record(&self) -> Option<(&Problem, &str)> {
+        self.math.learning_record()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,250 @@
+use super::choices::{draw_choices, update_choice, ChoiceLayout};
+use super::{center_y, draw_centered, draw_input_box, Challenge, ChallengeInput, ChallengeKind};
+use crate::i18n::{tr, trf, Text};
+use ::rand::seq::SliceRandom;
+use ::rand::Rng;
+use macroquad::prelude::*;
+use serde::Deserialize;
+
+// 单词表随程序一起编译，按单词长度区分难度
+const WORDS: &str = include_str!("../../assets/words.json");
+
+#[derive(Clone, Debug, Deserialize)]
+struct WordEntry {
+    word: String,
+    definition: String,
+}
+
+fn load_words() -> Vec<WordEntry> {
+    serde_json::from_str(WORDS).expect("assets/words.json 格式错误")
+}
+
+// 按难度档位挑一个单词：档位越高允许的单词越长
+fn pick_word(words: &[WordEntry], rng: &mut impl Rng, tier: u32) -> WordEntry {
+    let max_len = 5 + tier as usize;
+    let pool: Vec<&WordEntry> = words.iter().filter(|entry| entry.word.len() <= max_len).collect();
+    let pool = if pool.len() < 4 { words.iter().collect() } else { pool };
+    (*pool.choose(rng).expect("单词表为空")).clone()
+}
+
+// 打乱字母顺序，尽量不和原词相同
+fn scramble(word: &str, rng: &mut impl Rng) -> String {
+    let mut letters: Vec<char> = word.chars().collect();
+    for _ in 0..10 {
+        letters.shuffle(rng);
+        if letters.iter().collect::<String>() != word {
+            break;
+        }
+    }
+    letters.iter().collect()
+}
+
+// 常见的拼写错误：交换相邻字母、重复字母、漏掉字母、替换元音
+fn misspellings(word: &str, count: usize, rng: &mut impl Rng) -> Vec<String> {
+    let letters: Vec<char> = word.chars().collect();
+    let mut results: Vec<String> = Vec::new();
+    for _ in 0..100 {
+        if results.len() >= count {
+            break;
+        }
+        let mut wrong = letters.clone();
+        let index = rng.gen_range(0..wrong.len());
+        match rng.gen_range(0..4) {
+            0 if index + 1 < wrong.len() => wrong.swap(index, index + 1),
+            1 => wrong.insert(index, wrong[index]),
+            2 if wrong.len() > 3 => {
+                wrong.remove(index);
+            }
+            _ => {
+                let vowels = ['a', 'e', 'i', 'o', 'u'];
+                if let Some(position) = wrong.iter().position(|c| vowels.contains(c)) {
+                    wrong[position] = *vowels.choose(rng).unwrap_or(&'e');
+                }
+            }
+        }
+        let wrong: String = wrong.into_iter().collect();
+        if wrong != word && !results.contains(&wrong) {
+            results.push(wrong);
+        }
+    }
+    results
+}
+
+// 拼写：根据释义把打乱的字母拼回单词
+pub struct SpellingChallenge {
+    word: String,
+    definition: String,
+    scrambled: String,
+    input: String,
+    pub choices: Vec<String>, // 使用手柄时改为选择正确的拼写
+    selected: usize,
+}
+
+impl SpellingChallenge {
+    pub fn new(rng: &mut impl Rng, tier: u32) -> Self {
+        let entry = pick_word(&load_words(), rng, tier);
+        Self {
+            scrambled: scramble(&entry.word, rng),
+            word: entry.word,
+            definition: entry.definition,
+            input: String::new(),
+            choices: Vec::new(),
+            selected: 0,
+        }
+    }
+    
+    fn offer_choices(&mut self, rng: &mut impl Rng) {
+        let mut choices = misspellings(&self.word, 3, rng);
+        choices.push(self.word.clone());
+        choices.shuffle(rng);
+        self.choices = choices;
+        self.selected = 0;
+    }
+}
+
+impl Challenge for SpellingChallenge {
+    fn kind(&self) -> ChallengeKind {
+        ChallengeKind::Spelling
+    }
+    
+    fn subtitle(&self) -> String {
+        trf(Text::SubtitleLetters, &[&self.word.chars().count()])
+    }
+    
+    fn prompt(&self) -> &str {
+        tr(Text::PromptSpelling)
+    }
+    
+    fn update(&mut self, input: &ChallengeInput, _dt: f32) -> Option<bool> {
+        if input.pad_used && self.choices.is_empty() {
+            self.offer_choices(&mut ::rand::thread_rng());
+            return None;
+        }
+        
+        if !self.choices.is_empty() {
+            let index = update_choice(&mut self.selected, self.choices.len(), ChoiceLayout::Grid, input)?;
+            return Some(self.choices[index] == self.word);
+        }
+        
+        for &c in &input.chars {
+            if c.is_alphabetic() && self.input.chars().count() < self.word.chars().count() + 4 {
+                self.input.extend(c.to_lowercase());
+            }
+        }
+        if input.backspace {
+            self.input.pop();
+        }
+        if input.confirm && !self.input.is_empty() {
+            return Some(self.input == self.word);
+        }
+        None
+    }
+    
+    fn draw(&self) -> f32 {
+        draw_centered(&self.definition, center_y(-35.0), 18.0, LIGHTGRAY);
+        if self.choices.is_empty() {
+            let letters: Vec<String> = self.scrambled.to_uppercase().chars().map(String::from).collect();
+            draw_centered(&letters.join(" "), center_y(-5.0), 32.0, YELLOW);
+            draw_input_box(&self.input.to_uppercase(), center_y(30.0));
+            center_y(95.0)
+        } else {
+            draw_choices(&self.choices, self.selected, ChoiceLayout::Grid)
+        }
+    }
+    
+    fn hint(&self) -> &str {
+        if self.choices.is_empty() {
+            tr(Text::HintSpellingTyped)
+        } else {
+            tr(Text::HintSpellingChoices)
+        }
+    }
+    
+    fn correct_answer(&self) -> String {
+        self.word.clone()
+    }
+}
+
+// 词汇：为单词选择正确的释义
+pub struct VocabularyChallenge {
+    word: String,
+    definitions: Vec<String>,
+    answer: usize,
+    selected: usize,
+}
+
+impl VocabularyChallenge {
+    pub fn new(rng: &mut impl Rng, tier: u32) -> Self {
+        let words = load_words();
+        let entry = pick_word(&words, rng, tier);
+        let mut definitions: Vec<String> = words
+            .iter()
+            .filter(|other| other.word != entry.word)
+            .map(|other| other.definition.clone())
+            .collect::<Vec<_>>()
+            .choose_multiple(rng, 3)
+            .cloned()
+            .collect();
+        definitions.push(entry.definition.clone());
+        definitions.shuffle(rng);
+        let answer = definitions.iter().position(|definition| *definition == entry.definition).unwrap_or(0);
+        Self {
+            word: entry.word,
+            definitions,
+            answer,
+            selected: 0,
+        }
+    }
+}
+
+impl Challenge for VocabularyChallenge {
+    fn kind(&self) -> ChallengeKind {
+        ChallengeKind::Vocabulary
+    }
+    
+    fn prompt(&self) -> &str {
+        tr(Text::PromptVocabulary)
+    }
+    
+    fn update(&mut self, input: &ChallengeInput, _dt: f32) -> Option<bool> {
+        let index = update_choice(&mut self.selected, self.definitions.len(), ChoiceLayout::List, input)?;
+        Some(index == self.answer)
+    }
+    
+    fn draw(&self) -> f32 {
+        draw_centered(&self.word.to_uppercase(), center_y(-10.0), 32.0, YELLOW);
+        draw_choices(&self.definitions, self.selected, ChoiceLayout::List)
+    }
+    
+    fn hint(&self) -> &str {
+        tr(Text::HintChoices)
+    }
+    
+    fn correct_answer(&self) -> String {
+        self.definitions[self.answer].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+    
+    #[test]
+    fn word_list_parses() {
+        let words = load_words();
+        assert!(words.len() >= 4);
+        assert!(words.iter().all(|entry| entry.word.chars().all(|c| c.is_ascii_lowercase())));
+    }
+    
+    #[test]
+    fn misspellings_differ_from_the_word() {
+        let mut rng = StdRng::seed_from_u64(4);
+        for entry in load_words() {
+            let wrong = misspellings(&entry.word, 3, &mut rng);
+            assert_eq!(wrong.len(), 3, "{}", entry.word);
+            assert!(!wrong.contains(&entry.word));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+
+// 实体的稳定编号。槽位被复用时代数加一，旧编号因此失效，不会误指向新实体
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EntityId {
+    index: u32,
+    generation: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+// 按代数区分的实体容器。删除实体不会移动其他实体，编号在实体存活期间保持不变
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn insert(&mut self, value: T) -> EntityId {
+        self.len += 1;
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.value = Some(value);
+            return EntityId { index, generation: slot.generation };
+        }
+        
+        let index = self.slots.len() as u32;
+        self.slots.push(Slot { generation: 0, value: Some(value) });
+        EntityId { index, generation: 0 }
+    }
+    
+    pub fn remove(&mut self, id: EntityId) -> Option<T> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        self.len -= 1;
+        Some(value)
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    // 清空所有实体但保留槽位，存活实体的槽位代数加一，清空前的编号不会匹配之后插入的实体
+    pub fn clear(&mut self) {
+        for slot in &mut self.slots {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+        }
+        self.free = (0..self.slots.len() as u32).rev().collect();
+        self.len = 0;
+    }
+    
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EntityId, &mut T)> {
+        self.slots.iter_mut().enumerate().filter_map(|(index, slot)| {
+            let id = EntityId { index: index as u32, generation: slot.generation };
+            slot.value.as_mut().map(|value| (id, value))
+        })
+    }
+    
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().filter_map(|slot| slot.value.as_ref())
+    }
+    
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.slots.iter_mut().filter_map(|slot| slot.value.as_mut())
+    }
+    
+    // 移除所有不满足条件的实体
+    pub fn retain(&mut self, mut keep: impl FnMut(EntityId, &mut T) -> bool) {
+        let removed: Vec<EntityId> = self
+            .iter_mut()
+            .filter_map(|(id, value)| (!keep(id, value)).then_some(id))
+            .collect();
+        for id in removed {
+            self.remove(id);
+        }
+    }
+    
+    // 检查从存档读取的数据是否一致：空闲列表恰好包含所有空槽位
+    pub fn is_consistent(&self) -> bool {
+        let empty = self.slots.iter().filter(|slot| slot.value.is_none()).count();
+        let mut free = self.free.clone();
+        free.sort_unstable();
+        free.dedup();
+        empty == self.free.len()
+            && free.len() == self.free.len()
+            && free.iter().all(|&index| self.slots.get(index as usize).is_some_and(|slot| slot.value.is_none()))
+            && self.len == self.slots.len() - empty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn stale_ids_do_not_match_reused_slots() {
+        let mut arena = Arena::new();
+        let first = arena.insert("first");
+        assert_eq!(arena.remove(first), Some("first"));
+        assert_eq!(arena.remove(first), None);
+        
+        // 同一个槽位被新实体复用，旧编号不能删除新实体
+        let second = arena.insert("second");
+        assert_eq!(second.index, first.index);
+        assert_ne!(second, first);
+        assert_eq!(arena.remove(first), None);
+        assert_eq!(arena.len(), 1);
+        assert_eq!(arena.remove(second), Some("second"));
+        assert!(arena.is_empty());
+    }
+    
+    #[test]
+    fn clear_invalidates_existing_ids() {
+        let mut arena = Arena::new();
+        let ids: Vec<EntityId> = (0..3).map(|i| arena.insert(i)).collect();
+        arena.remove(ids[1]);
+        arena.clear();
+        assert!(arena.is_empty());
+        assert!(arena.is_consistent());
+        
+        let reused: Vec<EntityId> = (10..13).map(|i| arena.insert(i)).collect();
+        assert_eq!(arena.slots.len(), 3);
+        for id in &ids {
+            assert!(!reused.contains(id));
+            assert_eq!(arena.remove(*id), None);
+        }
+        assert_eq!(arena.len(), 3);
+    }
+    
+    #[test]
+    fn retain_removes_rejected_entities_and_keeps_ids() {
+        let mut arena = Arena::new();
+        let ids: Vec<EntityId> = (0..6).map(|i| arena.insert(i)).collect();
+        arena.retain(|_, value| {
+            *value *= 10;
+            *value % 20 == 0
+        });
+        assert_eq!(arena.values().copied().collect::<Vec<_>>(), vec![0, 20, 40]);
+        assert_eq!(arena.len(), 3);
+        assert!(arena.is_consistent());
+        assert_eq!(arena.remove(ids[1]), None);
+        assert_eq!(arena.remove(ids[2]), Some(20));
+    }
+    
+    #[test]
+    fn consistency_check_rejects_broken_free_lists() {
+        let mut arena = Arena::new();
+        let id = arena.insert(1);
+        arena.insert(2);
+        arena.remove(id);
+        assert!(arena.is_consistent());
+        
+        let mut missing = arena.clone();
+        missing.free.clear();
+        assert!(!missing.is_consistent());
+        
+        let mut duplicated = arena.clone();
+        duplicated.free.push(id.index);
+        assert!(!duplicated.is_consistent());
+        
+        let mut occupied = arena.clone();
+        occupied.free = vec![1];
+        assert!(!occupied.is_consistent());
+        
+        let mut wrong_len = arena.clone();
+        wrong_len.len = 2;
+        assert!(!wrong_len.is_consistent());
+    }
+}
\ No newline at end of file
@@ -1,16 +1,38 @@
-use super::{Position, Velocity};
+use super::{EntityId, Position, Velocity, WORLD_HEIGHT, WORLD_WIDTH};
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BulletKind {
+    Shell,  // 普通炮弹，命中即造成伤害
+    Mortar, // 迫击炮弹，越过障碍物，落地后爆炸
+    Mine,   // 地雷，静止不动，有坦克靠近时爆炸
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Bullet {
     pub position: Position,
+    pub prev_position: Position,
     pub velocity: Velocity,
     pub damage: i32,
     pub size: f32,
+    #[serde(with = "super::color_serde")]
     pub color: Color,
     pub from_player: bool,
     pub lifetime: f32,
     pub max_lifetime: f32,
+    pub kind: BulletKind,
+    // 同一次开火射出的子弹编号相同，用于统计一次齐射的击杀数
+    #[serde(default)]
+    pub volley: u32,
+    // 还能穿透的坦克数和还能反弹的次数，来自本局选择的强化
+    #[serde(default)]
+    pub pierce: u32,
+    #[serde(default)]
+    pub ricochet: u32,
+    // 已经穿过的坦克，同一辆坦克只结算一次
+    #[serde(default)]
+    pub pierced: Vec<EntityId>,
 }
 
 impl Bullet {
@@ -18,6 +40,7 @@ impl Bullet {
         let speed = 300.0;
         Self {
             position: Position::new(x, y),
+            prev_position: Position::new(x, y),
             velocity: Velocity::from_angle(angle, speed),
             damage: 25,
             size: 3.0,
@@ -25,6 +48,11 @@ impl Bullet {
             from_player,
             lifetime: 0.0,
             max_lifetime: 3.0,
+            kind: BulletKind::Shell,
+            volley: 0,
+            pierce: 0,
+            ricochet: 0,
+            pierced: Vec::new(),
         }
     }
     
@@ -33,6 +61,7 @@ impl Bullet {
         let actual_angle = angle + spread;
         Self {
             position: Position::new(x, y),
+            prev_position: Position::new(x, y),
             velocity: Velocity::from_angle(actual_angle, speed),
             damage: 15,
             size: 2.5,
@@ -40,30 +69,177 @@ impl Bullet {
             from_player,
             lifetime: 0.0,
             max_lifetime: 2.5,
+            kind: BulletKind::Shell,
+            volley: 0,
+            pierce: 0,
+            ricochet: 0,
+            pierced: Vec::new(),
+        }
+    }
+    
+    // 重型炮弹：飞得慢，体积大，伤害高
+    pub fn new_heavy(x: f32, y: f32, angle: f32, from_player: bool) -> Self {
+        let speed = 220.0;
+        Self {
+            position: Position::new(x, y),
+            prev_position: Position::new(x, y),
+            velocity: Velocity::from_angle(angle, speed),
+            damage: 70,
+            size: 5.5,
+            color: if from_player { Color::new(1.0, 0.6, 0.2, 1.0) } else { ORANGE },
+            from_player,
+            lifetime: 0.0,
+            max_lifetime: 3.5,
+            kind: BulletKind::Shell,
+            volley: 0,
+            pierce: 0,
+            ricochet: 0,
+            pierced: Vec::new(),
+        }
+    }
+    
+    pub fn new_mortar(x: f32, y: f32, target: Position, from_player: bool) -> Self {
+        let speed = 220.0;
+        let dx = target.x - x;
+        let dy = target.y - y;
+        let distance = (dx * dx + dy * dy).sqrt().max(1.0);
+        Self {
+            position: Position::new(x, y),
+            prev_position: Position::new(x, y),
+            velocity: Velocity::new(dx / distance * speed, dy / distance * speed),
+            damage: 0, // 伤害由落地爆炸造成
+            size: 5.0,
+            color: if from_player { GOLD } else { MAROON },
+            from_player,
+            lifetime: 0.0,
+            // 飞行时间正好到达目标点
+            max_lifetime: distance / speed,
+            kind: BulletKind::Mortar,
+            volley: 0,
+            pierce: 0,
+            ricochet: 0,
+            pierced: Vec::new(),
+        }
+    }
+    
+    pub fn new_mine(x: f32, y: f32, from_player: bool) -> Self {
+        Self {
+            position: Position::new(x, y),
+            prev_position: Position::new(x, y),
+            velocity: Velocity::new(0.0, 0.0),
+            damage: 0, // 伤害由爆炸造成
+            size: 7.0,
+            color: if from_player { YELLOW } else { RED },
+            from_player,
+            lifetime: 0.0,
+            max_lifetime: 30.0,
+            kind: BulletKind::Mine,
+            volley: 0,
+            pierce: 0,
+            ricochet: 0,
+            pierced: Vec::new(),
         }
     }
     
     pub fn update(&mut self, dt: f32) -> bool {
+        self.prev_position = self.position;
         self.position.x += self.velocity.x * dt;
         self.position.y += self.velocity.y * dt;
         self.lifetime += dt;
         
-        // 检查是否超出屏幕边界或生命周期结束
-        let screen_width = screen_width();
-        let screen_height = screen_height();
+        // 检查是否超出世界边界或生命周期结束
+        let world_width = WORLD_WIDTH;
+        let world_height = WORLD_HEIGHT;
         
         self.position.x >= 0.0
-            && self.position.x <= screen_width
+            && self.position.x <= world_width
             && self.position.y >= 0.0
-            && self.position.y <= screen_height
+            && self.position.y <= world_height
             && self.lifetime < self.max_lifetime
     }
     
-    pub fn draw(&self) {
-        draw_circle(self.position.x, self.position.y, self.size, self.color);
+    // 撞到障碍物时反弹：根据上一帧的位置判断撞到的是竖直还是水平的边
+    pub fn bounce(&mut self, obstacle: &super::Obstacle) {
+        let prev = self.prev_position;
+        let outside_x = prev.x < obstacle.position.x || prev.x > obstacle.position.x + obstacle.width;
+        let outside_y = prev.y < obstacle.position.y || prev.y > obstacle.position.y + obstacle.height;
+        if outside_x {
+            self.velocity.x = -self.velocity.x;
+        }
+        if outside_y || !outside_x {
+            self.velocity.y = -self.velocity.y;
+        }
+        self.position = prev;
+        self.ricochet -= 1;
+    }
+    
+    // 是否以爆炸而不是直接命中造成伤害
+    pub fn is_explosive(&self) -> bool {
+        self.kind != BulletKind::Shell
+    }
+    
+    // 迫击炮弹在空中时不与坦克和障碍物碰撞
+    pub fn is_airborne(&self) -> bool {
+        self.kind == BulletKind::Mortar
+    }
+    
+    // 地雷布设后需要短暂时间才会激活
+    pub fn is_armed(&self) -> bool {
+        self.kind != BulletKind::Mine || self.lifetime > 1.0
+    }
+    
+    // 碰撞判定半径，地雷的触发范围大于其绘制尺寸
+    pub fn trigger_radius(&self) -> f32 {
+        match self.kind {
+            BulletKind::Mine => self.size + 8.0,
+            _ => self.size,
+        }
+    }
+    
+    pub fn render_position(&self, alpha: f32) -> Position {
+        Position::new(
+            self.prev_position.x + (self.position.x - self.prev_position.x) * alpha,
+            self.prev_position.y + (self.position.y - self.prev_position.y) * alpha,
+        )
+    }
+    
+    pub fn draw(&self, alpha: f32) {
+        let pos = self.render_position(alpha);
+        
+        match self.kind {
+            BulletKind::Shell => {
+                draw_circle(pos.x, pos.y, self.size, self.color);
+            }
+            BulletKind::Mortar => {
+                // 抛物线飞行：中途最高，绘制时放大并偏移阴影
+                let progress = (self.lifetime / self.max_lifetime).clamp(0.0, 1.0);
+                let height = (progress * std::f32::consts::PI).sin();
+                draw_circle(
+                    pos.x,
+                    pos.y,
+                    self.size * 0.8,
+                    Color::new(0.0, 0.0, 0.0, 0.3),
+                );
+                draw_circle(
+                    pos.x,
+                    pos.y - height * 25.0,
+                    self.size * (1.0 + height * 0.6),
+                    self.color,
+                );
+            }
+            BulletKind::Mine => {
+                draw_circle(pos.x, pos.y, self.size, DARKGRAY);
+                // 激活后指示灯闪烁
+                let blink = !self.is_armed() || (self.lifetime * 4.0).sin() > 0.0;
+                if blink {
+                    draw_circle(pos.x, pos.y, self.size * 0.4, self.color);
+                }
+                draw_circle_lines(pos.x, pos.y, self.size, 1.0, BLACK);
+            }
+        }
     }
     
     pub fn collides_with_circle(&self, pos: &Position, radius: f32) -> bool {
-        self.position.distance_to(pos) < self.size + radius
+        self.position.distance_to(pos) < self.trigger_radius() + radius
     }
 }
\ No newline at end of file
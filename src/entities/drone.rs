@@ -0,0 +1,80 @@
+use super::{Arena, Bullet, Enemy, Position};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 环绕玩家飞行的无人机，自动攻击射程内最近的敌人
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Drone {
+    pub position: Position,
+    pub prev_position: Position,
+    pub orbit_angle: f32,
+    pub shot_timer: f32,
+}
+
+impl Drone {
+    const ORBIT_RADIUS: f32 = 45.0;
+    const ORBIT_SPEED: f32 = 2.0; // 弧度每秒
+    const FIRE_INTERVAL: f32 = 0.9;
+    const RANGE: f32 = 320.0;
+    const DAMAGE: i32 = 12;
+    
+    pub fn new(owner: Position, orbit_angle: f32) -> Self {
+        let position = Self::orbit_position(owner, orbit_angle);
+        Self {
+            position,
+            prev_position: position,
+            orbit_angle,
+            shot_timer: Self::FIRE_INTERVAL,
+        }
+    }
+    
+    fn orbit_position(owner: Position, angle: f32) -> Position {
+        Position::new(
+            owner.x + angle.cos() * Self::ORBIT_RADIUS,
+            owner.y + angle.sin() * Self::ORBIT_RADIUS,
+        )
+    }
+    
+    // 更新位置，冷却结束且射程内有敌人时返回射出的子弹
+    pub fn update(&mut self, owner: Position, enemies: &Arena<Enemy>, dt: f32) -> Option<Bullet> {
+        self.prev_position = self.position;
+        self.orbit_angle = (self.orbit_angle + Self::ORBIT_SPEED * dt) % std::f32::consts::TAU;
+        self.position = Self::orbit_position(owner, self.orbit_angle);
+        self.shot_timer = (self.shot_timer - dt).max(0.0);
+        if self.shot_timer > 0.0 {
+            return None;
+        }
+        
+        let target = enemies
+            .values()
+            .filter(|enemy| enemy.tank.health > 0)
+            .map(|enemy| enemy.tank.position)
+            .filter(|position| position.distance_to(&self.position) <= Self::RANGE)
+            .min_by(|a, b| a.distance_to(&self.position).total_cmp(&b.distance_to(&self.position)))?;
+        
+        self.shot_timer = Self::FIRE_INTERVAL;
+        let angle = (target.y - self.position.y).atan2(target.x - self.position.x);
+        let mut bullet = Bullet::new(self.position.x, self.position.y, angle, true);
+        bullet.damage = Self::DAMAGE;
+        bullet.size = 2.5;
+        bullet.color = SKYBLUE;
+        Some(bullet)
+    }
+    
+    // 无人机在重新排列时均匀分布在玩家周围
+    pub fn spread(drones: &mut [Drone]) {
+        let base = drones.first().map(|drone| drone.orbit_angle).unwrap_or(0.0);
+        let count = drones.len() as f32;
+        for (i, drone) in drones.iter_mut().enumerate() {
+            drone.orbit_angle = base + std::f32::consts::TAU * i as f32 / count;
+        }
+    }
+    
+    pub fn draw(&self, alpha: f32) {
+        let x = self.prev_position.x + (self.position.x - self.prev_position.x) * alpha;
+        let y = self.prev_position.y + (self.position.y - self.prev_position.y) * alpha;
+        draw_circle(x, y, 6.0, DARKGRAY);
+        draw_circle(x, y, 4.0, SKYBLUE);
+        draw_circle_lines(x, y, 6.0, 1.0, WHITE);
+    }
+}
\ No newline at end of file
@@ -1,11 +1,29 @@
-use super::{Position, Tank};
+use super::{Position, Tank, WORLD_HEIGHT, WORLD_WIDTH};
 use macroquad::prelude::*;
 use ::rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
 
+// 敌方坦克及其AI，作为一个实体存放在 Arena 中
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Enemy {
+    pub tank: Tank,
+    pub ai: EnemyAI,
+}
+
+impl Enemy {
+    pub fn new(tank: Tank, difficulty: f32) -> Self {
+        Self {
+            tank,
+            ai: EnemyAI::new_with_difficulty(difficulty),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct EnemyAI {
     #[allow(dead_code)]
     pub target_position: Position,
-    pub last_direction_change: f64,
+    pub time_since_direction_change: f64,
     pub direction_change_interval: f64,
     pub aggression_level: f32,
     pub difficulty: f32,
@@ -35,7 +53,7 @@ impl EnemyAI {
         
         Self {
             target_position: Position::new(0.0, 0.0),
-            last_direction_change: get_time(),
+            time_since_direction_change: 0.0,
             direction_change_interval: rng.gen_range(2.0..5.0), // 增加方向改变间隔
             aggression_level: base_aggression,
             difficulty,
@@ -44,11 +62,11 @@ impl EnemyAI {
         }
     }
     
-    pub fn update(&mut self, enemy_tank: &mut Tank, player_tank: &Tank, obstacles: &[super::Obstacle]) {
-        let current_time = get_time();
+    pub fn update(&mut self, enemy_tank: &mut Tank, player_tank: &Tank, obstacles: &[super::Obstacle], dt: f32) {
+        self.time_since_direction_change += dt as f64;
         
         // 更新玩家速度估计（用于预测瞄准）
-        self.update_player_velocity_estimate(player_tank);
+        self.update_player_velocity_estimate(player_tank, dt);
         
         // 计算到玩家的距离
         let distance_to_player = enemy_tank.position.distance_to(&player_tank.position);
@@ -89,7 +107,7 @@ impl EnemyAI {
                 self.approach_player(enemy_tank, player_tank);
             } else {
                 // 在合适距离内，使用智能战术移动
-                if current_time - self.last_direction_change > self.direction_change_interval {
+                if self.time_since_direction_change > self.direction_change_interval {
                     if self.difficulty <= 1.0 {
                         // 容易模式：使用侧向移动战术
                         self.tactical_movement(enemy_tank, player_tank);
@@ -97,7 +115,7 @@ impl EnemyAI {
                         // 其他模式：随机移动
                         self.random_movement(enemy_tank);
                     }
-                    self.last_direction_change = current_time;
+                    self.time_since_direction_change = 0.0;
                     let interval_range = if self.difficulty <= 1.0 {
                         2.0..4.0 // 容易模式：更频繁的战术调整
                     } else {
@@ -108,9 +126,9 @@ impl EnemyAI {
             }
         } else {
             // 不追击时进行随机移动
-            if current_time - self.last_direction_change > self.direction_change_interval {
+            if self.time_since_direction_change > self.direction_change_interval {
                 self.random_movement(enemy_tank);
-                self.last_direction_change = current_time;
+                self.time_since_direction_change = 0.0;
                 self.direction_change_interval = rng.gen_range(2.0..5.0);
             }
         }
@@ -127,10 +145,8 @@ impl EnemyAI {
         }
         
         // 决定是否射击
-        if self.should_shoot(enemy_tank, player_tank, distance_to_player) {
-            if enemy_tank.can_shoot() {
-                enemy_tank.shoot();
-            }
+        if self.should_shoot(enemy_tank, player_tank, distance_to_player, dt) && enemy_tank.can_shoot() {
+            enemy_tank.shoot();
         }
     }
     
@@ -201,17 +217,22 @@ impl EnemyAI {
         enemy_tank.angle = dy.atan2(dx);
     }
     
-    fn update_player_velocity_estimate(&mut self, player_tank: &Tank) {
-        // 计算玩家速度
-        let dx = player_tank.position.x - self.last_player_position.x;
-        let dy = player_tank.position.y - self.last_player_position.y;
+    fn update_player_velocity_estimate(&mut self, player_tank: &Tank, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
         
-        // 平滑速度估计
-        self.player_velocity_estimate.0 = self.player_velocity_estimate.0 * 0.7 + dx * 0.3;
-        self.player_velocity_estimate.1 = self.player_velocity_estimate.1 * 0.7 + dy * 0.3;
+        // 计算玩家速度（单位/秒）
+        let vx = (player_tank.position.x - self.last_player_position.x) / dt;
+        let vy = (player_tank.position.y - self.last_player_position.y) / dt;
+        
+        // 平滑速度估计，平滑系数按时间换算，与帧率无关
+        let blend = 1.0 - 0.7f32.powf(dt * 60.0);
+        self.player_velocity_estimate.0 += (vx - self.player_velocity_estimate.0) * blend;
+        self.player_velocity_estimate.1 += (vy - self.player_velocity_estimate.1) * blend;
         
         // 更新上一帧玩家位置
-        self.last_player_position = player_tank.position.clone();
+        self.last_player_position = player_tank.position;
     }
     
     fn aim_at_player_predictive(&mut self, enemy_tank: &mut Tank, player_tank: &Tank) {
@@ -221,8 +242,8 @@ impl EnemyAI {
         let flight_time = distance / bullet_speed;
         
         // 预测玩家位置
-        let predicted_x = player_tank.position.x + self.player_velocity_estimate.0 * flight_time * 60.0; // 60fps
-        let predicted_y = player_tank.position.y + self.player_velocity_estimate.1 * flight_time * 60.0;
+        let predicted_x = player_tank.position.x + self.player_velocity_estimate.0 * flight_time;
+        let predicted_y = player_tank.position.y + self.player_velocity_estimate.1 * flight_time;
         
         // 瞄准预测位置
         let dx = predicted_x - enemy_tank.position.x;
@@ -231,8 +252,8 @@ impl EnemyAI {
     }
     
     fn check_and_avoid_boundaries(&mut self, enemy_tank: &mut Tank) -> bool {
-        let screen_width = screen_width();
-        let screen_height = screen_height();
+        let world_width = WORLD_WIDTH;
+        let world_height = WORLD_HEIGHT;
         let boundary_margin = enemy_tank.size + 50.0; // 增加边界检测距离
         
         let mut avoid_x: f32 = 0.0;
@@ -246,8 +267,8 @@ impl EnemyAI {
             needs_avoidance = true;
         }
         // 检查右边界
-        if enemy_tank.position.x > screen_width - boundary_margin {
-            let distance_factor = (enemy_tank.position.x - (screen_width - boundary_margin)) / boundary_margin;
+        if enemy_tank.position.x > world_width - boundary_margin {
+            let distance_factor = (enemy_tank.position.x - (world_width - boundary_margin)) / boundary_margin;
             avoid_x -= distance_factor;
             needs_avoidance = true;
         }
@@ -258,8 +279,8 @@ impl EnemyAI {
             needs_avoidance = true;
         }
         // 检查下边界
-        if enemy_tank.position.y > screen_height - boundary_margin {
-            let distance_factor = (enemy_tank.position.y - (screen_height - boundary_margin)) / boundary_margin;
+        if enemy_tank.position.y > world_height - boundary_margin {
+            let distance_factor = (enemy_tank.position.y - (world_height - boundary_margin)) / boundary_margin;
             avoid_y -= distance_factor;
             needs_avoidance = true;
         }
@@ -340,26 +361,26 @@ impl EnemyAI {
         false // 没有需要避让的障碍物
     }
     
-    pub fn should_shoot(&self, _enemy_tank: &Tank, _player_tank: &Tank, distance: f32) -> bool {
-        // 根据难度调整射击频率
-        let base_shoot_chance = if self.difficulty <= 1.0 {
-            0.02 // 容易模式：提高射击频率，让AI更智能
+    pub fn should_shoot(&self, _enemy_tank: &Tank, _player_tank: &Tank, distance: f32, dt: f32) -> bool {
+        // 根据难度调整射击频率（每秒的射击概率，按 dt 换算，与帧率无关）
+        let base_shoot_rate = if self.difficulty <= 1.0 {
+            1.2 // 容易模式：提高射击频率，让AI更智能
         } else if self.difficulty <= 2.0 {
-            0.015 // 普通模式：中等射击频率
+            0.9 // 普通模式：中等射击频率
         } else {
-            0.025 // 困难模式：高射击频率
+            1.5 // 困难模式：高射击频率
         };
         
         // 基于距离调整射击概率
-        let distance_factor = if distance < 150.0 { 
-            1.0 
-        } else if distance < 250.0 { 
-            0.7 
-        } else { 
-            0.3 
+        let distance_factor = if distance < 150.0 {
+            1.0
+        } else if distance < 250.0 {
+            0.7
+        } else {
+            0.3
         };
         
         let mut rng = thread_rng();
-        rng.gen::<f32>() < self.aggression_level * distance_factor * base_shoot_chance
+        rng.gen::<f32>() < self.aggression_level * distance_factor * base_shoot_rate * dt
     }
 }
\ No newline at end of file
@@ -0,0 +1,126 @@
+use super::{Position, Velocity};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Explosion {
+    pub position: Position,
+    pub radius: f32,
+    pub max_damage: i32,
+    pub knockback: f32,
+    pub from_player: bool, // 玩家自己的武器，不会伤害玩家
+    // 由玩家直接或通过连锁反应引起，击毁的坦克算作玩家的击杀；不影响爆炸是否伤害玩家
+    #[serde(default)]
+    pub by_player: bool,
+    pub detonated: bool,
+    pub lifetime: f32,
+    pub max_lifetime: f32,
+}
+
+impl Explosion {
+    pub fn new(x: f32, y: f32, radius: f32, max_damage: i32, knockback: f32, from_player: bool) -> Self {
+        Self {
+            position: Position::new(x, y),
+            radius,
+            max_damage,
+            knockback,
+            from_player,
+            by_player: from_player,
+            detonated: false,
+            lifetime: 0.0,
+            max_lifetime: 0.5,
+        }
+    }
+    
+    // 坦克被摧毁时的殉爆，不区分敌我；by_player 为击毁这辆坦克的是不是玩家
+    pub fn new_tank_death(x: f32, y: f32, by_player: bool) -> Self {
+        Self {
+            by_player,
+            ..Self::new(x, y, 60.0, 30, 250.0, false)
+        }
+    }
+    
+    pub fn new_mortar(x: f32, y: f32, from_player: bool) -> Self {
+        Self::new(x, y, 70.0, 60, 300.0, from_player)
+    }
+    
+    pub fn new_mine(x: f32, y: f32, from_player: bool) -> Self {
+        Self::new(x, y, 55.0, 80, 350.0, from_player)
+    }
+    
+    // 爆炸桶威力最大，可以引发连锁反应；by_player 为引爆它的是不是玩家
+    pub fn new_barrel(x: f32, y: f32, by_player: bool) -> Self {
+        Self {
+            by_player,
+            max_lifetime: 0.7,
+            ..Self::new(x, y, 90.0, 70, 400.0, false)
+        }
+    }
+    
+    // 计算对指定位置目标的伤害，随距离线性衰减
+    pub fn damage_at(&self, pos: &Position, target_radius: f32) -> i32 {
+        let distance = (self.position.distance_to(pos) - target_radius).max(0.0);
+        if distance >= self.radius {
+            return 0;
+        }
+        
+        let falloff = 1.0 - distance / self.radius;
+        ((self.max_damage as f32 * falloff).round() as i32).max(1)
+    }
+    
+    // 计算对指定位置目标的击退速度，方向背离爆炸中心
+    pub fn knockback_at(&self, pos: &Position) -> Velocity {
+        let dx = pos.x - self.position.x;
+        let dy = pos.y - self.position.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+        if distance >= self.radius || distance <= 0.0 {
+            return Velocity::new(0.0, 0.0);
+        }
+        
+        let strength = self.knockback * (1.0 - distance / self.radius);
+        Velocity::new(dx / distance * strength, dy / distance * strength)
+    }
+    
+    // 判断矩形区域（障碍物）是否在爆炸范围内
+    pub fn reaches_rect(&self, x: f32, y: f32, width: f32, height: f32) -> bool {
+        let closest_x = self.position.x.clamp(x, x + width);
+        let closest_y = self.position.y.clamp(y, y + height);
+        let distance = ((self.position.x - closest_x).powi(2) + (self.position.y - closest_y).powi(2)).sqrt();
+        distance < self.radius
+    }
+    
+    pub fn update(&mut self, dt: f32) -> bool {
+        self.lifetime += dt;
+        self.lifetime < self.max_lifetime
+    }
+    
+    pub fn draw(&self) {
+        let progress = (self.lifetime / self.max_lifetime).clamp(0.0, 1.0);
+        let alpha = 1.0 - progress;
+        
+        // 冲击波
+        let wave_radius = self.radius * (0.3 + progress * 0.7);
+        draw_circle(
+            self.position.x,
+            self.position.y,
+            wave_radius,
+            Color::new(1.0, 0.5, 0.0, 0.35 * alpha),
+        );
+        draw_circle_lines(
+            self.position.x,
+            self.position.y,
+            wave_radius,
+            3.0,
+            Color::new(1.0, 0.8, 0.2, alpha),
+        );
+        
+        // 火球核心
+        let core_radius = self.radius * 0.4 * (1.0 - progress * 0.5);
+        draw_circle(
+            self.position.x,
+            self.position.y,
+            core_radius,
+            Color::new(1.0, 0.9, 0.5, alpha),
+        );
+    }
+}
\ No newline at end of file
@@ -1,18 +1,31 @@
+pub mod arena;
 pub mod tank;
 pub mod bullet;
 pub mod obstacle;
 pub mod powerup;
 pub mod enemy;
+pub mod explosion;
+pub mod weapon;
+pub mod drone;
 
+pub use arena::*;
 pub use tank::*;
 pub use bullet::*;
 pub use obstacle::*;
 pub use powerup::*;
 pub use enemy::*;
+pub use explosion::*;
+pub use weapon::*;
+pub use drone::*;
 
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug)]
+// 世界（战场）尺寸，与窗口大小无关
+pub const WORLD_WIDTH: f32 = 2000.0;
+pub const WORLD_HEIGHT: f32 = 1500.0;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -28,7 +41,7 @@ impl Position {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Velocity {
     pub x: f32,
     pub y: f32,
@@ -45,4 +58,19 @@ impl Velocity {
             y: angle.sin() * speed,
         }
     }
+}
+
+// macroquad 的 Color 没有实现 serde，存档中以 [r, g, b, a] 数组保存
+pub(crate) mod color_serde {
+    use macroquad::prelude::Color;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        [color.r, color.g, color.b, color.a].serialize(serializer)
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let [r, g, b, a] = <[f32; 4]>::deserialize(deserializer)?;
+        Ok(Color::new(r, g, b, a))
+    }
 }
\ No newline at end of file
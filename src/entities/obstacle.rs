@@ -1,7 +1,8 @@
 use super::Position;
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Obstacle {
     pub position: Position,
     pub width: f32,
@@ -9,6 +10,7 @@ pub struct Obstacle {
     pub health: i32,
     pub max_health: i32,
     pub destructible: bool,
+    pub explosive: bool,
 }
 
 impl Obstacle {
@@ -20,6 +22,7 @@ impl Obstacle {
             health: 100,
             max_health: 100,
             destructible: true,
+            explosive: false,
         }
     }
     
@@ -31,9 +34,30 @@ impl Obstacle {
             health: 1000,
             max_health: 1000,
             destructible: false,
+            explosive: false,
         }
     }
     
+    // 爆炸桶：耐久很低，被摧毁时发生爆炸
+    pub fn new_barrel(x: f32, y: f32) -> Self {
+        Self {
+            position: Position::new(x, y),
+            width: 24.0,
+            height: 24.0,
+            health: 30,
+            max_health: 30,
+            destructible: true,
+            explosive: true,
+        }
+    }
+    
+    pub fn center(&self) -> Position {
+        Position::new(
+            self.position.x + self.width / 2.0,
+            self.position.y + self.height / 2.0,
+        )
+    }
+    
     pub fn take_damage(&mut self, damage: i32) -> bool {
         if !self.destructible {
             return false;
@@ -59,7 +83,23 @@ impl Obstacle {
         distance < radius
     }
     
+    // 障碍物被摧毁时碎片的颜色
+    pub fn debris_color(&self) -> Color {
+        if self.explosive {
+            Color::new(0.8, 0.1, 0.1, 1.0)
+        } else if self.destructible {
+            Color::new(0.6, 0.3, 0.1, 1.0)
+        } else {
+            GRAY
+        }
+    }
+    
     pub fn draw(&self) {
+        if self.explosive {
+            self.draw_barrel();
+            return;
+        }
+        
         let color = if self.destructible {
             let health_ratio = self.health as f32 / self.max_health as f32;
             Color::new(0.6 * health_ratio, 0.3, 0.1, 1.0)
@@ -85,4 +125,25 @@ impl Obstacle {
             DARKGRAY,
         );
     }
+    
+    fn draw_barrel(&self) {
+        let center = self.center();
+        let radius = self.width.min(self.height) / 2.0;
+        
+        draw_circle(center.x + 2.0, center.y + 2.0, radius, Color::new(0.0, 0.0, 0.0, 0.3));
+        draw_circle(center.x, center.y, radius, Color::new(0.8, 0.1, 0.1, 1.0));
+        draw_circle_lines(center.x, center.y, radius, 2.0, DARKGRAY);
+        draw_circle_lines(center.x, center.y, radius * 0.6, 1.5, Color::new(0.5, 0.0, 0.0, 1.0));
+        
+        // 警示标志
+        let text = "!";
+        let text_dims = measure_text(text, None, 18, 1.0);
+        draw_text(
+            text,
+            center.x - text_dims.width / 2.0,
+            center.y + text_dims.height / 2.0,
+            18.0,
+            YELLOW,
+        );
+    }
 }
\ No newline at end of file
@@ -1,17 +1,19 @@
 use super::Position;
 use macroquad::prelude::*;
 use ::rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PowerUpType {
     Health,
     Shield,
     ScatterShot,
     SpeedBoost,
     Damage,
+    SupplyDrop, // 数学补给箱：答对题目后获得更强的效果
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PowerUp {
     pub position: Position,
     pub power_type: PowerUpType,
@@ -58,6 +60,14 @@ impl PowerUp {
         self.collected = true;
     }
     
+    // 数学补给箱，只在设置中开启后生成
+    pub fn new_supply_drop(x: f32, y: f32) -> Self {
+        Self {
+            power_type: PowerUpType::SupplyDrop,
+            ..Self::new_random(x, y)
+        }
+    }
+    
     pub fn get_color(&self) -> Color {
         match self.power_type {
             PowerUpType::Health => GREEN,
@@ -65,6 +75,7 @@ impl PowerUp {
             PowerUpType::ScatterShot => PURPLE,
             PowerUpType::SpeedBoost => SKYBLUE,
             PowerUpType::Damage => RED,
+            PowerUpType::SupplyDrop => GOLD,
         }
     }
     
@@ -75,6 +86,7 @@ impl PowerUp {
             PowerUpType::ScatterShot => "*",
             PowerUpType::SpeedBoost => ">",
             PowerUpType::Damage => "!",
+            PowerUpType::SupplyDrop => "?",
         }
     }
     
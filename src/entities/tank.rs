@@ -1,35 +1,63 @@
-use super::{Position, Velocity};
+use super::{Bullet, BulletKind, Position, Velocity, Weapon, WORLD_HEIGHT, WORLD_WIDTH};
+use crate::ui::Palette;
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Tank {
     pub position: Position,
+    pub prev_position: Position,
     pub velocity: Velocity,
     pub angle: f32,
     pub health: i32,
     pub max_health: i32,
     pub size: f32,
     pub speed: f32,
+    #[serde(with = "super::color_serde")]
     pub color: Color,
     pub shield: Option<Shield>,
-    pub last_shot: f64,
+    pub shot_timer: f64, // 剩余射击冷却时间（秒），为 0 时可以射击
     pub shot_cooldown: f64,
     pub is_player: bool,
     pub scatter_shot: bool,
+    pub weapon: Weapon,
+    pub knockback: Velocity,
+    #[serde(default)]
+    pub volleys_fired: u32,
+    // 可以切换的武器；敌方坦克只有一种
+    #[serde(default = "default_arsenal")]
+    pub arsenal: Vec<Weapon>,
+    #[serde(default)]
+    pub perks: TankPerks,
 }
 
-#[derive(Clone)]
+// 本局在波次间隙选择的强化
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TankPerks {
+    pub pierce: u32,       // 炮弹可以穿透的坦克数
+    pub ricochet: u32,     // 炮弹可以在障碍物上反弹的次数
+    pub regen: f32,        // 每秒恢复的生命值
+    pub shield_bonus: f32, // 护盾持续时间的加成比例
+    regen_progress: f32,   // 不足 1 点的恢复量累积到下一帧
+}
+
+fn default_arsenal() -> Vec<Weapon> {
+    Weapon::BASIC.to_vec()
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Shield {
     #[allow(dead_code)]
     pub duration: f64,
-    #[allow(dead_code)]
-    pub start_time: f64,
+    pub remaining: f64,
 }
 
 impl Tank {
     pub fn new_player(x: f32, y: f32) -> Self {
         Self {
             position: Position::new(x, y),
+            prev_position: Position::new(x, y),
             velocity: Velocity::new(0.0, 0.0),
             angle: 0.0,
             health: 200,
@@ -38,16 +66,22 @@ impl Tank {
             speed: 250.0, // 提高基础速度
             color: BLUE,
             shield: None,
-            last_shot: 0.0,
+            shot_timer: 0.0,
             shot_cooldown: 0.25, // 稍微减少射击冷却时间
             is_player: true,
             scatter_shot: false,
+            weapon: Weapon::Cannon,
+            knockback: Velocity::new(0.0, 0.0),
+            volleys_fired: 0,
+            arsenal: default_arsenal(),
+            perks: TankPerks::default(),
         }
     }
     
     pub fn new_enemy(x: f32, y: f32) -> Self {
         Self {
             position: Position::new(x, y),
+            prev_position: Position::new(x, y),
             velocity: Velocity::new(0.0, 0.0),
             angle: 0.0,
             health: 50,
@@ -56,13 +90,26 @@ impl Tank {
             speed: 80.0,
             color: RED,
             shield: None,
-            last_shot: 0.0,
+            shot_timer: 0.0,
             shot_cooldown: 1.0,
             is_player: false,
             scatter_shot: false,
+            weapon: Weapon::Cannon,
+            knockback: Velocity::new(0.0, 0.0),
+            volleys_fired: 0,
+            arsenal: vec![Weapon::Cannon],
+            perks: TankPerks::default(),
         }
     }
     
+    pub fn new_enemy_with_weapon(x: f32, y: f32, weapon: Weapon) -> Self {
+        let mut tank = Self::new_enemy(x, y);
+        tank.weapon = weapon;
+        tank.arsenal = vec![weapon];
+        tank.color = weapon.enemy_color();
+        tank
+    }
+    
     #[allow(dead_code)]
     pub fn update(&mut self, dt: f32) {
         // 预测新位置
@@ -70,8 +117,8 @@ impl Tank {
         let new_y = self.position.y + self.velocity.y * dt;
         
         // 边界检查 - 预防性碰撞检测
-        let screen_width = screen_width();
-        let screen_height = screen_height();
+        let world_width = WORLD_WIDTH;
+        let world_height = WORLD_HEIGHT;
         
         let mut final_x = new_x;
         let mut final_y = new_y;
@@ -80,8 +127,8 @@ impl Tank {
         if new_x - self.size < 0.0 {
             final_x = self.size;
             self.velocity.x = 0.0; // 停止X轴移动
-        } else if new_x + self.size > screen_width {
-            final_x = screen_width - self.size;
+        } else if new_x + self.size > world_width {
+            final_x = world_width - self.size;
             self.velocity.x = 0.0; // 停止X轴移动
         }
         
@@ -89,8 +136,8 @@ impl Tank {
         if new_y - self.size < 0.0 {
             final_y = self.size;
             self.velocity.y = 0.0; // 停止Y轴移动
-        } else if new_y + self.size > screen_height {
-            final_y = screen_height - self.size;
+        } else if new_y + self.size > world_height {
+            final_y = world_height - self.size;
             self.velocity.y = 0.0; // 停止Y轴移动
         }
         
@@ -99,11 +146,26 @@ impl Tank {
         self.position.y = final_y;
         
         // 更新护盾
-        if let Some(shield) = &self.shield {
-            if get_time() - shield.start_time > shield.duration {
+        self.update_timers(dt);
+    }
+    
+    // 推进射击冷却和护盾计时，每个逻辑帧调用一次
+    pub fn update_timers(&mut self, dt: f32) {
+        self.shot_timer = (self.shot_timer - dt as f64).max(0.0);
+        
+        if let Some(shield) = &mut self.shield {
+            shield.remaining -= dt as f64;
+            if shield.remaining <= 0.0 {
                 self.shield = None;
             }
         }
+        
+        if self.perks.regen > 0.0 && self.health > 0 && self.health < self.max_health {
+            self.perks.regen_progress += self.perks.regen * dt;
+            let amount = self.perks.regen_progress.floor();
+            self.perks.regen_progress -= amount;
+            self.heal(amount as i32);
+        }
     }
     
     // 新增：检查是否会与障碍物碰撞的预测函数
@@ -131,35 +193,42 @@ impl Tank {
     }
     
     // 新增：安全移动函数，考虑障碍物碰撞
-    pub fn safe_move(&mut self, dt: f32, obstacles: &[crate::entities::Obstacle]) {
+    // 返回本次移动是否被障碍物挡住
+    pub fn safe_move(&mut self, dt: f32, obstacles: &[crate::entities::Obstacle]) -> bool {
         let original_x = self.position.x;
         let original_y = self.position.y;
         
-        // 计算预期的新位置
-        let target_x = self.position.x + self.velocity.x * dt;
-        let target_y = self.position.y + self.velocity.y * dt;
+        // 计算预期的新位置（包含爆炸击退）
+        let target_x = self.position.x + (self.velocity.x + self.knockback.x) * dt;
+        let target_y = self.position.y + (self.velocity.y + self.knockback.y) * dt;
+        
+        // 击退效果逐渐衰减
+        let decay = (-6.0 * dt).exp();
+        self.knockback.x *= decay;
+        self.knockback.y *= decay;
         
         // 边界检查
-        let screen_width = screen_width();
-        let screen_height = screen_height();
+        let world_width = WORLD_WIDTH;
+        let world_height = WORLD_HEIGHT;
         
         let mut new_x = target_x;
         let mut new_y = target_y;
+        let mut blocked = false;
         
         // 边界限制和反弹
         if new_x - self.size < 0.0 {
             new_x = self.size;
             self.velocity.x = 0.0; // 停止向边界移动
-        } else if new_x + self.size > screen_width {
-            new_x = screen_width - self.size;
+        } else if new_x + self.size > world_width {
+            new_x = world_width - self.size;
             self.velocity.x = 0.0; // 停止向边界移动
         }
         
         if new_y - self.size < 0.0 {
             new_y = self.size;
             self.velocity.y = 0.0; // 停止向边界移动
-        } else if new_y + self.size > screen_height {
-            new_y = screen_height - self.size;
+        } else if new_y + self.size > world_height {
+            new_y = world_height - self.size;
             self.velocity.y = 0.0; // 停止向边界移动
         }
         
@@ -168,6 +237,7 @@ impl Tank {
         if !self.would_collide_with_obstacles(test_x_pos, original_y, obstacles) {
             self.position.x = test_x_pos;
         } else {
+            blocked = true;
             self.velocity.x = 0.0; // 停止X轴移动
             // 添加小幅度的反向推力防止抖动
             if target_x > original_x {
@@ -182,6 +252,7 @@ impl Tank {
         if !self.would_collide_with_obstacles(self.position.x, test_y_pos, obstacles) {
             self.position.y = test_y_pos;
         } else {
+            blocked = true;
             self.velocity.y = 0.0; // 停止Y轴移动
             // 添加小幅度的反向推力防止抖动
             if target_y > original_y {
@@ -208,27 +279,121 @@ impl Tank {
                     let escape_y = original_y + angle.sin() * escape_distance;
                     
                     // 检查边界
-                    if escape_x - self.size >= 0.0 && escape_x + self.size <= screen_width &&
-                       escape_y - self.size >= 0.0 && escape_y + self.size <= screen_height {
-                        if !self.would_collide_with_obstacles(escape_x, escape_y, obstacles) {
-                            self.position.x = escape_x;
-                            self.position.y = escape_y;
-                            self.velocity.x *= 0.5; // 减少速度避免再次卡住
-                            self.velocity.y *= 0.5;
-                            break;
-                        }
+                    if escape_x - self.size >= 0.0 && escape_x + self.size <= world_width &&
+                       escape_y - self.size >= 0.0 && escape_y + self.size <= world_height &&
+                       !self.would_collide_with_obstacles(escape_x, escape_y, obstacles) {
+                        self.position.x = escape_x;
+                        self.position.y = escape_y;
+                        self.velocity.x *= 0.5; // 减少速度避免再次卡住
+                        self.velocity.y *= 0.5;
+                        break;
                     }
                 }
             }
         }
+        
+        blocked
+    }
+    
+    // 在每个逻辑帧开始时记录位置，用于渲染插值
+    pub fn store_previous_position(&mut self) {
+        self.prev_position = self.position;
+    }
+    
+    pub fn render_position(&self, alpha: f32) -> Position {
+        Position::new(
+            self.prev_position.x + (self.position.x - self.prev_position.x) * alpha,
+            self.prev_position.y + (self.position.y - self.prev_position.y) * alpha,
+        )
     }
     
     pub fn can_shoot(&self) -> bool {
-        get_time() - self.last_shot > self.shot_cooldown
+        self.shot_timer <= 0.0
     }
     
     pub fn shoot(&mut self) {
-        self.last_shot = get_time();
+        self.shot_timer = self.shot_cooldown * self.weapon.cooldown_multiplier();
+    }
+    
+    // 炮口位置，子弹和炮口火光都从这里产生
+    pub fn muzzle_position(&self) -> Position {
+        Position::new(
+            self.position.x + self.angle.cos() * (self.size + 5.0),
+            self.position.y + self.angle.sin() * (self.size + 5.0),
+        )
+    }
+    
+    // 使用当前武器开火，target 为迫击炮的落点
+    pub fn fire(&mut self, target: Position) -> Vec<Bullet> {
+        self.shoot();
+        
+        let muzzle = self.muzzle_position();
+        let (muzzle_x, muzzle_y) = (muzzle.x, muzzle.y);
+        
+        let mut bullets = match self.weapon {
+            Weapon::Cannon => {
+                if self.scatter_shot {
+                    // 散弹射击
+                    let spread_angles = [-0.3, -0.15, 0.0, 0.15, 0.3];
+                    spread_angles
+                        .iter()
+                        .map(|&spread| Bullet::new_scatter(muzzle_x, muzzle_y, self.angle, spread, self.is_player))
+                        .collect()
+                } else {
+                    // 普通射击
+                    vec![Bullet::new(muzzle_x, muzzle_y, self.angle, self.is_player)]
+                }
+            }
+            Weapon::HeavyCannon => vec![Bullet::new_heavy(muzzle_x, muzzle_y, self.angle, self.is_player)],
+            Weapon::TwinCannon => {
+                // 两发平行的炮弹，分别从炮口两侧射出
+                let (offset_x, offset_y) = (-self.angle.sin() * 6.0, self.angle.cos() * 6.0);
+                [-1.0, 1.0]
+                    .iter()
+                    .map(|side| {
+                        let mut bullet = Bullet::new(
+                            muzzle_x + offset_x * side,
+                            muzzle_y + offset_y * side,
+                            self.angle,
+                            self.is_player,
+                        );
+                        bullet.damage = 18;
+                        bullet
+                    })
+                    .collect()
+            }
+            Weapon::Mortar => vec![Bullet::new_mortar(muzzle_x, muzzle_y, target, self.is_player)],
+            Weapon::MineLayer => {
+                // 地雷布设在坦克后方
+                let mine_x = self.position.x - self.angle.cos() * (self.size + 10.0);
+                let mine_y = self.position.y - self.angle.sin() * (self.size + 10.0);
+                vec![Bullet::new_mine(mine_x, mine_y, self.is_player)]
+            }
+        };
+        
+        self.volleys_fired = self.volleys_fired.wrapping_add(1);
+        for bullet in &mut bullets {
+            bullet.volley = self.volleys_fired;
+            if bullet.kind == BulletKind::Shell {
+                bullet.pierce = self.perks.pierce;
+                bullet.ricochet = self.perks.ricochet;
+            }
+        }
+        bullets
+    }
+    
+    // 切换到武器库中的下一种武器
+    pub fn next_weapon(&mut self) {
+        if self.arsenal.is_empty() {
+            return;
+        }
+        let idx = self.arsenal.iter().position(|w| *w == self.weapon).unwrap_or(0);
+        self.weapon = self.arsenal[(idx + 1) % self.arsenal.len()];
+    }
+    
+    pub fn apply_knockback(&mut self, impulse: Velocity) {
+        self.knockback.x += impulse.x;
+        self.knockback.y += impulse.y;
     }
     
     pub fn take_damage(&mut self, damage: i32) -> bool {
@@ -245,17 +410,22 @@ impl Tank {
     }
     
     pub fn add_shield(&mut self, duration: f64) {
+        let duration = duration * (1.0 + self.perks.shield_bonus as f64);
         self.shield = Some(Shield {
             duration,
-            start_time: get_time(),
+            remaining: duration,
         });
     }
     
-    pub fn draw(&self) {
+    // alpha 为渲染插值系数：0 为上一个逻辑帧的位置，1 为当前位置
+    pub fn draw(&self, alpha: f32, palette: &Palette) {
+        let pos = self.render_position(alpha);
+        
+        let base_color = palette.tank_color(self);
         let color = if self.shield.is_some() {
-            Color::new(self.color.r, self.color.g, self.color.b, 0.7)
+            Color::new(base_color.r, base_color.g, base_color.b, 0.7)
         } else {
-            self.color
+            base_color
         };
         
         // 计算坦克主体的尺寸
@@ -265,8 +435,8 @@ impl Tank {
         
         // 绘制旋转的坦克主体
         self.draw_rotated_rectangle(
-            self.position.x,
-            self.position.y,
+            pos.x,
+            pos.y,
             body_width,
             body_height,
             self.angle,
@@ -277,10 +447,10 @@ impl Tank {
         
         // 绘制炮管（与坦克主体同方向）
         let barrel_length = self.size * 2.2;
-        let barrel_start_x = self.position.x + self.angle.cos() * turret_radius * 0.8;
-        let barrel_start_y = self.position.y + self.angle.sin() * turret_radius * 0.8;
-        let barrel_end_x = self.position.x + self.angle.cos() * barrel_length;
-        let barrel_end_y = self.position.y + self.angle.sin() * barrel_length;
+        let barrel_start_x = pos.x + self.angle.cos() * turret_radius * 0.8;
+        let barrel_start_y = pos.y + self.angle.sin() * turret_radius * 0.8;
+        let barrel_end_x = pos.x + self.angle.cos() * barrel_length;
+        let barrel_end_y = pos.y + self.angle.sin() * barrel_length;
         
         // 炮管阴影
         draw_line(
@@ -315,8 +485,8 @@ impl Tank {
         // 绘制炮塔（圆形，与主体同方向）
         // 炮塔阴影
         draw_circle(
-            self.position.x + 1.0,
-            self.position.y + 1.0,
+            pos.x + 1.0,
+            pos.y + 1.0,
             turret_radius,
             Color::new(0.0, 0.0, 0.0, 0.3),
         );
@@ -328,26 +498,26 @@ impl Tank {
             color.b * 0.9,
             color.a,
         );
-        draw_circle(self.position.x, self.position.y, turret_radius, turret_color);
+        draw_circle(pos.x, pos.y, turret_radius, turret_color);
         
         // 炮塔边框
-        draw_circle_lines(self.position.x, self.position.y, turret_radius, 2.0, DARKGRAY);
+        draw_circle_lines(pos.x, pos.y, turret_radius, 2.0, DARKGRAY);
         
         // 炮塔方向指示器（小矩形）
         let indicator_length = turret_radius * 0.6;
-        let indicator_x = self.position.x + self.angle.cos() * indicator_length;
-        let indicator_y = self.position.y + self.angle.sin() * indicator_length;
+        let indicator_x = pos.x + self.angle.cos() * indicator_length;
+        let indicator_y = pos.y + self.angle.sin() * indicator_length;
         draw_circle(indicator_x, indicator_y, 2.0, DARKGRAY);
         
         // 炮塔中心点
-        draw_circle(self.position.x, self.position.y, 3.0, DARKGRAY);
+        draw_circle(pos.x, pos.y, 3.0, DARKGRAY);
         
         // 绘制护盾效果
         if self.shield.is_some() {
             let shield_radius = (body_width.max(body_height) / 2.0) + 8.0;
             draw_circle_lines(
-                self.position.x,
-                self.position.y,
+                pos.x,
+                pos.y,
                 shield_radius,
                 3.0,
                 YELLOW,
@@ -357,8 +527,8 @@ impl Tank {
             let time = macroquad::prelude::get_time();
             if (time * 8.0).sin() > 0.0 {
                 draw_circle_lines(
-                    self.position.x,
-                    self.position.y,
+                    pos.x,
+                    pos.y,
                     shield_radius - 3.0,
                     2.0,
                     Color::new(1.0, 1.0, 0.0, 0.5),
@@ -370,21 +540,15 @@ impl Tank {
         if !self.is_player {
             let bar_width = body_width;
             let bar_height = 4.0;
-            let bar_x = self.position.x - bar_width / 2.0;
-            let bar_y = self.position.y - body_height / 2.0 - 12.0;
+            let bar_x = pos.x - bar_width / 2.0;
+            let bar_y = pos.y - body_height / 2.0 - 12.0;
             
             // 血条背景
             draw_rectangle(bar_x, bar_y, bar_width, bar_height, DARKGRAY);
             
             // 血量
             let health_ratio = self.health as f32 / self.max_health as f32;
-            let health_color = if health_ratio > 0.6 {
-                GREEN
-            } else if health_ratio > 0.3 {
-                YELLOW
-            } else {
-                RED
-            };
+            let health_color = palette.health_color(health_ratio);
             
             draw_rectangle(
                 bar_x,
@@ -0,0 +1,61 @@
+use crate::i18n::{tr, Text};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Weapon {
+    Cannon,
+    Mortar,
+    MineLayer,
+    HeavyCannon, // 以下为只能通过升级解锁的玩家武器
+    TwinCannon,
+}
+
+impl Weapon {
+    // 基础武器：敌人只使用这些，玩家开局也都拥有
+    pub const BASIC: [Weapon; 3] = [Weapon::Cannon, Weapon::Mortar, Weapon::MineLayer];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Weapon::Cannon => tr(Text::WeaponCannon),
+            Weapon::Mortar => tr(Text::WeaponMortar),
+            Weapon::MineLayer => tr(Text::WeaponMines),
+            Weapon::HeavyCannon => tr(Text::WeaponHeavyCannon),
+            Weapon::TwinCannon => tr(Text::WeaponTwinCannon),
+        }
+    }
+    
+    // 相对于坦克基础射击冷却的倍率
+    pub fn cooldown_multiplier(&self) -> f64 {
+        match self {
+            Weapon::Cannon => 1.0,
+            Weapon::Mortar => 4.0,
+            Weapon::MineLayer => 6.0,
+            Weapon::HeavyCannon => 2.5,
+            Weapon::TwinCannon => 1.4,
+        }
+    }
+    
+    // 发射直射炮弹的武器，命中率只统计这些武器
+    pub fn fires_shells(&self) -> bool {
+        matches!(self, Weapon::Cannon | Weapon::HeavyCannon | Weapon::TwinCannon)
+    }
+    
+    // 迫击炮的射程（玩家瞄准时使用）
+    pub fn range(&self) -> f32 {
+        match self {
+            Weapon::Mortar => 260.0,
+            _ => 0.0,
+        }
+    }
+    
+    // 敌方坦克根据武器使用不同颜色以便区分
+    pub fn enemy_color(&self) -> Color {
+        match self {
+            Weapon::Cannon => RED,
+            Weapon::Mortar => MAROON,
+            Weapon::MineLayer => Color::new(0.9, 0.4, 0.1, 1.0),
+            Weapon::HeavyCannon | Weapon::TwinCannon => RED,
+        }
+    }
+}
\ No newline at end of file
@@ -1,9 +1,26 @@
 use crate::entities::*;
 use crate::systems::*;
-use crate::ui::GameUI;
-use crate::math_challenge::MathChallenge;
+use crate::ui::{GameUI, ACHIEVEMENTS_PER_PAGE};
+use crate::challenges::{
+    Challenge, ChallengeInput, ChallengeKind, ChallengeSession, GeographyChallenge, PackChallenge, QuestionPack,
+    ReflexChallenge, SpellingChallenge, TimesTableChallenge, VocabularyChallenge,
+};
+use crate::i18n::{cjk_font_hint, locale, tr, trf, Fonts, Locale, Text};
+use crate::math_challenge::{adaptive, MathChallenge};
+use crate::storage::{
+    current_date, AchievementProgress, Leaderboard, LearningData, Progression, QuestionPacks, RunRecord, SaveGame, ScoreEntry, Settings,
+    SettingsItem, StatsHistory, Upgrade, WindowMode,
+};
+use crate::ui::Palette;
 use macroquad::prelude::*;
+use ::rand::seq::SliceRandom;
 use ::rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+// 固定逻辑帧间隔（60 Hz），与渲染帧率无关
+pub const FIXED_DT: f32 = 1.0 / 60.0;
+// 单帧最多累积的时间，避免卡顿后一次性模拟过多逻辑帧
+const MAX_FRAME_TIME: f32 = 0.25;
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum GameState {
@@ -11,51 +28,251 @@ pub enum GameState {
     Playing,
     Paused,
     GameOver,
-    MathChallenge,
+    Challenge, // 死亡后答题复活
+    Intermission, // 波次之间选择强化
+    NameEntry,
+    Leaderboard,
+    Achievements,
+    Shop,
+    LearningReport,
+    ProfileEntry, // 在学习报告中新建档案
+    Settings,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GameMode {
+    Classic,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 1] = [GameMode::Classic];
+    
+    pub fn key(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "classic",
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Classic => tr(Text::ModeClassic),
+        }
+    }
+}
+
+// 菜单中可选的难度及其名称
+pub const DIFFICULTIES: [(f32, &str); 3] = [(1.0, "Easy"), (1.5, "Normal"), (2.0, "Hard")];
+
+// 主菜单中的选项
+#[derive(Clone, Copy, PartialEq)]
+pub enum MenuItem {
+    Play(usize), // DIFFICULTIES 中的下标
+    Continue,
+    Leaderboard,
+    Achievements,
+    Shop,
+    Learning,
+    Settings,
+}
+
+impl MenuItem {
+    pub fn label(&self) -> String {
+        match self {
+            MenuItem::Play(index) => format!("{} - {}", index + 1, difficulty_label(*index)),
+            MenuItem::Continue => tr(Text::MenuContinue).to_string(),
+            MenuItem::Leaderboard => tr(Text::MenuLeaderboard).to_string(),
+            MenuItem::Achievements => tr(Text::MenuAchievements).to_string(),
+            MenuItem::Shop => tr(Text::MenuShop).to_string(),
+            MenuItem::Learning => tr(Text::MenuLearning).to_string(),
+            MenuItem::Settings => tr(Text::MenuSettings).to_string(),
+        }
+    }
+    
+    // 键盘快捷键
+    fn hotkey(&self) -> KeyCode {
+        match self {
+            MenuItem::Play(0) => KeyCode::Key1,
+            MenuItem::Play(1) => KeyCode::Key2,
+            MenuItem::Play(_) => KeyCode::Key3,
+            MenuItem::Continue => KeyCode::C,
+            MenuItem::Leaderboard => KeyCode::L,
+            MenuItem::Achievements => KeyCode::T,
+            MenuItem::Shop => KeyCode::U,
+            MenuItem::Learning => KeyCode::E,
+            MenuItem::Settings => KeyCode::O,
+        }
+    }
+}
+
+// 暂停菜单中的选项
+#[derive(Clone, Copy, PartialEq)]
+pub enum PauseItem {
+    Resume,
+    SaveAndQuit,
+    Settings,
+}
+
+impl PauseItem {
+    pub const ALL: [PauseItem; 3] = [PauseItem::Resume, PauseItem::SaveAndQuit, PauseItem::Settings];
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseItem::Resume => tr(Text::PauseResume),
+            PauseItem::SaveAndQuit => tr(Text::PauseSaveQuit),
+            PauseItem::Settings => tr(Text::MenuSettings),
+        }
+    }
+    
+    fn hotkey(&self) -> Option<KeyCode> {
+        match self {
+            PauseItem::Resume => None,
+            PauseItem::SaveAndQuit => Some(KeyCode::S),
+            PauseItem::Settings => Some(KeyCode::O),
+        }
+    }
+}
+
+pub fn difficulty_name(difficulty: f32) -> &'static str {
+    DIFFICULTIES
+        .iter()
+        .rev()
+        .find(|(value, _)| difficulty >= *value)
+        .map(|(_, name)| *name)
+        .unwrap_or(DIFFICULTIES[0].1)
+}
+
+// 界面上显示的难度名称；difficulty_name 是英文名称，也用作排行榜的键
+pub fn difficulty_label(index: usize) -> &'static str {
+    const LABELS: [Text; 3] = [Text::DifficultyEasy, Text::DifficultyNormal, Text::DifficultyHard];
+    tr(LABELS[index.min(LABELS.len() - 1)])
 }
 
 pub struct Game {
     pub state: GameState,
     pub player_tank: Tank,
-    pub enemy_tanks: Vec<Tank>,
-    pub enemy_ais: Vec<EnemyAI>,
+    pub enemies: Arena<Enemy>,
     pub bullets: Vec<Bullet>,
+    pub explosions: Vec<Explosion>,
     pub obstacles: Vec<Obstacle>,
     pub powerups: Vec<PowerUp>,
+    pub drones: Vec<Drone>,
+    pub particles: ParticleSystem,
+    pub audio: AudioSystem,
+    pub events: EventQueue,
+    pub camera: GameCamera,
     pub spawn_system: SpawnSystem,
     pub ui: GameUI,
+    pub fonts: Fonts,
     pub score: i32,
+    pub scoring: ScoreTracker,
+    pub run_stats: RunStats,
+    pub stats_history: StatsHistory,
     pub high_score: i32,
     pub wave: i32,
     pub enemies_killed_this_wave: i32,
     pub enemies_per_wave: i32,
     pub difficulty: f32,
-    pub last_difficulty_increase: f64,
-    pub math_challenge: Option<MathChallenge>,
+    pub time_since_difficulty_increase: f64,
+    pub challenge: Option<ChallengeSession>,
+    pub supply_drop: Option<SupplyDrop>, // 正在解答的数学补给箱
+    pub revives_used: u32, // 本局已经复活的次数，每次复活题目更难
+    pub revive_streak: u32, // 本局连续答对复活题的次数，答错或本局结束时清零
+    pub perk_offers: Vec<Perk>,
+    pub perk_selected: usize,
+    pub accumulator: f32,
+    pub mode: GameMode,
+    pub run_time: f64,
+    pub leaderboard: Leaderboard,
+    pub player_name: String,
+    pub last_rank: Option<usize>,
+    pub missed_answer: Option<String>, // 复活挑战答错时的正确答案，显示在结算界面
+    pub leaderboard_page: usize,
+    pub achievements: AchievementTracker,
+    pub achievements_page: usize,
+    pub progression: Progression,
+    pub upgraded_run: bool, // 本局是否使用了升级
+    pub last_scrap: u32,    // 上一局获得的废料
+    pub shop_selected: usize,
+    pub learning: LearningData,
+    pub question_packs: QuestionPacks,
+    pub learning_status: Option<String>, // 学习报告界面的提示（例如导出结果）
+    pub profile_name: String,
+    pub has_save: bool,
+    pub settings: Settings,
+    pub settings_selected: usize,
+    pub settings_return: GameState, // 关闭设置界面后返回的状态（主菜单或暂停菜单）
+    pub rebinding: Option<Action>,  // 正在等待新按键的动作
+    pub input: DeviceInput,
+    pub menu_selected: usize,
+    pub pause_selected: usize,
 }
 
 impl Game {
     pub fn new() -> Self {
+        let leaderboard = Leaderboard::load();
+        let settings = Settings::load();
         let mut game = Self {
             state: GameState::Menu,
-            player_tank: Tank::new_player(400.0, 300.0),
-            enemy_tanks: Vec::new(),
-            enemy_ais: Vec::new(),
+            player_tank: Tank::new_player(WORLD_WIDTH / 2.0, WORLD_HEIGHT / 2.0),
+            enemies: Arena::new(),
             bullets: Vec::new(),
+            explosions: Vec::new(),
             obstacles: Vec::new(),
             powerups: Vec::new(),
+            drones: Vec::new(),
+            particles: ParticleSystem::new(settings.particle_quality),
+            audio: AudioSystem::new(),
+            events: EventQueue::new(),
+            camera: GameCamera::new(),
             spawn_system: SpawnSystem::new(1.0),
             ui: GameUI::new(),
+            fonts: Fonts::new(),
             score: 0,
-            high_score: 0,
+            scoring: ScoreTracker::new(settings.scoring.clone()),
+            run_stats: RunStats::default(),
+            stats_history: StatsHistory::load(),
+            high_score: leaderboard.best_score(),
             wave: 1,
             enemies_killed_this_wave: 0,
             enemies_per_wave: 5,
             difficulty: 1.0,
-            last_difficulty_increase: 0.0,
-            math_challenge: None,
+            time_since_difficulty_increase: 0.0,
+            challenge: None,
+            supply_drop: None,
+            revives_used: 0,
+            revive_streak: 0,
+            perk_offers: Vec::new(),
+            perk_selected: 0,
+            accumulator: 0.0,
+            mode: GameMode::Classic,
+            run_time: 0.0,
+            leaderboard,
+            player_name: String::new(),
+            last_rank: None,
+            missed_answer: None,
+            leaderboard_page: 0,
+            achievements: AchievementTracker::new(AchievementProgress::load()),
+            achievements_page: 0,
+            progression: Progression::load(),
+            upgraded_run: false,
+            last_scrap: 0,
+            shop_selected: 0,
+            learning: LearningData::load(),
+            question_packs: QuestionPacks::load(),
+            learning_status: None,
+            profile_name: String::new(),
+            has_save: SaveGame::exists(),
+            settings,
+            settings_selected: 0,
+            settings_return: GameState::Menu,
+            rebinding: None,
+            input: DeviceInput::new(),
+            menu_selected: 0,
+            pause_selected: 0,
         };
+        game.menu_selected = game.settings.default_difficulty;
         
+        game.apply_settings();
         game.generate_obstacles();
         game
     }
@@ -63,37 +280,59 @@ impl Game {
     pub fn start_game(&mut self, difficulty: f32) {
         self.difficulty = difficulty;
         self.state = GameState::Playing;
-        self.player_tank = Tank::new_player(screen_width() / 2.0, screen_height() / 2.0);
-        self.enemy_tanks.clear();
-        self.enemy_ais.clear();
+        self.player_tank = Tank::new_player(WORLD_WIDTH / 2.0, WORLD_HEIGHT / 2.0);
+        self.upgraded_run = self.progression.is_upgraded();
+        self.progression.apply(&mut self.player_tank);
+        self.camera.snap_to(self.player_tank.position);
+        self.enemies.clear();
         self.bullets.clear();
+        self.explosions.clear();
         self.powerups.clear();
+        self.drones.clear();
+        self.perk_offers.clear();
+        self.particles.clear();
         self.spawn_system = SpawnSystem::new(difficulty);
         self.score = 0;
+        self.scoring.reset();
+        self.run_stats = RunStats::default();
+        self.achievements.reset_run();
         self.wave = 1;
         self.enemies_killed_this_wave = 0;
         self.enemies_per_wave = 5;
-        self.last_difficulty_increase = get_time();
-        self.math_challenge = None;
+        self.time_since_difficulty_increase = 0.0;
+        self.challenge = None;
+        self.supply_drop = None;
+        self.revives_used = 0;
+        self.revive_streak = 0;
+        self.accumulator = 0.0;
+        self.run_time = 0.0;
+        self.last_rank = None;
+        self.missed_answer = None;
         self.generate_obstacles();
+        self.events.clear();
+        self.events.push(GameEvent::WaveStarted { wave: 1 });
     }
     
     fn generate_obstacles(&mut self) {
         self.obstacles.clear();
         let mut rng = thread_rng();
         
-        let screen_w = screen_width();
-        let screen_h = screen_height();
+        let world_w = WORLD_WIDTH;
+        let world_h = WORLD_HEIGHT;
         
-        // 生成随机障碍物
-        for _ in 0..15 {
-            let x = rng.gen_range(50.0..screen_w - 100.0);
-            let y = rng.gen_range(50.0..screen_h - 100.0);
+        // 按世界面积生成随机障碍物（800x600 的区域约 15 个）
+        let area_factor = (world_w * world_h) / (800.0 * 600.0);
+        let wall_count = (15.0 * area_factor) as usize;
+        let barrel_count = (5.0 * area_factor) as usize;
+        
+        for _ in 0..wall_count {
+            let x = rng.gen_range(50.0..world_w - 100.0);
+            let y = rng.gen_range(50.0..world_h - 100.0);
             let width = rng.gen_range(30.0..80.0);
             let height = rng.gen_range(30.0..80.0);
             
             // 确保不在玩家起始位置附近
-            if (x - screen_w / 2.0).abs() > 100.0 || (y - screen_h / 2.0).abs() > 100.0 {
+            if (x - world_w / 2.0).abs() > 100.0 || (y - world_h / 2.0).abs() > 100.0 {
                 if rng.gen_bool(0.8) {
                     self.obstacles.push(Obstacle::new_wall(x, y, width, height));
                 } else {
@@ -102,72 +341,359 @@ impl Game {
             }
         }
         
+        // 生成爆炸桶，靠近墙壁时可以引发连锁爆炸
+        for _ in 0..barrel_count {
+            let x = rng.gen_range(50.0..world_w - 100.0);
+            let y = rng.gen_range(50.0..world_h - 100.0);
+            
+            if (x - world_w / 2.0).abs() > 120.0 || (y - world_h / 2.0).abs() > 120.0 {
+                self.obstacles.push(Obstacle::new_barrel(x, y));
+            }
+        }
+        
         // 添加边界墙
         let wall_thickness = 20.0;
-        self.obstacles.push(Obstacle::new_steel(0.0, 0.0, screen_w, wall_thickness));
-        self.obstacles.push(Obstacle::new_steel(0.0, screen_h - wall_thickness, screen_w, wall_thickness));
-        self.obstacles.push(Obstacle::new_steel(0.0, 0.0, wall_thickness, screen_h));
-        self.obstacles.push(Obstacle::new_steel(screen_w - wall_thickness, 0.0, wall_thickness, screen_h));
+        self.obstacles.push(Obstacle::new_steel(0.0, 0.0, world_w, wall_thickness));
+        self.obstacles.push(Obstacle::new_steel(0.0, world_h - wall_thickness, world_w, wall_thickness));
+        self.obstacles.push(Obstacle::new_steel(0.0, 0.0, wall_thickness, world_h));
+        self.obstacles.push(Obstacle::new_steel(world_w - wall_thickness, 0.0, wall_thickness, world_h));
     }
     
     pub async fn update(&mut self) {
+        self.input.update();
+        
         match self.state {
             GameState::Menu => self.update_menu().await,
             GameState::Playing => self.update_playing().await,
             GameState::Paused => self.update_paused().await,
             GameState::GameOver => self.update_game_over().await,
-            GameState::MathChallenge => self.update_math_challenge().await,
+            GameState::Challenge => self.update_challenge().await,
+            GameState::Intermission => self.update_intermission().await,
+            GameState::NameEntry => self.update_name_entry().await,
+            GameState::Leaderboard => self.update_leaderboard().await,
+            GameState::Achievements => self.update_achievements().await,
+            GameState::Shop => self.update_shop().await,
+            GameState::LearningReport => self.update_learning_report().await,
+            GameState::ProfileEntry => self.update_profile_entry().await,
+            GameState::Settings => self.update_settings().await,
+        }
+        
+        // 菜单和结算界面中产生的事件（例如本局结束）
+        self.process_events();
+        self.update_audio();
+        self.ui.update_toasts(get_frame_time().min(MAX_FRAME_TIME));
+    }
+    
+    // 把队列中的事件依次交给各个消费者处理
+    fn process_events(&mut self) {
+        for event in self.events.take() {
+            self.score += self.scoring.handle_event(&event);
+            self.run_stats.handle_event(&event);
+            match &event {
+                GameEvent::TankDestroyed { .. } => self.enemies_killed_this_wave += 1,
+                GameEvent::PowerUpCollected { power_type, position } => self.apply_powerup(power_type, *position),
+                _ => {}
+            }
+            self.particles.handle_event(&event);
+            self.camera.handle_event(&event, self.player_tank.position);
+            self.audio.handle_event(&event, self.player_tank.position);
+            
+            let unlocked = self.achievements.handle_event(&event);
+            for achievement in &unlocked {
+                self.ui.push_toast(&trf(Text::AchievementToast, &[&achievement.title()]), achievement.summary());
+            }
+            // 解锁新成就或一局结束时保存成就进度
+            if !unlocked.is_empty() || matches!(event, GameEvent::RunEnded { .. }) {
+                self.achievements.progress.save();
+            }
+        }
+    }
+    
+    // 根据战斗激烈程度调整背景音乐
+    fn update_audio(&mut self) {
+        let dt = get_frame_time().min(MAX_FRAME_TIME);
+        self.audio.update(dt);
+        let in_run = match self.state {
+            GameState::Playing | GameState::Paused | GameState::Challenge | GameState::Intermission => true,
+            // 从暂停菜单打开设置时音乐继续播放
+            GameState::Settings => self.settings_return == GameState::Paused,
+            _ => false,
+        };
+        self.audio.update_music(in_run, self.music_intensity(), dt);
+    }
+    
+    // 附近的敌人越多、玩家血量越低，音乐越紧张
+    fn music_intensity(&self) -> f32 {
+        let nearby = self
+            .enemies
+            .values()
+            .filter(|enemy| enemy.tank.position.distance_to(&self.player_tank.position) < 500.0)
+            .count();
+        let health_ratio = self.player_tank.health as f32 / self.player_tank.max_health as f32;
+        (nearby as f32 / 4.0 + (1.0 - health_ratio) * 0.5).clamp(0.0, 1.0)
+    }
+    
+    fn pressed(&self, action: Action) -> bool {
+        self.settings.bindings.pressed(&self.input, action)
+    }
+    
+    // 上下选择菜单项，返回新的下标
+    fn navigate(&self, selected: usize, count: usize) -> usize {
+        if self.pressed(Action::MenuDown) {
+            (selected + 1) % count
+        } else if self.pressed(Action::MenuUp) {
+            (selected + count - 1) % count
+        } else {
+            selected
+        }
+    }
+    
+    pub fn menu_items(&self) -> Vec<MenuItem> {
+        let mut items: Vec<MenuItem> = (0..DIFFICULTIES.len()).map(MenuItem::Play).collect();
+        if self.has_save {
+            items.push(MenuItem::Continue);
         }
+        items.push(MenuItem::Leaderboard);
+        items.push(MenuItem::Achievements);
+        items.push(MenuItem::Shop);
+        items.push(MenuItem::Learning);
+        items.push(MenuItem::Settings);
+        items
     }
     
     async fn update_menu(&mut self) {
-        if is_key_pressed(KeyCode::Key1) {
-            self.start_game(1.0);
-        } else if is_key_pressed(KeyCode::Key2) {
-            self.start_game(1.5);
-        } else if is_key_pressed(KeyCode::Key3) {
-            self.start_game(2.0);
+        let items = self.menu_items();
+        self.menu_selected = self.navigate(self.menu_selected.min(items.len() - 1), items.len());
+        
+        // 快捷键直接选择，确认键选择当前高亮的选项
+        let chosen = items
+            .iter()
+            .copied()
+            .find(|item| is_key_pressed(item.hotkey()))
+            .or_else(|| self.pressed(Action::Confirm).then(|| items[self.menu_selected]));
+        
+        match chosen {
+            Some(MenuItem::Play(index)) => self.start_game(DIFFICULTIES[index].0),
+            Some(MenuItem::Continue) => self.continue_game(),
+            Some(MenuItem::Leaderboard) => {
+                self.leaderboard_page = 0;
+                self.state = GameState::Leaderboard;
+            }
+            Some(MenuItem::Achievements) => {
+                self.achievements_page = 0;
+                self.state = GameState::Achievements;
+            }
+            Some(MenuItem::Shop) => {
+                self.shop_selected = 0;
+                self.state = GameState::Shop;
+            }
+            Some(MenuItem::Learning) => {
+                self.learning_status = None;
+                self.state = GameState::LearningReport;
+            }
+            Some(MenuItem::Settings) => self.open_settings(GameState::Menu),
+            None => {}
         }
     }
     
+    // 保存当前这一局并回到主菜单
+    fn save_and_quit(&mut self) {
+        match self.to_save().save() {
+            Ok(()) => {
+                self.achievements.progress.save();
+                self.has_save = true;
+                self.state = GameState::Menu;
+            }
+            Err(err) => eprintln!("无法保存游戏: {}", err),
+        }
+    }
+    
+    // 读取存档继续游戏；存档读取后即被删除
+    fn continue_game(&mut self) {
+        let save = SaveGame::load();
+        SaveGame::delete();
+        self.has_save = false;
+        
+        if let Some(save) = save {
+            self.restore(save);
+        }
+    }
+    
+    fn to_save(&self) -> SaveGame {
+        SaveGame {
+            version: SaveGame::VERSION,
+            mode: self.mode,
+            difficulty: self.difficulty,
+            score: self.score,
+            wave: self.wave,
+            enemies_killed_this_wave: self.enemies_killed_this_wave,
+            enemies_per_wave: self.enemies_per_wave,
+            time_since_difficulty_increase: self.time_since_difficulty_increase,
+            run_time: self.run_time,
+            player_tank: self.player_tank.clone(),
+            enemies: self.enemies.clone(),
+            bullets: self.bullets.clone(),
+            explosions: self.explosions.clone(),
+            obstacles: self.obstacles.clone(),
+            powerups: self.powerups.clone(),
+            drones: self.drones.clone(),
+            spawn_system: self.spawn_system.clone(),
+            stats: self.run_stats.clone(),
+            achievement_run: self.achievements.run.clone(),
+            upgraded: self.upgraded_run,
+            revives_used: self.revives_used,
+            revive_streak: self.revive_streak,
+            supply_drop: self.supply_drop.clone(),
+        }
+    }
+    
+    fn restore(&mut self, save: SaveGame) {
+        self.mode = save.mode;
+        self.difficulty = save.difficulty;
+        self.score = save.score;
+        self.scoring.reset();
+        self.wave = save.wave;
+        self.enemies_killed_this_wave = save.enemies_killed_this_wave;
+        self.enemies_per_wave = save.enemies_per_wave;
+        self.time_since_difficulty_increase = save.time_since_difficulty_increase;
+        self.run_time = save.run_time;
+        self.player_tank = save.player_tank;
+        self.enemies = save.enemies;
+        self.bullets = save.bullets;
+        self.explosions = save.explosions;
+        self.obstacles = save.obstacles;
+        self.powerups = save.powerups;
+        self.drones = save.drones;
+        self.spawn_system = save.spawn_system;
+        self.run_stats = save.stats;
+        self.achievements.run = save.achievement_run;
+        self.upgraded_run = save.upgraded;
+        self.revives_used = save.revives_used;
+        self.revive_streak = save.revive_streak;
+        self.supply_drop = save.supply_drop;
+        
+        self.particles.clear();
+        self.camera.snap_to(self.player_tank.position);
+        self.challenge = None;
+        self.accumulator = 0.0;
+        self.last_rank = None;
+        self.missed_answer = None;
+        self.state = GameState::Playing;
+        self.events.clear();
+        self.events.push(GameEvent::WaveStarted { wave: self.wave });
+    }
+    
     async fn update_playing(&mut self) {
-        if is_key_pressed(KeyCode::Escape) {
+        if self.pressed(Action::Pause) {
+            self.pause_selected = 0;
             self.state = GameState::Paused;
             return;
         }
         
-        let dt = get_frame_time();
+        // 切换武器（按键事件每个渲染帧只读取一次，不放在逻辑帧中）
+        if self.pressed(Action::NextWeapon) {
+            self.player_tank.next_weapon();
+        }
+        
+        // 补给箱题目按真实时间计时，答题期间游戏放慢
+        self.update_supply_drop();
+        let time_scale = if self.supply_drop.is_some() { SLOW_MOTION } else { 1.0 };
+        
+        // 固定时间步长：累积真实帧时间，按固定间隔推进模拟
+        self.accumulator += get_frame_time().min(MAX_FRAME_TIME) * time_scale;
+        while self.accumulator >= FIXED_DT {
+            self.tick(FIXED_DT);
+            self.accumulator -= FIXED_DT;
+            
+            // 玩家死亡等状态切换后停止推进
+            if self.state != GameState::Playing {
+                break;
+            }
+        }
+    }
+    
+    // 推进一个固定步长的游戏逻辑
+    fn tick(&mut self, dt: f32) {
+        self.run_time += dt as f64;
+        
+        // 记录上一逻辑帧的位置，用于渲染插值，并推进冷却和护盾计时
+        self.player_tank.store_previous_position();
+        self.player_tank.update_timers(dt);
+        for enemy in self.enemies.values_mut() {
+            let tank = &mut enemy.tank;
+            tank.store_previous_position();
+            tank.update_timers(dt);
+        }
         
         // 处理玩家输入
-        let new_bullets = handle_player_input(&mut self.player_tank, dt);
+        let mouse_world = self.camera.screen_to_world(self.input.mouse_position());
+        let mouse_world = Position::new(mouse_world.x, mouse_world.y);
+        // 补给箱打开时，用来作答的数字键和十字键不再控制坦克
+        let controls = if self.supply_drop.is_some() {
+            let input = MaskedInput::new(&self.input, &ANSWER_INPUTS);
+            self.settings.bindings.player_controls(&input, mouse_world)
+        } else {
+            self.settings.bindings.player_controls(&self.input, mouse_world)
+        };
+        let new_bullets = handle_player_input(&mut self.player_tank, dt, &controls);
+        if !new_bullets.is_empty() {
+            self.events.push(Self::shot_event(&self.player_tank, new_bullets.len()));
+        }
         self.bullets.extend(new_bullets);
         
         // 更新玩家坦克 - 使用安全移动
-        self.player_tank.safe_move(dt, &self.obstacles);
+        if self.player_tank.safe_move(dt, &self.obstacles) {
+            self.events.push(GameEvent::PlayerBlocked);
+        }
+        Self::emit_tank_effects(&mut self.particles, &self.player_tank, dt);
+        let travelled = self.player_tank.position.distance_to(&self.player_tank.prev_position);
+        self.run_stats.advance(dt, travelled);
         
         // 更新敌方坦克
-        for (tank, ai) in self.enemy_tanks.iter_mut().zip(self.enemy_ais.iter_mut()) {
-            ai.update(tank, &self.player_tank, &self.obstacles);
+        for Enemy { tank, ai } in self.enemies.values_mut() {
+            ai.update(tank, &self.player_tank, &self.obstacles, dt);
             // 使用安全移动，防止卡在障碍物中
             tank.safe_move(dt, &self.obstacles);
+            Self::emit_tank_effects(&mut self.particles, tank, dt);
             
             // 敌方坦克射击
             let distance = tank.position.distance_to(&self.player_tank.position);
-            if tank.can_shoot() && ai.should_shoot(tank, &self.player_tank, distance) {
-                tank.shoot();
-                let bullet_x = tank.position.x + tank.angle.cos() * (tank.size + 5.0);
-                let bullet_y = tank.position.y + tank.angle.sin() * (tank.size + 5.0);
-                self.bullets.push(Bullet::new(bullet_x, bullet_y, tank.angle, false));
+            if tank.can_shoot() && ai.should_shoot(tank, &self.player_tank, distance, dt) {
+                // 迫击炮直接瞄准玩家当前位置
+                let new_bullets = tank.fire(self.player_tank.position);
+                self.events.push(Self::shot_event(tank, new_bullets.len()));
+                self.bullets.extend(new_bullets);
+            }
+        }
+        
+        // 无人机环绕玩家，自动攻击附近的敌人
+        for drone in &mut self.drones {
+            if let Some(bullet) = drone.update(self.player_tank.position, &self.enemies, dt) {
+                self.events.push(GameEvent::ShotFired {
+                    weapon: Weapon::Cannon,
+                    position: drone.position,
+                    muzzle: drone.position,
+                    angle: bullet.velocity.y.atan2(bullet.velocity.x),
+                    from_player: true,
+                    bullets: 1,
+                });
+                self.bullets.push(bullet);
             }
         }
         
-        // 更新子弹
-        self.bullets.retain(|bullet| {
-            let mut b = bullet.clone();
-            b.update(dt)
+        // 更新子弹，落地的迫击炮弹产生爆炸
+        let explosions = &mut self.explosions;
+        self.bullets.retain_mut(|bullet| {
+            let alive = bullet.update(dt);
+            if !alive && bullet.kind == BulletKind::Mortar {
+                explosions.push(Explosion::new_mortar(bullet.position.x, bullet.position.y, bullet.from_player));
+            }
+            alive
         });
-        for bullet in &mut self.bullets {
-            bullet.update(dt);
+        for bullet in &self.bullets {
+            if bullet.kind == BulletKind::Shell {
+                self.particles.emit_bullet_trail(bullet.position, bullet.color);
+            }
         }
         
         // 更新道具
@@ -179,161 +705,683 @@ impl Game {
             powerup.update(dt);
         }
         
-        // 碰撞检测
-        let destroyed_tanks = check_bullet_tank_collisions(&mut self.bullets, &mut self.enemy_tanks, &mut self.player_tank);
+        // 碰撞检测，被摧毁的坦克和收集的道具通过事件结算
+        check_bullet_tank_collisions(
+            &mut self.bullets,
+            &mut self.enemies,
+            &mut self.player_tank,
+            &mut self.explosions,
+            &mut self.events,
+        );
+        check_bullet_obstacle_collisions(&mut self.bullets, &mut self.obstacles, &mut self.explosions, &mut self.events);
         
-        // 移除被摧毁的敌方坦克和对应的AI
-        for &tank_idx in destroyed_tanks.iter().rev() {
-            if tank_idx < self.enemy_tanks.len() {
-                self.enemy_tanks.remove(tank_idx);
-                self.enemy_ais.remove(tank_idx);
-                self.score += 100;
-                self.enemies_killed_this_wave += 1;
-            }
-        }
+        // 结算爆炸伤害（连锁反应中被摧毁的坦克会产生新的爆炸）
+        check_explosion_collisions(
+            &mut self.explosions,
+            &mut self.enemies,
+            &mut self.player_tank,
+            &mut self.obstacles,
+            &mut self.events,
+        );
+        self.remove_destroyed_enemies();
+        self.explosions.retain_mut(|explosion| explosion.update(dt));
         
-        check_bullet_obstacle_collisions(&mut self.bullets, &mut self.obstacles);
+        check_powerup_collisions(&mut self.player_tank, &mut self.powerups, &mut self.events);
         
-        // 处理道具收集
-        let collected_powerups = check_powerup_collisions(&mut self.player_tank, &mut self.powerups);
-        for powerup_type in collected_powerups {
-            self.apply_powerup(powerup_type);
-        }
+        // 分发本逻辑帧的事件：计分、特效、音效等
+        self.process_events();
         
-        // 生成系统更新
-        self.spawn_system.update(&mut self.enemy_tanks, &mut self.powerups, &self.obstacles);
+        // 更新粒子、连击计时和得分飘字
+        self.particles.update(dt);
+        self.scoring.update(dt);
         
-        // 为新生成的敌人创建AI
-        while self.enemy_ais.len() < self.enemy_tanks.len() {
-            self.enemy_ais.push(EnemyAI::new_with_difficulty(self.difficulty));
-        }
+        // 镜头跟随玩家
+        self.camera.follow(self.player_tank.position, dt);
+        self.camera.update(dt);
+        
+        // 生成系统更新
+        self.spawn_system.update(
+            &mut self.enemies,
+            &mut self.powerups,
+            &self.obstacles,
+            self.difficulty,
+            self.settings.supply_drops,
+            dt,
+        );
         
         // 检查波数完成
-        if self.enemies_killed_this_wave >= self.enemies_per_wave && self.enemy_tanks.is_empty() {
+        if self.enemies_killed_this_wave >= self.enemies_per_wave && self.enemies.is_empty() {
             self.next_wave();
         }
         
         // 定期增加难度
-        if get_time() - self.last_difficulty_increase > 30.0 {
+        self.time_since_difficulty_increase += dt as f64;
+        if self.time_since_difficulty_increase > 30.0 {
             self.spawn_system.increase_difficulty();
-            self.last_difficulty_increase = get_time();
+            self.time_since_difficulty_increase = 0.0;
         }
         
         // 检查玩家死亡
         if self.player_tank.health <= 0 {
-            // 生成数学挑战
-            self.math_challenge = Some(MathChallenge::new_random());
-            self.state = GameState::MathChallenge;
+            self.events.push(GameEvent::PlayerDied);
+            // 死亡时正在解答的补给箱作废
+            self.supply_drop = None;
+            let revives_left = self.settings.math_revives == 0 || self.revives_used < self.settings.math_revives;
+            if self.settings.math_challenge && revives_left {
+                self.challenge = Some(self.create_challenge());
+                // 丢弃游戏中按下的字符，避免带入答案
+                while get_char_pressed().is_some() {}
+                self.state = GameState::Challenge;
+                self.events.push(GameEvent::ChallengeStarted);
+            } else {
+                self.finish_run();
+            }
         }
     }
     
     async fn update_paused(&mut self) {
-        if is_key_pressed(KeyCode::Escape) {
+        if self.pressed(Action::Pause) || self.pressed(Action::Back) {
             self.state = GameState::Playing;
+            return;
+        }
+        
+        self.pause_selected = self.navigate(self.pause_selected, PauseItem::ALL.len());
+        let chosen = PauseItem::ALL
+            .iter()
+            .copied()
+            .find(|item| item.hotkey().is_some_and(is_key_pressed))
+            .or_else(|| self.pressed(Action::Confirm).then(|| PauseItem::ALL[self.pause_selected]));
+        
+        match chosen {
+            Some(PauseItem::Resume) => self.state = GameState::Playing,
+            Some(PauseItem::SaveAndQuit) => self.save_and_quit(),
+            Some(PauseItem::Settings) => self.open_settings(GameState::Paused),
+            None => {}
+        }
+    }
+    
+    fn open_settings(&mut self, return_state: GameState) {
+        self.settings_selected = 0;
+        self.settings_return = return_state;
+        self.rebinding = None;
+        self.state = GameState::Settings;
+    }
+    
+    async fn update_settings(&mut self) {
+        // 等待新按键：ESC 取消，其他按键、鼠标按键或手柄按钮成为新的绑定
+        if let Some(action) = self.rebinding {
+            if is_key_pressed(KeyCode::Escape) {
+                self.rebinding = None;
+            } else if let Some(binding) = self.input.last_pressed().filter(|b| b.is_bindable()) {
+                self.settings.bindings.rebind(action, binding);
+                self.rebinding = None;
+            }
+            return;
+        }
+        
+        let items = SettingsItem::all();
+        self.settings_selected = self.navigate(self.settings_selected, items.len());
+        let item = items[self.settings_selected];
+        
+        if let SettingsItem::Bind(action) = item {
+            if self.pressed(Action::Confirm) {
+                self.rebinding = Some(action);
+            }
+        } else {
+            let direction = if self.pressed(Action::MenuRight) || self.pressed(Action::Confirm) {
+                1
+            } else if self.pressed(Action::MenuLeft) {
+                -1
+            } else {
+                0
+            };
+            if direction != 0 && item == SettingsItem::QuestionPack {
+                // 每次切换时重新读取题库文件夹，新放入的题库不用重启游戏
+                self.question_packs = QuestionPacks::load();
+                self.settings.question_pack =
+                    self.question_packs.cycle(self.settings.question_pack.as_deref(), direction);
+            } else if direction != 0 {
+                // 修改立即生效
+                item.adjust(&mut self.settings, direction);
+                self.apply_settings();
+                if item.affects_window() {
+                    self.apply_window_settings();
+                }
+            }
+        }
+        
+        // 离开设置界面时保存
+        if self.pressed(Action::Back) {
+            self.settings.save();
+            self.state = self.settings_return;
+        }
+    }
+    
+    // 将设置应用到各个子系统
+    fn apply_settings(&mut self) {
+        self.fonts.apply(self.settings.language.unwrap_or_else(Locale::from_env));
+        self.ui.hud_scale = self.settings.hud_scale;
+        self.ui.palette = Palette::new(self.settings.colorblind);
+        self.camera.shake_enabled = self.settings.screen_shake;
+        if self.particles.quality != self.settings.particle_quality {
+            self.particles.set_quality(self.settings.particle_quality);
+        }
+        self.audio.set_volumes(self.settings.master_volume, self.settings.music_volume, self.settings.sfx_volume);
+    }
+    
+    // 窗口模式和分辨率只在修改时应用，启动时由 window_conf 设置
+    fn apply_window_settings(&self) {
+        match self.settings.window_mode {
+            WindowMode::Fullscreen => set_fullscreen(true),
+            WindowMode::Windowed => {
+                set_fullscreen(false);
+                let (width, height) = self.settings.window_size();
+                request_new_screen_size(width as f32, height as f32);
+            }
         }
     }
     
     async fn update_game_over(&mut self) {
-        if is_key_pressed(KeyCode::R) {
+        if is_key_pressed(KeyCode::R) || self.pressed(Action::Confirm) {
             self.state = GameState::Menu;
         }
     }
     
-    async fn update_math_challenge(&mut self) {
-        if let Some(ref mut challenge) = self.math_challenge {
-            // 处理数字输入
-            for key_code in [
-                KeyCode::Key0, KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4,
-                KeyCode::Key5, KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9,
-            ] {
-                if is_key_pressed(key_code) {
-                    let digit = match key_code {
-                        KeyCode::Key0 => '0',
-                        KeyCode::Key1 => '1',
-                        KeyCode::Key2 => '2',
-                        KeyCode::Key3 => '3',
-                        KeyCode::Key4 => '4',
-                        KeyCode::Key5 => '5',
-                        KeyCode::Key6 => '6',
-                        KeyCode::Key7 => '7',
-                        KeyCode::Key8 => '8',
-                        KeyCode::Key9 => '9',
-                        _ => continue,
+    // 从当前档案启用的挑战中随机选一种，本局每复活一次难度提高一档
+    fn create_challenge(&self) -> ChallengeSession {
+        let mut rng = thread_rng();
+        let profile = self.learning.profile();
+        let tier = self.settings.math_level.tier_for_wave(self.wave) + self.revives_used;
+        let multiple_choice = self.settings.math_choices;
+        let kind = *profile.revive_challenges().choose(&mut rng).unwrap_or(&ChallengeKind::Arithmetic);
+        let challenge: Box<dyn Challenge> = match kind {
+            // 选择了题库时用题库中的题目代替内置的数学题
+            ChallengeKind::Arithmetic => match self.selected_pack() {
+                Some(pack) => Box::new(PackChallenge::new(pack, tier, multiple_choice, &mut rng)),
+                None => {
+                    let topics = self.settings.math_grade.topics(&self.settings.math_topics);
+                    // 学习模式下优先复习答错的题，并更多地练习薄弱题型
+                    let mut challenge = if self.settings.learning_mode {
+                        MathChallenge::new(adaptive::choose_problem(profile, &topics, tier, &mut rng))
+                    } else {
+                        MathChallenge::generate(&topics, tier)
                     };
-                    challenge.add_digit(digit);
-                }
-            }
-            
-            // 处理退格键
-            if is_key_pressed(KeyCode::Backspace) {
-                challenge.remove_digit();
-            }
-            
-            // 处理回车键提交答案
-            if is_key_pressed(KeyCode::Enter) {
-                if challenge.submit_answer() {
-                    // 答案正确，复活玩家
-                    self.player_tank.health = self.player_tank.max_health / 2; // 复活时恢复一半血量
-                    self.math_challenge = None;
-                    self.state = GameState::Playing;
-                } else {
-                    // 答案错误，游戏结束
-                    if self.score > self.high_score {
-                        self.high_score = self.score;
+                    if multiple_choice {
+                        challenge.offer_choices(&mut rng);
                     }
-                    self.math_challenge = None;
-                    self.state = GameState::GameOver;
+                    Box::new(challenge)
                 }
+            },
+            ChallengeKind::TimesTables => Box::new(TimesTableChallenge::new(&mut rng, tier, multiple_choice)),
+            ChallengeKind::Spelling => Box::new(SpellingChallenge::new(&mut rng, tier)),
+            ChallengeKind::Vocabulary => Box::new(VocabularyChallenge::new(&mut rng, tier)),
+            ChallengeKind::Geography => Box::new(GeographyChallenge::new(&mut rng, tier)),
+            ChallengeKind::Reflex => Box::new(ReflexChallenge::new(&mut rng, tier)),
+        };
+        let time_limit = (self.settings.math_time_limit > 0).then_some(self.settings.math_time_limit as f32);
+        ChallengeSession::new(challenge, time_limit)
+    }
+    
+    // 设置界面中选中的行的说明，出错时显示为橙色
+    fn settings_notice(&self) -> Option<(String, bool)> {
+        match SettingsItem::all().get(self.settings_selected)? {
+            SettingsItem::Language => Some(self.language_notice()),
+            SettingsItem::QuestionPack => Some(self.pack_notice()),
+            _ => None,
+        }
+    }
+    
+    // 没有中文字体时界面仍是英文，提示玩家把字体放到哪里
+    fn language_notice(&self) -> (String, bool) {
+        if self.settings.language.unwrap_or_else(Locale::from_env) != locale() {
+            return (trf(Text::LanguageFontMissing, &[&cjk_font_hint().display()]), true);
+        }
+        (tr(Text::LanguageNote).to_string(), false)
+    }
+    
+    fn pack_notice(&self) -> (String, bool) {
+        if let Some(error) = self.question_packs.errors.first() {
+            let more = match self.question_packs.errors.len() {
+                1 => String::new(),
+                count => format!(" {}", trf(Text::PacksWithErrors, &[&count])),
+            };
+            return (format!("{}{}", error, more), true);
+        }
+        match self.selected_pack() {
+            Some(pack) => (trf(Text::PackSummary, &[&pack.questions.len(), &pack.description]), false),
+            None => (tr(Text::PackFolderHint).to_string(), false),
+        }
+    }
+    
+    // 设置中选择的题库；题库文件被删除或出错时退回内置题目
+    fn selected_pack(&self) -> Option<&QuestionPack> {
+        self.settings.question_pack.as_deref().and_then(|name| self.question_packs.find(name))
+    }
+    
+    // 收集答题界面本帧的输入
+    fn challenge_input(&self) -> ChallengeInput {
+        let mut chars = Vec::new();
+        while let Some(c) = get_char_pressed() {
+            chars.push(c);
+        }
+        ChallengeInput {
+            chars,
+            backspace: is_key_pressed(KeyCode::Backspace),
+            confirm: self.pressed(Action::Confirm),
+            left: self.pressed(Action::MenuLeft),
+            right: self.pressed(Action::MenuRight),
+            up: self.pressed(Action::MenuUp),
+            down: self.pressed(Action::MenuDown),
+            pad_used: self.input.gamepads.last_pressed().is_some(),
+            mouse: Vec2::from(mouse_position()),
+            mouse_moved: mouse_delta_position() != Vec2::ZERO,
+            clicked: is_mouse_button_pressed(MouseButton::Left),
+        }
+    }
+    
+    async fn update_challenge(&mut self) {
+        let give_up = self.pressed(Action::Back);
+        let input = self.challenge_input();
+        let dt = get_frame_time().min(MAX_FRAME_TIME);
+        let Some(session) = self.challenge.as_mut() else {
+            return;
+        };
+        
+        // 超时视为答错
+        if session.tick(dt) {
+            if let Some((problem, _)) = session.challenge.learning_record() {
+                self.learning.record(problem, "", false, session.elapsed);
+                self.learning.save();
             }
-            
-            // ESC键直接游戏结束
-            if is_key_pressed(KeyCode::Escape) {
-                if self.score > self.high_score {
-                    self.high_score = self.score;
-                }
-                self.math_challenge = None;
-                self.state = GameState::GameOver;
+            self.missed_answer = Some(session.challenge.correct_answer());
+            self.revive_streak = 0;
+            self.events.push(GameEvent::ChallengeAnswered { correct: false });
+            self.finish_run();
+            return;
+        }
+        
+        // ESC键直接游戏结束
+        if give_up {
+            self.revive_streak = 0;
+            self.finish_run();
+            return;
+        }
+        
+        let Some(correct) = session.challenge.update(&input, dt) else {
+            return;
+        };
+        
+        // 数学题记入学习档案
+        if let Some((problem, given)) = session.challenge.learning_record() {
+            self.learning.record(problem, given, correct, session.elapsed);
+            self.learning.save();
+        }
+        
+        self.events.push(GameEvent::ChallengeAnswered { correct });
+        if correct {
+            // 答案正确，复活玩家；血量取决于答题速度和连续答对次数
+            let fraction = session.revive_fraction(self.revive_streak);
+            self.player_tank.health = ((self.player_tank.max_health as f32 * fraction).round() as i32).max(1);
+            self.revives_used += 1;
+            self.revive_streak += 1;
+            self.challenge = None;
+            // 清波的同一帧死亡时，复活后继续选择强化
+            self.state = if self.perk_offers.is_empty() {
+                GameState::Playing
+            } else {
+                GameState::Intermission
+            };
+        } else {
+            // 答案错误，游戏结束
+            self.missed_answer = Some(session.challenge.correct_answer());
+            self.revive_streak = 0;
+            self.finish_run();
+        }
+    }
+    
+    // 本局结束：成绩能进入排行榜时先输入名字，否则直接显示游戏结束画面
+    fn finish_run(&mut self) {
+        if self.score > self.high_score {
+            self.high_score = self.score;
+        }
+        self.challenge = None;
+        self.supply_drop = None;
+        self.last_rank = None;
+        self.events.push(GameEvent::RunEnded { score: self.score });
+        
+        // 先结算队列中剩余的事件，保证统计完整，再写入历史记录
+        self.process_events();
+        self.stats_history.record(RunRecord {
+            date: current_date(),
+            mode: self.mode,
+            difficulty: self.difficulty,
+            score: self.score,
+            wave: self.wave,
+            stats: self.run_stats.clone(),
+        });
+        self.stats_history.save();
+        
+        self.last_scrap = Progression::scrap_for_run(self.score, self.wave);
+        self.progression.award(self.last_scrap);
+        self.progression.save();
+        
+        if self.leaderboard.qualifies(&self.board_key(), self.score) {
+            // 清空游戏过程中积累的字符输入
+            while get_char_pressed().is_some() {}
+            self.player_name = self.leaderboard.last_name.clone();
+            self.state = GameState::NameEntry;
+        } else {
+            self.state = GameState::GameOver;
+        }
+    }
+    
+    async fn update_name_entry(&mut self) {
+        while let Some(c) = get_char_pressed() {
+            let allowed = c.is_alphanumeric() || c == ' ' || c == '_' || c == '-';
+            if allowed && self.player_name.chars().count() < Leaderboard::MAX_NAME_LEN {
+                self.player_name.push(c);
             }
         }
+        
+        if is_key_pressed(KeyCode::Backspace) {
+            self.player_name.pop();
+        }
+        
+        if self.pressed(Action::Confirm) {
+            let name = match self.player_name.trim() {
+                "" => "Player".to_string(),
+                name => name.to_string(),
+            };
+            let entry = ScoreEntry {
+                name,
+                score: self.score,
+                wave: self.wave,
+                duration_secs: self.run_time,
+                date: current_date(),
+            };
+            self.last_rank = self.leaderboard.insert(&self.board_key(), entry);
+            self.leaderboard.save();
+            self.state = GameState::GameOver;
+        }
     }
     
-    fn apply_powerup(&mut self, powerup_type: PowerUpType) {
-        match powerup_type {
-            PowerUpType::Health => {
-                self.player_tank.heal(50);
-                self.score += 20;
+    async fn update_leaderboard(&mut self) {
+        let pages = Self::board_pages().len();
+        if self.pressed(Action::MenuRight) {
+            self.leaderboard_page = (self.leaderboard_page + 1) % pages;
+        }
+        if self.pressed(Action::MenuLeft) {
+            self.leaderboard_page = (self.leaderboard_page + pages - 1) % pages;
+        }
+        if self.pressed(Action::Back) || self.pressed(Action::Confirm) {
+            self.state = GameState::Menu;
+        }
+    }
+    
+    async fn update_achievements(&mut self) {
+        let pages = self.achievements.definitions.len().div_ceil(ACHIEVEMENTS_PER_PAGE).max(1);
+        if self.pressed(Action::MenuRight) {
+            self.achievements_page = (self.achievements_page + 1) % pages;
+        }
+        if self.pressed(Action::MenuLeft) {
+            self.achievements_page = (self.achievements_page + pages - 1) % pages;
+        }
+        if self.pressed(Action::Back) || self.pressed(Action::Confirm) {
+            self.state = GameState::Menu;
+        }
+    }
+    
+    async fn update_learning_report(&mut self) {
+        let direction = if self.pressed(Action::MenuRight) {
+            1
+        } else if self.pressed(Action::MenuLeft) {
+            -1
+        } else {
+            0
+        };
+        if direction != 0 {
+            self.learning.cycle(direction);
+            self.learning.save();
+            self.learning_status = None;
+        }
+        // 数字键 1-6 开关复活时出现的挑战类型
+        while let Some(c) = get_char_pressed() {
+            let index = c.to_digit(10).and_then(|digit| (digit as usize).checked_sub(1));
+            if let Some(&kind) = index.and_then(|index| ChallengeKind::ALL.get(index)) {
+                self.learning.profile_mut().toggle_challenge(kind);
+                self.learning.save();
             }
-            PowerUpType::Shield => {
-                self.player_tank.add_shield(30.0);
-                self.score += 30;
+        }
+        if is_key_pressed(KeyCode::N) {
+            self.profile_name.clear();
+            self.state = GameState::ProfileEntry;
+            return;
+        }
+        if is_key_pressed(KeyCode::X) {
+            self.learning_status = Some(match self.learning.export_csv() {
+                Ok(path) => trf(Text::ExportedTo, &[&path.display()]),
+                Err(err) => trf(Text::ExportFailed, &[&err]),
+            });
+        }
+        if self.pressed(Action::Back) || self.pressed(Action::Confirm) {
+            self.state = GameState::Menu;
+        }
+    }
+    
+    async fn update_profile_entry(&mut self) {
+        while let Some(c) = get_char_pressed() {
+            let allowed = c.is_alphanumeric() || c == ' ' || c == '_' || c == '-';
+            if allowed && self.profile_name.chars().count() < LearningData::MAX_NAME_LEN {
+                self.profile_name.push(c);
             }
-            PowerUpType::ScatterShot => {
-                self.player_tank.scatter_shot = true;
-                // 散弹效果持续15秒
-                self.score += 25;
+        }
+        
+        if is_key_pressed(KeyCode::Backspace) {
+            self.profile_name.pop();
+        }
+        
+        if self.pressed(Action::Confirm) {
+            let name = self.profile_name.trim();
+            if !name.is_empty() {
+                self.learning.select_or_create(name);
+                self.learning.save();
             }
+            self.learning_status = None;
+            self.state = GameState::LearningReport;
+        } else if self.pressed(Action::Back) {
+            self.state = GameState::LearningReport;
+        }
+    }
+    
+    async fn update_shop(&mut self) {
+        self.shop_selected = self.navigate(self.shop_selected, Upgrade::ALL.len());
+        if self.pressed(Action::Confirm) && self.progression.buy(Upgrade::ALL[self.shop_selected]) {
+            self.progression.save();
+        }
+        if is_key_pressed(KeyCode::V) {
+            self.progression.vanilla = !self.progression.vanilla;
+            self.progression.save();
+        }
+        if self.pressed(Action::Back) {
+            self.state = GameState::Menu;
+        }
+    }
+    
+    // 排行榜按 "模式/难度" 分榜，使用了升级的成绩另外分榜
+    fn board_key_for(mode: GameMode, difficulty: f32, upgraded: bool) -> String {
+        let key = format!("{}/{}", mode.key(), difficulty_name(difficulty).to_lowercase());
+        if upgraded {
+            key + "/upgraded"
+        } else {
+            key
+        }
+    }
+    
+    fn board_key(&self) -> String {
+        Self::board_key_for(self.mode, self.difficulty, self.upgraded_run)
+    }
+    
+    // 排行榜界面的所有分页
+    fn board_pages() -> Vec<(GameMode, f32, bool)> {
+        [false, true]
+            .iter()
+            .flat_map(|&upgraded| {
+                GameMode::ALL.iter().flat_map(move |&mode| {
+                    DIFFICULTIES.iter().map(move |&(difficulty, _)| (mode, difficulty, upgraded))
+                })
+            })
+            .collect()
+    }
+    
+    // 坦克移动留下履带印，受损时冒烟
+    fn emit_tank_effects(particles: &mut ParticleSystem, tank: &Tank, dt: f32) {
+        let distance = tank.position.distance_to(&tank.prev_position);
+        if distance > 0.0 {
+            particles.emit_tread_marks(tank.position, tank.angle, tank.size, distance);
+        }
+        particles.emit_smoke(tank.position, tank.health as f32 / tank.max_health as f32, dt);
+    }
+    
+    // 移除被摧毁的敌方坦克（击杀已经通过 TankDestroyed 事件结算）
+    fn remove_destroyed_enemies(&mut self) {
+        self.enemies.retain(|_, enemy| enemy.tank.health > 0);
+    }
+    
+    fn shot_event(tank: &Tank, bullets: usize) -> GameEvent {
+        GameEvent::ShotFired {
+            weapon: tank.weapon,
+            position: tank.position,
+            muzzle: tank.muzzle_position(),
+            angle: tank.angle,
+            from_player: tank.is_player,
+            bullets: bullets as u32,
+        }
+    }
+    
+    // 道具效果，得分由 points_for 结算
+    fn apply_powerup(&mut self, powerup_type: &PowerUpType, position: Position) {
+        match powerup_type {
+            PowerUpType::Health => self.player_tank.heal(50),
+            PowerUpType::Shield => self.player_tank.add_shield(30.0),
+            // 散弹效果持续15秒
+            PowerUpType::ScatterShot => self.player_tank.scatter_shot = true,
             PowerUpType::SpeedBoost => {
                 self.player_tank.speed = (self.player_tank.speed * 1.5).min(300.0);
-                self.score += 25;
             }
-            PowerUpType::Damage => {
-                // 这里可以增加伤害，暂时增加分数
-                self.score += 40;
+            // 这里可以增加伤害，暂时只加分
+            PowerUpType::Damage => {}
+            // 已经在答题时拾取的补给箱直接丢弃
+            PowerUpType::SupplyDrop if self.supply_drop.is_some() => {}
+            PowerUpType::SupplyDrop => {
+                let topics = self.settings.math_grade.topics(&self.settings.math_topics);
+                let tier = self.settings.math_level.tier_for_wave(self.wave);
+                self.supply_drop = Some(SupplyDrop::new(&topics, tier, position));
+                // 丢弃游戏中按下的字符，避免带入答案
+                while get_char_pressed().is_some() {}
             }
         }
     }
     
+    // 补给箱题目：数字键 1-4 或十字键（上、右、下、左）作答，超时或答错则补给箱作废
+    fn update_supply_drop(&mut self) {
+        let pad_choice = match self.input.gamepads.last_pressed() {
+            Some(PadButton::DPadUp) => Some(0),
+            Some(PadButton::DPadRight) => Some(1),
+            Some(PadButton::DPadDown) => Some(2),
+            Some(PadButton::DPadLeft) => Some(3),
+            _ => None,
+        };
+        let Some(drop) = self.supply_drop.as_mut() else {
+            return;
+        };
+        
+        let mut chosen = pad_choice;
+        while let Some(c) = get_char_pressed() {
+            let index = c.to_digit(10).and_then(|digit| (digit as usize).checked_sub(1));
+            chosen = chosen.or(index.filter(|&index| index < drop.challenge.choices.len()));
+        }
+        let correct = match chosen {
+            Some(index) => drop.challenge.choose(index),
+            None if drop.tick(get_frame_time().min(MAX_FRAME_TIME)) => false,
+            None => return,
+        };
+        
+        let Some(drop) = self.supply_drop.take() else {
+            return;
+        };
+        self.learning.record(&drop.challenge.problem, drop.challenge.get_user_answer(), correct, drop.elapsed());
+        self.learning.save();
+        self.events.push(GameEvent::SupplyDropAnswered {
+            correct,
+            tier: drop.challenge.problem.tier,
+            position: drop.position,
+        });
+        if correct {
+            drop.reward.apply(&mut self.player_tank);
+            self.ui.push_toast(&trf(Text::SupplyDropUnlocked, &[&drop.reward.name()]), drop.reward.description());
+        } else {
+            let answer = trf(Text::AnswerWas, &[&drop.challenge.problem.answer]);
+            self.ui.push_toast(tr(Text::SupplyDropLost), &answer);
+        }
+    }
+    
     fn next_wave(&mut self) {
+        self.events.push(GameEvent::WaveCleared { wave: self.wave });
         self.wave += 1;
         self.enemies_killed_this_wave = 0;
         self.enemies_per_wave += 2;
-        self.score += self.wave * 50;
         
         // 增加难度
         self.spawn_system.increase_difficulty();
         
         // 恢复玩家一些生命值
         self.player_tank.heal(25);
+        
+        // 进入波次间隙，从随机的强化中选择一个
+        let drones = self.drones.len();
+        let tank = &self.player_tank;
+        self.perk_offers = roll_perks(&mut thread_rng(), 3, |perk| perk.is_available(tank, drones));
+        self.perk_selected = 0;
+        if self.perk_offers.is_empty() {
+            self.events.push(GameEvent::WaveStarted { wave: self.wave });
+        } else {
+            self.state = GameState::Intermission;
+        }
+    }
+    
+    async fn update_intermission(&mut self) {
+        let count = self.perk_offers.len();
+        if count == 0 {
+            self.state = GameState::Playing;
+            return;
+        }
+        if self.pressed(Action::MenuRight) {
+            self.perk_selected = (self.perk_selected + 1) % count;
+        }
+        if self.pressed(Action::MenuLeft) {
+            self.perk_selected = (self.perk_selected + count - 1) % count;
+        }
+        
+        // 数字键直接选择，确认键选择当前高亮的强化
+        let hotkeys = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3];
+        let chosen = hotkeys
+            .iter()
+            .take(count)
+            .position(|key| is_key_pressed(*key))
+            .or_else(|| self.pressed(Action::Confirm).then_some(self.perk_selected));
+        
+        if let Some(index) = chosen {
+            let perk = self.perk_offers[index];
+            perk.apply(&mut self.player_tank);
+            if perk == Perk::Drone {
+                self.drones.push(Drone::new(self.player_tank.position, 0.0));
+                Drone::spread(&mut self.drones);
+            }
+            self.perk_offers.clear();
+            self.accumulator = 0.0;
+            self.state = GameState::Playing;
+            self.events.push(GameEvent::WaveStarted { wave: self.wave });
+        }
     }
     
     pub async fn draw(&self) {
@@ -341,36 +1389,125 @@ impl Game {
         
         match self.state {
             GameState::Menu => {
-                self.ui.draw_start_menu(self.high_score);
+                let labels: Vec<String> = self.menu_items().iter().map(|item| item.label()).collect();
+                self.ui.draw_start_menu(self.high_score, &labels, self.menu_selected);
             }
             GameState::Playing => {
                 self.draw_game();
+                self.ui.draw_minimap(
+                    &self.player_tank,
+                    &self.enemies,
+                    &self.obstacles,
+                    &self.powerups,
+                    self.camera.view_rect(),
+                );
                 self.ui.draw_hud(
                     self.player_tank.health,
                     self.player_tank.max_health,
                     self.score,
                     self.wave,
                     self.difficulty,
+                    self.player_tank.weapon.name(),
+                    &self.scoring,
+                    &self.settings.bindings.controls_help(),
                 );
+                if let Some(ref drop) = self.supply_drop {
+                    self.ui.draw_supply_drop(drop);
+                }
             }
             GameState::Paused => {
                 self.draw_game();
-                self.ui.draw_pause_menu();
+                let labels: Vec<&str> = PauseItem::ALL.iter().map(|item| item.label()).collect();
+                self.ui.draw_pause_menu(&labels, self.pause_selected);
+            }
+            GameState::Settings => {
+                if self.settings_return == GameState::Paused {
+                    self.draw_game();
+                }
+                let notice = self.settings_notice();
+                self.ui.draw_settings(&self.settings, self.settings_selected, self.rebinding.is_some(), notice);
             }
             GameState::GameOver => {
                 self.draw_game();
-                self.ui.draw_game_over(self.score, self.wave, self.high_score);
+                self.ui.draw_game_over(
+                    self.score,
+                    self.wave,
+                    self.high_score,
+                    self.last_rank,
+                    self.missed_answer.as_deref(),
+                    self.last_scrap,
+                    &self.run_stats,
+                    &self.stats_history,
+                );
+            }
+            GameState::NameEntry => {
+                self.draw_game();
+                self.ui.draw_name_entry(&self.player_name, self.score, self.wave);
+            }
+            GameState::Leaderboard => {
+                let pages = Self::board_pages();
+                let (mode, difficulty, upgraded) = pages[self.leaderboard_page % pages.len()];
+                let index = DIFFICULTIES.iter().position(|(value, _)| *value == difficulty).unwrap_or(0);
+                let mut title = format!("{} - {}", mode.name(), difficulty_label(index));
+                if upgraded {
+                    title.push_str(&format!(" {}", tr(Text::Upgraded)));
+                }
+                let key = Self::board_key_for(mode, difficulty, upgraded);
+                self.ui.draw_leaderboard(&title, self.leaderboard.entries(&key), self.leaderboard_page, pages.len());
+            }
+            GameState::Achievements => {
+                self.ui.draw_achievements(&self.achievements, self.achievements_page);
+            }
+            GameState::Shop => {
+                self.ui.draw_shop(&self.progression, self.shop_selected);
+            }
+            GameState::LearningReport => {
+                self.ui.draw_learning_report(&self.learning, self.learning_status.as_deref());
+            }
+            GameState::ProfileEntry => {
+                self.ui.draw_profile_entry(&self.profile_name);
+            }
+            GameState::Intermission => {
+                self.draw_game();
+                self.ui.draw_intermission(self.wave, &self.perk_offers, self.perk_selected);
             }
-            GameState::MathChallenge => {
+            GameState::Challenge => {
                 self.draw_game();
-                if let Some(ref challenge) = self.math_challenge {
-                    self.ui.draw_math_challenge(challenge);
+                if let Some(ref session) = self.challenge {
+                    let revives_left =
+                        (self.settings.math_revives > 0).then(|| self.settings.math_revives - self.revives_used);
+                    self.ui.draw_challenge(session, self.revive_streak, revives_left);
                 }
             }
         }
+        
+        self.ui.draw_toasts();
     }
     
     fn draw_game(&self) {
+        // 渲染插值系数：当前时刻位于两个逻辑帧之间的比例
+        let alpha = (self.accumulator / FIXED_DT).min(1.0);
+        
+        // 世界坐标绘制，结束后切回屏幕坐标绘制UI
+        set_camera(&self.camera.camera2d(alpha));
+        
+        // 绘制战场地面网格
+        let grid_color = Color::new(0.12, 0.12, 0.12, 1.0);
+        let grid_size = 100.0;
+        let mut x = 0.0;
+        while x <= WORLD_WIDTH {
+            draw_line(x, 0.0, x, WORLD_HEIGHT, 1.0, grid_color);
+            x += grid_size;
+        }
+        let mut y = 0.0;
+        while y <= WORLD_HEIGHT {
+            draw_line(0.0, y, WORLD_WIDTH, y, 1.0, grid_color);
+            y += grid_size;
+        }
+        
+        // 绘制地面痕迹（履带印、焦痕）
+        self.particles.draw_ground();
+        
         // 绘制障碍物
         for obstacle in &self.obstacles {
             obstacle.draw();
@@ -382,14 +1519,30 @@ impl Game {
         }
         
         // 绘制坦克
-        self.player_tank.draw();
-        for tank in &self.enemy_tanks {
-            tank.draw();
+        self.player_tank.draw(alpha, &self.ui.palette);
+        for drone in &self.drones {
+            drone.draw(alpha);
+        }
+        for enemy in self.enemies.values() {
+            enemy.tank.draw(alpha, &self.ui.palette);
         }
         
         // 绘制子弹
         for bullet in &self.bullets {
-            bullet.draw();
+            bullet.draw(alpha);
         }
+        
+        // 绘制爆炸
+        for explosion in &self.explosions {
+            explosion.draw();
+        }
+        
+        // 绘制粒子特效
+        self.particles.draw_air();
+        
+        // 绘制得分飘字
+        self.ui.draw_score_popups(&self.scoring.popups);
+        
+        set_default_camera();
     }
 }
\ No newline at end of file
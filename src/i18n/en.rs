@@ -0,0 +1,258 @@
+use super::Text;
+
+// 英文界面文字
+pub fn text(key: Text) -> &'static str {
+    match key {
+        Text::Seconds => "{0}s",
+        Text::On => "On",
+        Text::Off => "Off",
+        Text::DurationMinutes => "{0}:{1}",
+        Text::DurationHours => "{0}:{1}:{2}",
+        Text::LanguageSystem => "System ({0})",
+        Text::LanguageNote => "Spelling, vocabulary, geography and question pack content stays in English",
+        Text::LanguageFontMissing => "No Chinese font found. Put a CJK .ttf or .ttc font at {0}",
+        Text::HudHealth => "Health: {0}/{1}",
+        Text::HudScore => "Score: {0}",
+        Text::HudWave => "Wave: {0}",
+        Text::HudDifficulty => "Difficulty: {0}",
+        Text::HudWeapon => "Weapon: {0}",
+        Text::HudMultiplier => "Multiplier: x{0}",
+        Text::HudCombo => "Combo x{0}",
+        Text::HelpMove => "{0}: Move",
+        Text::HelpAim => "Mouse: Aim",
+        Text::HelpShoot => "{0}: Shoot",
+        Text::HelpSwitchWeapon => "{0}: Switch Weapon",
+        Text::HelpPause => "{0}: Pause",
+        Text::AchievementToast => "Achievement: {0}",
+        Text::GameTitle => "TANK BATTLE",
+        Text::SelectDifficulty => "Select Difficulty:",
+        Text::HighScore => "High Score: {0}",
+        Text::DifficultyEasy => "Easy",
+        Text::DifficultyNormal => "Normal",
+        Text::DifficultyHard => "Hard",
+        Text::MenuContinue => "C - Continue",
+        Text::MenuLeaderboard => "L - Leaderboard",
+        Text::MenuAchievements => "T - Achievements",
+        Text::MenuShop => "U - Upgrades",
+        Text::MenuLearning => "E - Learning Report",
+        Text::MenuSettings => "O - Settings",
+        Text::Paused => "PAUSED",
+        Text::PauseHelp => "ESC: Continue   UP/DOWN + ENTER: Select",
+        Text::PauseResume => "Resume",
+        Text::PauseSaveQuit => "S - Save & Quit",
+        Text::GameOver => "GAME OVER",
+        Text::FinalScoreLine => "Final Score: {0}   Waves Reached: {1}   High Score: {2}",
+        Text::ScrapEarned => "Scrap Earned: +{0}",
+        Text::NewLeaderboardEntry => "New Leaderboard Entry: #{0}",
+        Text::ThisRun => "THIS RUN",
+        Text::Lifetime => "LIFETIME",
+        Text::CorrectAnswer => "Correct answer: {0}",
+        Text::PressRToRestart => "Press R to Restart",
+        Text::StatTimeAlive => "Time Alive",
+        Text::StatShotsFired => "Shots Fired",
+        Text::StatAccuracy => "Accuracy",
+        Text::StatDamageDealt => "Damage Dealt",
+        Text::StatDamageTaken => "Damage Taken",
+        Text::StatKills => "Kills",
+        Text::StatWeaponKills => "{0} Tanks",
+        Text::StatPowerUps => "Power-ups",
+        Text::StatWallsDestroyed => "Walls Destroyed",
+        Text::StatMathAnswered => "Math Answered",
+        Text::StatDistance => "Distance",
+        Text::DistanceMeters => "{0} m",
+        Text::StatRunsPlayed => "Runs Played",
+        Text::StatBestScore => "Best Score",
+        Text::StatBestWave => "Best Wave",
+        Text::StatTotalTime => "Total Time",
+        Text::StatTotalKills => "Total Kills",
+        Text::NewHighScore => "NEW HIGH SCORE!",
+        Text::ScoreAndWave => "Score: {0}   Wave: {1}",
+        Text::EnterName => "Enter your name:",
+        Text::PressEnterToSave => "Press ENTER to save",
+        Text::LeaderboardTitle => "LEADERBOARD",
+        Text::ModeClassic => "Classic",
+        Text::Upgraded => "(Upgraded)",
+        Text::ColumnName => "Name",
+        Text::ColumnScore => "Score",
+        Text::ColumnWave => "Wave",
+        Text::ColumnTime => "Time",
+        Text::ColumnDate => "Date",
+        Text::NoScoresYet => "No scores yet",
+        Text::LeaderboardHelp => "LEFT/RIGHT: Switch Board   ESC: Back",
+        Text::AchievementsTitle => "ACHIEVEMENTS",
+        Text::AchievementsUnlocked => "{0}/{1} Unlocked   ({2}/{3})",
+        Text::Locked => "Locked",
+        Text::PageHelp => "LEFT/RIGHT: Switch Page   ESC: Back",
+        Text::LearningTitle => "LEARNING REPORT",
+        Text::ProfileLine => "< {0} >   ({1} profiles)",
+        Text::LearningSummary => "Questions: {0}   Accuracy: {1}%   Avg Time: {2}s   To Review: {3}",
+        Text::ColumnTopic => "Topic",
+        Text::ColumnQuestions => "Questions",
+        Text::ColumnAccuracy => "Accuracy",
+        Text::ColumnAvgTime => "Avg Time",
+        Text::RecentDays => "Recent Days",
+        Text::NoQuestionsYet => "No questions answered yet",
+        Text::LearningHelp => "LEFT/RIGHT: Switch Profile   1-6: Toggle Challenges   N: New Profile   X: Export CSV   ESC: Back",
+        Text::NewProfileName => "New profile name:",
+        Text::ProfileEntryHelp => "ENTER: Create or switch to profile   ESC: Cancel",
+        Text::ExportedTo => "Exported to {0}",
+        Text::ExportFailed => "Export failed: {0}",
+        Text::UpgradesTitle => "UPGRADES",
+        Text::ScrapAmount => "Scrap: {0}",
+        Text::VanillaOn => "Vanilla Mode: ON - upgrades disabled, runs use the standard leaderboard",
+        Text::VanillaOff => "Vanilla Mode: OFF - upgraded runs use separate leaderboards",
+        Text::ScrapCost => "{0} Scrap",
+        Text::MaxLevel => "MAX",
+        Text::ShopHelp => "ENTER: Buy   V: Toggle Vanilla   ESC: Back",
+        Text::SettingsTitle => "SETTINGS",
+        Text::PressAKey => "Press a key...",
+        Text::RebindHelp => "Press a key, mouse button or gamepad button   ESC: Cancel",
+        Text::SettingsBindHelp => "UP/DOWN: Select   ENTER: Rebind / Reset   ESC: Back",
+        Text::SettingsHelp => "UP/DOWN: Select   LEFT/RIGHT: Change   ESC: Back",
+        Text::SettingLanguage => "Language",
+        Text::SettingWindowMode => "Window Mode",
+        Text::SettingResolution => "Resolution",
+        Text::SettingMasterVolume => "Master Volume",
+        Text::SettingMusicVolume => "Music Volume",
+        Text::SettingSfxVolume => "Effects Volume",
+        Text::SettingHudScale => "HUD Scale",
+        Text::SettingColorblind => "Colorblind Palette",
+        Text::SettingScreenShake => "Screen Shake",
+        Text::SettingParticleQuality => "Effects Quality",
+        Text::SettingDefaultDifficulty => "Default Difficulty",
+        Text::SettingReviveChallenge => "Revive Challenge",
+        Text::SettingMathLevel => "Math Level",
+        Text::SettingMathTopics => "Math Topics",
+        Text::SettingMathTimeLimit => "Math Time Limit",
+        Text::SettingRevives => "Revives Per Run",
+        Text::SettingMultipleChoice => "Multiple Choice",
+        Text::SettingLearningMode => "Learning Mode",
+        Text::SettingQuestionPack => "Question Pack",
+        Text::SettingSupplyDrops => "Math Supply Drops",
+        Text::SettingResetControls => "Reset Controls",
+        Text::Windowed => "Windowed",
+        Text::Fullscreen => "Fullscreen",
+        Text::Unlimited => "Unlimited",
+        Text::BuiltIn => "Built-in",
+        Text::ColorDeuteranopia => "Deuteranopia",
+        Text::ColorProtanopia => "Protanopia",
+        Text::ColorTritanopia => "Tritanopia",
+        Text::QualityLow => "Low",
+        Text::QualityHigh => "High",
+        Text::Grades1To2 => "Grades 1-2",
+        Text::Grades3To4 => "Grades 3-4",
+        Text::Grades5To6 => "Grades 5-6",
+        Text::Grades7To8 => "Grades 7-8",
+        Text::GradesCustom => "Custom",
+        Text::PackSummary => "{0} questions. {1}",
+        Text::PackFolderHint => "Put .json or .toml packs in the tank_battle/packs data folder",
+        Text::PacksWithErrors => "[{0} packs have errors]",
+        Text::ActionMoveUp => "Move Up",
+        Text::ActionMoveDown => "Move Down",
+        Text::ActionMoveLeft => "Move Left",
+        Text::ActionMoveRight => "Move Right",
+        Text::ActionFire => "Fire",
+        Text::ActionNextWeapon => "Next Weapon",
+        Text::ActionPause => "Pause",
+        Text::ActionMenuUp => "Menu Up",
+        Text::ActionMenuDown => "Menu Down",
+        Text::ActionMenuLeft => "Menu Left",
+        Text::ActionMenuRight => "Menu Right",
+        Text::ActionConfirm => "Confirm",
+        Text::ActionBack => "Back",
+        Text::TopicAddition => "Addition",
+        Text::TopicSubtraction => "Subtraction",
+        Text::TopicMultiplication => "Multiplication",
+        Text::TopicDivision => "Division",
+        Text::TopicMultiStep => "Order of Operations",
+        Text::TopicNegatives => "Negative Numbers",
+        Text::TopicFractions => "Fractions",
+        Text::TopicPercentages => "Percentages",
+        Text::TopicPowers => "Powers",
+        Text::TopicEquations => "Equations",
+        Text::WeaponCannon => "Cannon",
+        Text::WeaponMortar => "Mortar",
+        Text::WeaponMines => "Mines",
+        Text::WeaponHeavyCannon => "Heavy Cannon",
+        Text::WeaponTwinCannon => "Twin Cannon",
+        Text::UpgradeMaxHealth => "Reinforced Armor",
+        Text::UpgradeMaxHealthDescription => "+25 max health per level",
+        Text::UpgradeSpeed => "Tuned Engine",
+        Text::UpgradeSpeedDescription => "+4% base speed per level",
+        Text::UpgradeFireRate => "Autoloader",
+        Text::UpgradeFireRateDescription => "-8% reload time per level",
+        Text::UpgradeStartingShield => "Shield Generator",
+        Text::UpgradeStartingShieldDescription => "Start each run with a shield (+3s per level)",
+        Text::UpgradeHeavyCannon => "Heavy Cannon",
+        Text::UpgradeHeavyCannonDescription => "Unlock a slow, hard-hitting cannon",
+        Text::UpgradeTwinCannon => "Twin Cannon",
+        Text::UpgradeTwinCannonDescription => "Unlock a cannon that fires two parallel shells",
+        Text::RarityCommon => "Common",
+        Text::RarityRare => "Rare",
+        Text::RarityEpic => "Epic",
+        Text::PerkFastReload => "Faster Reload",
+        Text::PerkFastReloadDescription => "Reload 12% faster",
+        Text::PerkRegen => "Regeneration",
+        Text::PerkRegenDescription => "Regenerate 1 health per second",
+        Text::PerkArmor => "Extra Armor",
+        Text::PerkArmorDescription => "+30 max health and repair 30",
+        Text::PerkBiggerShield => "Bigger Shield",
+        Text::PerkBiggerShieldDescription => "Shields last 50% longer",
+        Text::PerkPierce => "Piercing Shells",
+        Text::PerkPierceDescription => "Shells pass through one more tank",
+        Text::PerkRicochet => "+1 Ricochet",
+        Text::PerkRicochetDescription => "Shells bounce off one more wall",
+        Text::PerkDrone => "Drone Companion",
+        Text::PerkDroneDescription => "A drone orbits you and shoots nearby enemies",
+        Text::WaveIncoming => "WAVE {0} INCOMING",
+        Text::ChooseUpgrade => "Choose an upgrade for this run",
+        Text::IntermissionHelp => "1/2/3 or LEFT/RIGHT + ENTER: Choose",
+        Text::SupplyDropTitle => "SUPPLY DROP: {0} ({1})",
+        Text::SupplyDropHint => "Press 1-4 or D-pad Up / Right / Down / Left",
+        Text::SupplyDropUnlocked => "Supply Drop: {0}",
+        Text::SupplyDropLost => "Supply Drop Lost",
+        Text::AnswerWas => "The answer was {0}",
+        Text::RewardRepair => "Full Repair",
+        Text::RewardRepairDescription => "Restores all health",
+        Text::RewardFortress => "Fortress Shield",
+        Text::RewardFortressDescription => "60 second shield",
+        Text::RewardArsenal => "Arsenal",
+        Text::RewardArsenalDescription => "Scatter shot and +50% speed",
+        Text::ChallengeTitle => "{0} Challenge",
+        Text::ReviveTitle => "{0} - REVIVE!",
+        Text::ChallengeAnswerHelp => "ENTER / (A): Answer   ESC / (B): Give up",
+        Text::ReviveHealth => "Revive health: {0}%",
+        Text::ReviveStreak => "Streak x{0}",
+        Text::RevivesLeft => "Revives left: {0}",
+        Text::KindMath => "Math",
+        Text::KindTimesTables => "Times Tables",
+        Text::KindSpelling => "Spelling",
+        Text::KindVocabulary => "Vocabulary",
+        Text::KindGeography => "Geography",
+        Text::KindReflex => "Reflex",
+        Text::PromptMath => "Solve the math problem to revive:",
+        Text::PromptTimesTable => "Finish the times table to revive:",
+        Text::PromptSpelling => "Spell the word to revive:",
+        Text::PromptVocabulary => "Choose what the word means to revive:",
+        Text::PromptGeography => "Answer the geography question to revive:",
+        Text::PromptReflex => "Wait for green, then react to revive:",
+        Text::PromptPack => "Answer the question to revive:",
+        Text::HintChoices => "Click an answer, press 1-4, or use the arrows / D-pad",
+        Text::HintMathTyped => "Negative numbers, decimals (0.5) and fractions (3/4) are accepted",
+        Text::HintSpellingTyped => "Unscramble the letters and type the word",
+        Text::HintSpellingChoices => "Pick the correct spelling: click, press 1-4, or use the D-pad",
+        Text::HintReflex => "Press ENTER, click, or (A) as soon as the light turns green",
+        Text::HintPackTyped => "Type your answer; capital letters and extra spaces do not matter",
+        Text::HintPackChoices => "Click an answer, press the number keys, or use the arrows / D-pad",
+        Text::SubtitleTimesTable => "The {0} times table",
+        Text::SubtitleLetters => "{0} letters",
+        Text::SubtitleCapitals => "Capital Cities",
+        Text::GeographyQuestion => "What is the capital of {0}?",
+        Text::SubtitleReactionWindow => "Reaction window: {0}s",
+        Text::SubtitleLevel => "Level {0}",
+        Text::ReflexNow => "NOW!",
+        Text::ReflexGetReady => "Get ready...",
+        Text::ReflexWait => "Wait...",
+        Text::ReflexCorrectAnswer => "React within {0}s of the green light",
+    }
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use super::{set_locale, Locale};
+use macroquad::prelude::*;
+use std::path::PathBuf;
+
+// 玩家自己放入的中文字体，优先于系统字体
+pub const CJK_FONT_FILE: &str = "fonts/cjk.ttf";
+
+// 随程序发布的中文字体：构建时 assets/fonts/cjk.ttf 存在就编译进程序（见 build.rs）
+#[cfg(bundled_cjk_font)]
+const BUNDLED_CJK_FONT: Option<&[u8]> = Some(include_bytes!("../../assets/fonts/cjk.ttf"));
+#[cfg(not(bundled_cjk_font))]
+const BUNDLED_CJK_FONT: Option<&[u8]> = None;
+
+// 常见系统中自带的中文字体
+const SYSTEM_CJK_FONTS: [&str; 12] = [
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/wenquanyi/wqy-microhei/wqy-microhei.ttc",
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/STHeiti Medium.ttc",
+    "/Library/Fonts/Arial Unicode.ttf",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\simhei.ttf",
+    "C:\\Windows\\Fonts\\simsun.ttc",
+];
+
+// 界面字体：macroquad 自带的字体只有拉丁字母，中文界面需要另外加载字体
+pub struct Fonts {
+    builtin: Font,
+    cjk: Option<Font>,
+    searched: bool, // 中文字体文件较大，第一次切换到中文时才查找和读取
+}
+
+impl Fonts {
+    pub fn new() -> Self {
+        Self {
+            builtin: get_default_font(),
+            cjk: None,
+            searched: false,
+        }
+    }
+    
+    // 切换界面语言并换上能显示该语言的默认字体；找不到中文字体时退回英文，返回实际使用的语言
+    pub fn apply(&mut self, locale: Locale) -> Locale {
+        let font = if locale.needs_cjk_font() { self.cjk_font() } else { None };
+        let locale = match font {
+            Some(font) => {
+                set_default_font(font);
+                locale
+            }
+            None => {
+                set_default_font(self.builtin.clone());
+                Locale::En
+            }
+        };
+        set_locale(locale);
+        locale
+    }
+    
+    fn cjk_font(&mut self) -> Option<Font> {
+        if !self.searched {
+            self.searched = true;
+            self.cjk = BUNDLED_CJK_FONT
+                .and_then(|bytes| load_font(bytes, "assets/fonts/cjk.ttf (bundled)"))
+                .or_else(|| {
+                    cjk_font_paths()
+                        .iter()
+                        .find_map(|path| load_font(&std::fs::read(path).ok()?, path.display()))
+                });
+            if self.cjk.is_none() {
+                eprintln!("找不到中文字体，界面使用英文；可以把字体放到 {}", cjk_font_hint().display());
+            }
+        }
+        self.cjk.clone()
+    }
+}
+
+fn load_font(bytes: &[u8], source: impl std::fmt::Display) -> Option<Font> {
+    match load_ttf_font_from_bytes(bytes) {
+        Ok(font) => Some(font),
+        Err(err) => {
+            eprintln!("无法读取字体 {}: {}", source, err);
+            None
+        }
+    }
+}
+
+// 玩家放入中文字体的位置，显示在设置界面的提示中
+pub fn cjk_font_hint() -> PathBuf {
+    crate::storage::data_dir()
+        .unwrap_or_else(|| PathBuf::from("assets"))
+        .join(CJK_FONT_FILE)
+}
+
+// 按优先顺序排列的候选字体：程序目录下的 assets、数据目录，最后是系统字体
+fn cjk_font_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from("assets").join(CJK_FONT_FILE)];
+    if let Some(dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(PathBuf::from)) {
+        paths.push(dir.join("assets").join(CJK_FONT_FILE));
+    }
+    if let Some(dir) = crate::storage::data_dir() {
+        paths.push(dir.join(CJK_FONT_FILE));
+    }
+    paths.extend(SYSTEM_CJK_FONTS.iter().map(PathBuf::from));
+    paths
+}
\ No newline at end of file
@@ -0,0 +1,466 @@
+mod en;
+mod font;
+mod zh;
+
+pub use font::{cjk_font_hint, Fonts};
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// 界面语言；拼写、词汇、地理题和题库等内容仍然使用英文
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    En,
+    Zh,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Zh];
+    
+    // 语言名称总是用该语言本身显示，方便看不懂当前语言的玩家找到自己的语言
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Zh => "中文",
+        }
+    }
+    
+    // 是否需要能显示中日韩文字的字体
+    pub fn needs_cjk_font(&self) -> bool {
+        *self == Locale::Zh
+    }
+    
+    // 从 "zh_CN.UTF-8"、"en-US" 这样的语言标签中识别语言
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['_', '-', '.', '@']).next()?.to_lowercase();
+        match language.as_str() {
+            "zh" => Some(Locale::Zh),
+            "en" | "c" | "posix" => Some(Locale::En),
+            _ => None,
+        }
+    }
+    
+    // 按 POSIX 的优先顺序读取环境变量，都没有设置时使用英文
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG", "LANGUAGE"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .filter(|value| !value.is_empty())
+            .find_map(|value| value.split(':').find_map(Locale::from_tag))
+            .unwrap_or(Locale::En)
+    }
+    
+    fn text(&self, key: Text) -> &'static str {
+        match self {
+            Locale::En => en::text(key),
+            Locale::Zh => zh::text(key),
+        }
+    }
+    
+    // 成就的英文名称和说明写在 assets/achievements.json 中，其他语言按成就编号查表
+    pub fn achievement(&self, id: &str) -> Option<(&'static str, &'static str)> {
+        match self {
+            Locale::En => None,
+            Locale::Zh => zh::achievement(id),
+        }
+    }
+    
+    // 数字分组：每 group 位插入一个分隔符
+    fn digit_grouping(&self) -> (usize, char) {
+        match self {
+            // CLDR 中中文和英文一样每三位用逗号分隔；按万分组只在写出 "万"、"亿" 单位时才有意义
+            Locale::En | Locale::Zh => (3, ','),
+        }
+    }
+}
+
+// 当前界面语言，绘制时随处都要读取，所以放在全局
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn locale() -> Locale {
+    Locale::ALL[CURRENT.load(Ordering::Relaxed) as usize % Locale::ALL.len()]
+}
+
+pub fn set_locale(locale: Locale) {
+    let index = Locale::ALL.iter().position(|l| *l == locale).unwrap_or(0);
+    CURRENT.store(index as u8, Ordering::Relaxed);
+}
+
+// 当前语言的界面文字
+pub fn tr(key: Text) -> &'static str {
+    locale().text(key)
+}
+
+// 带参数的界面文字，模板中的 {0}、{1} 依次替换为参数
+pub fn trf(key: Text, args: &[&dyn Display]) -> String {
+    fill(tr(key), args)
+}
+
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut text = template.to_string();
+    for (i, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{}}}", i), &arg.to_string());
+    }
+    text
+}
+
+// 按当前语言给整数分组，例如 "1,234,567"
+pub fn format_number(value: i64) -> String {
+    format_number_in(locale(), value)
+}
+
+fn format_number_in(locale: Locale, value: i64) -> String {
+    let (group, separator) = locale.digit_grouping();
+    let digits = value.unsigned_abs().to_string();
+    let mut text = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(group) {
+            text.push(separator);
+        }
+        text.push(digit);
+    }
+    if value < 0 {
+        text.insert(0, '-');
+    }
+    text
+}
+
+// 按当前语言显示时长，例如英文 "1:05"、"1:02:03"，中文 "1分05秒"、"1时02分03秒"
+pub fn format_duration(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    let seconds = format!("{:02}", seconds);
+    let minutes_padded = format!("{:02}", minutes);
+    if hours > 0 {
+        trf(Text::DurationHours, &[&hours, &minutes_padded, &seconds])
+    } else {
+        trf(Text::DurationMinutes, &[&minutes, &seconds])
+    }
+}
+
+// 所有界面文字的键；每种语言的文字表都必须覆盖全部的键
+macro_rules! texts {
+    ($($key:ident),* $(,)?) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum Text {
+            $($key),*
+        }
+        
+        impl Text {
+            #[cfg(test)]
+            pub const ALL: &'static [Text] = &[$(Text::$key),*];
+        }
+    };
+}
+
+texts! {
+    Seconds,
+    On,
+    Off,
+    DurationMinutes,
+    DurationHours,
+    LanguageSystem,
+    LanguageNote,
+    LanguageFontMissing,
+    // HUD
+    HudHealth,
+    HudScore,
+    HudWave,
+    HudDifficulty,
+    HudWeapon,
+    HudMultiplier,
+    HudCombo,
+    HelpMove,
+    HelpAim,
+    HelpShoot,
+    HelpSwitchWeapon,
+    HelpPause,
+    AchievementToast,
+    // 主菜单和暂停菜单
+    GameTitle,
+    SelectDifficulty,
+    HighScore,
+    DifficultyEasy,
+    DifficultyNormal,
+    DifficultyHard,
+    MenuContinue,
+    MenuLeaderboard,
+    MenuAchievements,
+    MenuShop,
+    MenuLearning,
+    MenuSettings,
+    Paused,
+    PauseHelp,
+    PauseResume,
+    PauseSaveQuit,
+    // 结算
+    GameOver,
+    FinalScoreLine,
+    ScrapEarned,
+    NewLeaderboardEntry,
+    ThisRun,
+    Lifetime,
+    CorrectAnswer,
+    PressRToRestart,
+    StatTimeAlive,
+    StatShotsFired,
+    StatAccuracy,
+    StatDamageDealt,
+    StatDamageTaken,
+    StatKills,
+    StatWeaponKills,
+    StatPowerUps,
+    StatWallsDestroyed,
+    StatMathAnswered,
+    StatDistance,
+    DistanceMeters,
+    StatRunsPlayed,
+    StatBestScore,
+    StatBestWave,
+    StatTotalTime,
+    StatTotalKills,
+    // 输入名字和排行榜
+    NewHighScore,
+    ScoreAndWave,
+    EnterName,
+    PressEnterToSave,
+    LeaderboardTitle,
+    ModeClassic,
+    Upgraded,
+    ColumnName,
+    ColumnScore,
+    ColumnWave,
+    ColumnTime,
+    ColumnDate,
+    NoScoresYet,
+    LeaderboardHelp,
+    // 成就
+    AchievementsTitle,
+    AchievementsUnlocked,
+    Locked,
+    PageHelp,
+    // 学习报告
+    LearningTitle,
+    ProfileLine,
+    LearningSummary,
+    ColumnTopic,
+    ColumnQuestions,
+    ColumnAccuracy,
+    ColumnAvgTime,
+    RecentDays,
+    NoQuestionsYet,
+    LearningHelp,
+    NewProfileName,
+    ProfileEntryHelp,
+    ExportedTo,
+    ExportFailed,
+    // 升级商店
+    UpgradesTitle,
+    ScrapAmount,
+    VanillaOn,
+    VanillaOff,
+    ScrapCost,
+    MaxLevel,
+    ShopHelp,
+    // 设置
+    SettingsTitle,
+    PressAKey,
+    RebindHelp,
+    SettingsBindHelp,
+    SettingsHelp,
+    SettingLanguage,
+    SettingWindowMode,
+    SettingResolution,
+    SettingMasterVolume,
+    SettingMusicVolume,
+    SettingSfxVolume,
+    SettingHudScale,
+    SettingColorblind,
+    SettingScreenShake,
+    SettingParticleQuality,
+    SettingDefaultDifficulty,
+    SettingReviveChallenge,
+    SettingMathLevel,
+    SettingMathTopics,
+    SettingMathTimeLimit,
+    SettingRevives,
+    SettingMultipleChoice,
+    SettingLearningMode,
+    SettingQuestionPack,
+    SettingSupplyDrops,
+    SettingResetControls,
+    Windowed,
+    Fullscreen,
+    Unlimited,
+    BuiltIn,
+    ColorDeuteranopia,
+    ColorProtanopia,
+    ColorTritanopia,
+    QualityLow,
+    QualityHigh,
+    Grades1To2,
+    Grades3To4,
+    Grades5To6,
+    Grades7To8,
+    GradesCustom,
+    PackSummary,
+    PackFolderHint,
+    PacksWithErrors,
+    ActionMoveUp,
+    ActionMoveDown,
+    ActionMoveLeft,
+    ActionMoveRight,
+    ActionFire,
+    ActionNextWeapon,
+    ActionPause,
+    ActionMenuUp,
+    ActionMenuDown,
+    ActionMenuLeft,
+    ActionMenuRight,
+    ActionConfirm,
+    ActionBack,
+    // 数学题型
+    TopicAddition,
+    TopicSubtraction,
+    TopicMultiplication,
+    TopicDivision,
+    TopicMultiStep,
+    TopicNegatives,
+    TopicFractions,
+    TopicPercentages,
+    TopicPowers,
+    TopicEquations,
+    // 武器
+    WeaponCannon,
+    WeaponMortar,
+    WeaponMines,
+    WeaponHeavyCannon,
+    WeaponTwinCannon,
+    // 升级
+    UpgradeMaxHealth,
+    UpgradeMaxHealthDescription,
+    UpgradeSpeed,
+    UpgradeSpeedDescription,
+    UpgradeFireRate,
+    UpgradeFireRateDescription,
+    UpgradeStartingShield,
+    UpgradeStartingShieldDescription,
+    UpgradeHeavyCannon,
+    UpgradeHeavyCannonDescription,
+    UpgradeTwinCannon,
+    UpgradeTwinCannonDescription,
+    // 强化
+    RarityCommon,
+    RarityRare,
+    RarityEpic,
+    PerkFastReload,
+    PerkFastReloadDescription,
+    PerkRegen,
+    PerkRegenDescription,
+    PerkArmor,
+    PerkArmorDescription,
+    PerkBiggerShield,
+    PerkBiggerShieldDescription,
+    PerkPierce,
+    PerkPierceDescription,
+    PerkRicochet,
+    PerkRicochetDescription,
+    PerkDrone,
+    PerkDroneDescription,
+    // 波次之间的强化
+    WaveIncoming,
+    ChooseUpgrade,
+    IntermissionHelp,
+    // 补给箱
+    SupplyDropTitle,
+    SupplyDropHint,
+    SupplyDropUnlocked,
+    SupplyDropLost,
+    AnswerWas,
+    RewardRepair,
+    RewardRepairDescription,
+    RewardFortress,
+    RewardFortressDescription,
+    RewardArsenal,
+    RewardArsenalDescription,
+    // 复活挑战
+    ChallengeTitle,
+    ReviveTitle,
+    ChallengeAnswerHelp,
+    ReviveHealth,
+    ReviveStreak,
+    RevivesLeft,
+    KindMath,
+    KindTimesTables,
+    KindSpelling,
+    KindVocabulary,
+    KindGeography,
+    KindReflex,
+    PromptMath,
+    PromptTimesTable,
+    PromptSpelling,
+    PromptVocabulary,
+    PromptGeography,
+    PromptReflex,
+    PromptPack,
+    HintChoices,
+    HintMathTyped,
+    HintSpellingTyped,
+    HintSpellingChoices,
+    HintReflex,
+    HintPackTyped,
+    HintPackChoices,
+    SubtitleTimesTable,
+    SubtitleLetters,
+    SubtitleCapitals,
+    GeographyQuestion,
+    SubtitleReactionWindow,
+    SubtitleLevel,
+    ReflexNow,
+    ReflexGetReady,
+    ReflexWait,
+    ReflexCorrectAnswer,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn placeholders(text: &str) -> Vec<usize> {
+        let mut found: Vec<usize> = (0..4).filter(|i| text.contains(&format!("{{{}}}", i))).collect();
+        found.sort();
+        found
+    }
+    
+    #[test]
+    fn every_locale_uses_the_same_placeholders() {
+        for &key in Text::ALL {
+            let english = Locale::En.text(key);
+            assert!(!english.is_empty(), "{:?}", key);
+            for locale in Locale::ALL {
+                let text = locale.text(key);
+                assert!(!text.is_empty(), "{:?} {:?}", locale, key);
+                assert_eq!(placeholders(text), placeholders(english), "{:?} {:?}", locale, key);
+            }
+        }
+    }
+    
+    #[test]
+    fn locale_tags_from_the_environment() {
+        assert_eq!(Locale::from_tag("zh_CN.UTF-8"), Some(Locale::Zh));
+        assert_eq!(Locale::from_tag("zh-Hant-TW"), Some(Locale::Zh));
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("C"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("fr_FR"), None);
+    }
+    
+    #[test]
+    fn numbers_are_grouped_per_locale() {
+        assert_eq!(format_number_in(Locale::En, 1234567), "1,234,567");
+        assert_eq!(format_number_in(Locale::En, -950), "-950");
+        assert_eq!(format_number_in(Locale::Zh, 1234567), "1,234,567");
+        assert_eq!(format_number_in(Locale::Zh, 8000), "8,000");
+        assert_eq!(fill("{1} / {0}", &[&"a", &2]), "2 / a");
+    }
+}
\ No newline at end of file
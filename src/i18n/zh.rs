@@ -0,0 +1,278 @@
+use super::Text;
+
+// 中文界面文字
+pub fn text(key: Text) -> &'static str {
+    match key {
+        Text::Seconds => "{0}秒",
+        Text::On => "开",
+        Text::Off => "关",
+        Text::DurationMinutes => "{0}分{1}秒",
+        Text::DurationHours => "{0}时{1}分{2}秒",
+        Text::LanguageSystem => "跟随系统（{0}）",
+        Text::LanguageNote => "拼写、词汇、地理题和题库内容仍然使用英文",
+        Text::LanguageFontMissing => "没有找到中文字体，请把中文 .ttf 或 .ttc 字体放到 {0}",
+        Text::HudHealth => "生命：{0}/{1}",
+        Text::HudScore => "得分：{0}",
+        Text::HudWave => "波次：{0}",
+        Text::HudDifficulty => "难度：{0}",
+        Text::HudWeapon => "武器：{0}",
+        Text::HudMultiplier => "倍率：x{0}",
+        Text::HudCombo => "连击 x{0}",
+        Text::HelpMove => "{0}：移动",
+        Text::HelpAim => "鼠标：瞄准",
+        Text::HelpShoot => "{0}：射击",
+        Text::HelpSwitchWeapon => "{0}：切换武器",
+        Text::HelpPause => "{0}：暂停",
+        Text::AchievementToast => "成就：{0}",
+        Text::GameTitle => "坦克大战",
+        Text::SelectDifficulty => "选择难度：",
+        Text::HighScore => "最高分：{0}",
+        Text::DifficultyEasy => "简单",
+        Text::DifficultyNormal => "普通",
+        Text::DifficultyHard => "困难",
+        Text::MenuContinue => "C - 继续游戏",
+        Text::MenuLeaderboard => "L - 排行榜",
+        Text::MenuAchievements => "T - 成就",
+        Text::MenuShop => "U - 升级",
+        Text::MenuLearning => "E - 学习报告",
+        Text::MenuSettings => "O - 设置",
+        Text::Paused => "已暂停",
+        Text::PauseHelp => "ESC：继续   上/下 + ENTER：选择",
+        Text::PauseResume => "继续",
+        Text::PauseSaveQuit => "S - 保存并退出",
+        Text::GameOver => "游戏结束",
+        Text::FinalScoreLine => "最终得分：{0}   到达波次：{1}   最高分：{2}",
+        Text::ScrapEarned => "获得废料：+{0}",
+        Text::NewLeaderboardEntry => "登上排行榜：第 {0} 名",
+        Text::ThisRun => "本局",
+        Text::Lifetime => "累计",
+        Text::CorrectAnswer => "正确答案：{0}",
+        Text::PressRToRestart => "按 R 重新开始",
+        Text::StatTimeAlive => "存活时间",
+        Text::StatShotsFired => "射击次数",
+        Text::StatAccuracy => "命中率",
+        Text::StatDamageDealt => "造成伤害",
+        Text::StatDamageTaken => "受到伤害",
+        Text::StatKills => "击毁",
+        Text::StatWeaponKills => "{0} 坦克",
+        Text::StatPowerUps => "道具",
+        Text::StatWallsDestroyed => "摧毁墙体",
+        Text::StatMathAnswered => "答对题目",
+        Text::StatDistance => "行驶距离",
+        Text::DistanceMeters => "{0} 米",
+        Text::StatRunsPlayed => "游戏局数",
+        Text::StatBestScore => "最高得分",
+        Text::StatBestWave => "最高波次",
+        Text::StatTotalTime => "总时长",
+        Text::StatTotalKills => "总击毁",
+        Text::NewHighScore => "新纪录！",
+        Text::ScoreAndWave => "得分：{0}   波次：{1}",
+        Text::EnterName => "输入你的名字：",
+        Text::PressEnterToSave => "按 ENTER 保存",
+        Text::LeaderboardTitle => "排行榜",
+        Text::ModeClassic => "经典",
+        Text::Upgraded => "（已升级）",
+        Text::ColumnName => "名字",
+        Text::ColumnScore => "得分",
+        Text::ColumnWave => "波次",
+        Text::ColumnTime => "时长",
+        Text::ColumnDate => "日期",
+        Text::NoScoresYet => "还没有成绩",
+        Text::LeaderboardHelp => "左/右：切换榜单   ESC：返回",
+        Text::AchievementsTitle => "成就",
+        Text::AchievementsUnlocked => "已解锁 {0}/{1}   （{2}/{3}）",
+        Text::Locked => "未解锁",
+        Text::PageHelp => "左/右：翻页   ESC：返回",
+        Text::LearningTitle => "学习报告",
+        Text::ProfileLine => "< {0} >   （共 {1} 个档案）",
+        Text::LearningSummary => "答题：{0}   正确率：{1}%   平均用时：{2}秒   待复习：{3}",
+        Text::ColumnTopic => "题型",
+        Text::ColumnQuestions => "题数",
+        Text::ColumnAccuracy => "正确率",
+        Text::ColumnAvgTime => "平均用时",
+        Text::RecentDays => "最近几天",
+        Text::NoQuestionsYet => "还没有答过题",
+        Text::LearningHelp => "左/右：切换档案   1-6：开关挑战   N：新建档案   X：导出 CSV   ESC：返回",
+        Text::NewProfileName => "新档案名称：",
+        Text::ProfileEntryHelp => "ENTER：创建或切换到该档案   ESC：取消",
+        Text::ExportedTo => "已导出到 {0}",
+        Text::ExportFailed => "导出失败：{0}",
+        Text::UpgradesTitle => "升级",
+        Text::ScrapAmount => "废料：{0}",
+        Text::VanillaOn => "原版模式：开 - 不使用升级，成绩记入标准排行榜",
+        Text::VanillaOff => "原版模式：关 - 使用升级的成绩记入单独的排行榜",
+        Text::ScrapCost => "{0} 废料",
+        Text::MaxLevel => "已满级",
+        Text::ShopHelp => "ENTER：购买   V：切换原版模式   ESC：返回",
+        Text::SettingsTitle => "设置",
+        Text::PressAKey => "请按键...",
+        Text::RebindHelp => "按下键盘按键、鼠标按键或手柄按钮   ESC：取消",
+        Text::SettingsBindHelp => "上/下：选择   ENTER：重新绑定 / 恢复   ESC：返回",
+        Text::SettingsHelp => "上/下：选择   左/右：修改   ESC：返回",
+        Text::SettingLanguage => "语言",
+        Text::SettingWindowMode => "窗口模式",
+        Text::SettingResolution => "分辨率",
+        Text::SettingMasterVolume => "总音量",
+        Text::SettingMusicVolume => "音乐音量",
+        Text::SettingSfxVolume => "音效音量",
+        Text::SettingHudScale => "界面缩放",
+        Text::SettingColorblind => "色盲配色",
+        Text::SettingScreenShake => "屏幕震动",
+        Text::SettingParticleQuality => "特效质量",
+        Text::SettingDefaultDifficulty => "默认难度",
+        Text::SettingReviveChallenge => "复活挑战",
+        Text::SettingMathLevel => "数学难度",
+        Text::SettingMathTopics => "数学题型",
+        Text::SettingMathTimeLimit => "答题限时",
+        Text::SettingRevives => "每局复活次数",
+        Text::SettingMultipleChoice => "选择题",
+        Text::SettingLearningMode => "学习模式",
+        Text::SettingQuestionPack => "题库",
+        Text::SettingSupplyDrops => "数学补给箱",
+        Text::SettingResetControls => "恢复默认按键",
+        Text::Windowed => "窗口",
+        Text::Fullscreen => "全屏",
+        Text::Unlimited => "不限",
+        Text::BuiltIn => "内置题目",
+        Text::ColorDeuteranopia => "绿色弱",
+        Text::ColorProtanopia => "红色弱",
+        Text::ColorTritanopia => "蓝黄色弱",
+        Text::QualityLow => "低",
+        Text::QualityHigh => "高",
+        Text::Grades1To2 => "一至二年级",
+        Text::Grades3To4 => "三至四年级",
+        Text::Grades5To6 => "五至六年级",
+        Text::Grades7To8 => "七至八年级",
+        Text::GradesCustom => "自定义",
+        Text::PackSummary => "共 {0} 题。{1}",
+        Text::PackFolderHint => "把 .json 或 .toml 题库放到数据目录的 tank_battle/packs 文件夹中",
+        Text::PacksWithErrors => "[{0} 个题库有错误]",
+        Text::ActionMoveUp => "向上移动",
+        Text::ActionMoveDown => "向下移动",
+        Text::ActionMoveLeft => "向左移动",
+        Text::ActionMoveRight => "向右移动",
+        Text::ActionFire => "射击",
+        Text::ActionNextWeapon => "下一个武器",
+        Text::ActionPause => "暂停",
+        Text::ActionMenuUp => "菜单上",
+        Text::ActionMenuDown => "菜单下",
+        Text::ActionMenuLeft => "菜单左",
+        Text::ActionMenuRight => "菜单右",
+        Text::ActionConfirm => "确认",
+        Text::ActionBack => "返回",
+        Text::TopicAddition => "加法",
+        Text::TopicSubtraction => "减法",
+        Text::TopicMultiplication => "乘法",
+        Text::TopicDivision => "除法",
+        Text::TopicMultiStep => "四则混合运算",
+        Text::TopicNegatives => "负数",
+        Text::TopicFractions => "分数",
+        Text::TopicPercentages => "百分数",
+        Text::TopicPowers => "乘方",
+        Text::TopicEquations => "方程",
+        Text::WeaponCannon => "加农炮",
+        Text::WeaponMortar => "迫击炮",
+        Text::WeaponMines => "地雷",
+        Text::WeaponHeavyCannon => "重型加农炮",
+        Text::WeaponTwinCannon => "双管加农炮",
+        Text::UpgradeMaxHealth => "强化装甲",
+        Text::UpgradeMaxHealthDescription => "每级生命上限 +25",
+        Text::UpgradeSpeed => "调校引擎",
+        Text::UpgradeSpeedDescription => "每级基础速度 +4%",
+        Text::UpgradeFireRate => "自动装弹机",
+        Text::UpgradeFireRateDescription => "每级装填时间 -8%",
+        Text::UpgradeStartingShield => "护盾发生器",
+        Text::UpgradeStartingShieldDescription => "每局开始时带有护盾（每级 +3 秒）",
+        Text::UpgradeHeavyCannon => "重型加农炮",
+        Text::UpgradeHeavyCannonDescription => "解锁射速慢但威力大的加农炮",
+        Text::UpgradeTwinCannon => "双管加农炮",
+        Text::UpgradeTwinCannonDescription => "解锁同时发射两枚平行炮弹的加农炮",
+        Text::RarityCommon => "普通",
+        Text::RarityRare => "稀有",
+        Text::RarityEpic => "史诗",
+        Text::PerkFastReload => "快速装填",
+        Text::PerkFastReloadDescription => "装填速度提高 12%",
+        Text::PerkRegen => "自动修复",
+        Text::PerkRegenDescription => "每秒恢复 1 点生命值",
+        Text::PerkArmor => "加厚装甲",
+        Text::PerkArmorDescription => "生命上限 +30，并立即修复 30 点",
+        Text::PerkBiggerShield => "强化护盾",
+        Text::PerkBiggerShieldDescription => "护盾持续时间延长 50%",
+        Text::PerkPierce => "穿甲弹",
+        Text::PerkPierceDescription => "炮弹可以多穿透一辆坦克",
+        Text::PerkRicochet => "跳弹 +1",
+        Text::PerkRicochetDescription => "炮弹可以多反弹一次",
+        Text::PerkDrone => "僚机无人机",
+        Text::PerkDroneDescription => "无人机环绕在你身边，攻击附近的敌人",
+        Text::WaveIncoming => "第 {0} 波即将到来",
+        Text::ChooseUpgrade => "为本局选择一项强化",
+        Text::IntermissionHelp => "1/2/3 或 左/右 + ENTER：选择",
+        Text::SupplyDropTitle => "补给箱：{0}（{1}）",
+        Text::SupplyDropHint => "按 1-4 或手柄方向键 上 / 右 / 下 / 左",
+        Text::SupplyDropUnlocked => "补给箱：{0}",
+        Text::SupplyDropLost => "补给箱丢失",
+        Text::AnswerWas => "正确答案是 {0}",
+        Text::RewardRepair => "完全修复",
+        Text::RewardRepairDescription => "恢复全部生命",
+        Text::RewardFortress => "堡垒护盾",
+        Text::RewardFortressDescription => "60 秒护盾",
+        Text::RewardArsenal => "军火库",
+        Text::RewardArsenalDescription => "散射弹和 +50% 速度",
+        Text::ChallengeTitle => "{0}挑战",
+        Text::ReviveTitle => "{0} - 复活！",
+        Text::ChallengeAnswerHelp => "ENTER / (A)：作答   ESC / (B)：放弃",
+        Text::ReviveHealth => "复活生命：{0}%",
+        Text::ReviveStreak => "连对 x{0}",
+        Text::RevivesLeft => "剩余复活：{0}",
+        Text::KindMath => "数学",
+        Text::KindTimesTables => "乘法表",
+        Text::KindSpelling => "拼写",
+        Text::KindVocabulary => "词汇",
+        Text::KindGeography => "地理",
+        Text::KindReflex => "反应",
+        Text::PromptMath => "答对这道数学题即可复活：",
+        Text::PromptTimesTable => "完成乘法表即可复活：",
+        Text::PromptSpelling => "拼出这个单词即可复活：",
+        Text::PromptVocabulary => "选出单词的意思即可复活：",
+        Text::PromptGeography => "答对这道地理题即可复活：",
+        Text::PromptReflex => "等待绿灯亮起，及时反应即可复活：",
+        Text::PromptPack => "答对这道题即可复活：",
+        Text::HintChoices => "点击答案、按 1-4，或使用方向键 / 手柄方向键",
+        Text::HintMathTyped => "可以输入负数、小数（0.5）和分数（3/4）",
+        Text::HintSpellingTyped => "把打乱的字母重新排列，输入这个单词",
+        Text::HintSpellingChoices => "选出正确的拼写：点击、按 1-4 或使用手柄方向键",
+        Text::HintReflex => "绿灯一亮就按 ENTER、点击鼠标或按 (A)",
+        Text::HintPackTyped => "输入答案，不区分大小写，多余的空格不影响",
+        Text::HintPackChoices => "点击答案、按数字键，或使用方向键 / 手柄方向键",
+        Text::SubtitleTimesTable => "{0} 的乘法表",
+        Text::SubtitleLetters => "{0} 个字母",
+        Text::SubtitleCapitals => "首都",
+        Text::GeographyQuestion => "{0} 的首都是哪里？",
+        Text::SubtitleReactionWindow => "反应时间：{0}秒",
+        Text::SubtitleLevel => "难度 {0}",
+        Text::ReflexNow => "就是现在！",
+        Text::ReflexGetReady => "准备...",
+        Text::ReflexWait => "等待...",
+        Text::ReflexCorrectAnswer => "在绿灯亮起后 {0} 秒内反应",
+    }
+}
+
+// 成就的名称和说明，按 assets/achievements.json 中的编号查找
+pub fn achievement(id: &str) -> Option<(&'static str, &'static str)> {
+    let text = match id {
+        "first_blood" => ("首开纪录", "击毁第一辆敌方坦克"),
+        "baptism_of_fire" => ("初经战火", "清除第 1 波"),
+        "holding_the_line" => ("坚守阵地", "清除第 5 波"),
+        "last_tank_standing" => ("最后的坦克", "清除第 15 波"),
+        "purist" => ("纯粹主义者", "不拾取任何道具清除第 10 波"),
+        "ghost_driver" => ("幽灵驾驶", "不碰到墙清除第 5 波"),
+        "two_birds" => ("一箭双雕", "一次散射击毁 2 辆坦克"),
+        "buckshot" => ("霰弹风暴", "一次散射击毁 3 辆坦克"),
+        "mathlete" => ("数学达人", "连续答对 5 道数学题"),
+        "high_roller" => ("高分玩家", "单局得分达到 10,000 分"),
+        "tank_hunter" => ("坦克猎人", "累计击毁 100 辆敌方坦克"),
+        "veteran" => ("身经百战", "累计击毁 1000 辆敌方坦克"),
+        _ => return None,
+    };
+    Some(text)
+}
\ No newline at end of file
@@ -4,13 +4,31 @@ mod game;
 mod entities;
 mod systems;
 mod ui;
+mod challenges;
+mod i18n;
 mod math_challenge;
+mod storage;
 
 use game::Game;
+use storage::{Settings, WindowMode};
 
-#[macroquad::main("Tank Battle")]
+// 启动时按照保存的设置创建窗口
+fn window_conf() -> Conf {
+    let settings = Settings::load();
+    let (width, height) = settings.window_size();
+    Conf {
+        window_title: "Tank Battle".to_string(),
+        window_width: width as i32,
+        window_height: height as i32,
+        fullscreen: settings.window_mode == WindowMode::Fullscreen,
+        ..Default::default()
+    }
+}
+
+#[macroquad::main(window_conf)]
 async fn main() {
     let mut game = Game::new();
+    game.audio.load().await;
     
     loop {
         game.update().await;
@@ -0,0 +1,100 @@
+use super::{Answer, Problem, Topic};
+use crate::storage::LearnerProfile;
+use ::rand::Rng;
+
+// 计算题型正确率时只看最近的作答，进步后权重会随之下降
+const RECENT_ATTEMPTS: usize = 20;
+// 没有做过的题型的权重，介于全对（1.0）和全错（4.0）之间
+const UNSEEN_WEIGHT: f32 = 2.0;
+
+// 题型被选中的权重：正确率越低、答题越慢，越容易被选中
+pub fn topic_weight(profile: &LearnerProfile, topic: Topic) -> f32 {
+    let summary = profile.topic_summary(topic, Some(RECENT_ATTEMPTS));
+    if summary.attempts == 0 {
+        return UNSEEN_WEIGHT;
+    }
+    let slow = if summary.average_seconds() > 15.0 { 0.5 } else { 0.0 };
+    1.0 + 3.0 * (1.0 - summary.accuracy()) + slow
+}
+
+// 学习模式下的出题：优先出到期的复习题，否则按薄弱程度选择题型
+pub fn choose_problem(profile: &LearnerProfile, topics: &[Topic], tier: u32, rng: &mut impl Rng) -> Problem {
+    if let Some(item) = profile.due_review(topics) {
+        if let Some(answer) = Answer::parse(&item.answer) {
+            return Problem {
+                topic: item.topic,
+                tier: item.tier,
+                text: item.question.clone(),
+                answer,
+            };
+        }
+    }
+    
+    let weights: Vec<f32> = topics.iter().map(|&topic| topic_weight(profile, topic)).collect();
+    let mut roll = rng.gen_range(0.0..weights.iter().sum::<f32>().max(f32::EPSILON));
+    let topic = topics
+        .iter()
+        .zip(&weights)
+        .find(|(_, &weight)| {
+            if roll < weight {
+                true
+            } else {
+                roll -= weight;
+                false
+            }
+        })
+        .map(|(&topic, _)| topic)
+        .or_else(|| topics.last().copied())
+        .unwrap_or(Topic::Addition);
+    topic.generate(rng, tier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Attempt;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+    
+    fn record(profile: &mut LearnerProfile, topic: Topic, question: &str, correct: bool) {
+        profile.record(Attempt {
+            date: "2026-01-01".to_string(),
+            topic,
+            tier: 0,
+            question: question.to_string(),
+            answer: "4".to_string(),
+            given: String::new(),
+            correct,
+            seconds: 3.0,
+        });
+    }
+    
+    #[test]
+    fn weak_topics_are_chosen_more_often() {
+        let mut profile = LearnerProfile::default();
+        for i in 0..10 {
+            record(&mut profile, Topic::Addition, &format!("{} + 0 = ?", i), true);
+            record(&mut profile, Topic::Division, &format!("{} / 1 = ?", i), i % 2 == 0);
+        }
+        profile.review.clear();
+        assert!(topic_weight(&profile, Topic::Division) > topic_weight(&profile, Topic::Addition));
+        
+        let mut rng = StdRng::seed_from_u64(2);
+        let topics = [Topic::Addition, Topic::Division];
+        let division = (0..1000)
+            .filter(|_| choose_problem(&profile, &topics, 0, &mut rng).topic == Topic::Division)
+            .count();
+        assert!(division > 600, "{}", division);
+    }
+    
+    #[test]
+    fn due_reviews_are_asked_first() {
+        let mut profile = LearnerProfile::default();
+        record(&mut profile, Topic::Multiplication, "2 * 2 = ?", false);
+        record(&mut profile, Topic::Addition, "1 + 3 = ?", true);
+        let mut rng = StdRng::seed_from_u64(0);
+        let problem = choose_problem(&profile, &[Topic::Multiplication], 0, &mut rng);
+        assert_eq!(problem.text, "2 * 2 = ?");
+        assert_eq!(problem.answer, Answer::integer(4));
+    }
+}
\ No newline at end of file
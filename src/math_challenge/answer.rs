@@ -0,0 +1,136 @@
+use ::rand::seq::SliceRandom;
+use ::rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// 题目答案，统一用约分后的分数表示，这样 "0.5"、"1/2" 和 "2/4" 都能判为相同答案
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answer {
+    numerator: i64,
+    denominator: i64, // 始终大于 0
+}
+
+impl Answer {
+    pub fn integer(value: i64) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+    
+    pub fn fraction(numerator: i64, denominator: i64) -> Self {
+        Self::checked_fraction(numerator, denominator).expect("分母不能为 0，分子和分母不能是 i64::MIN")
+    }
+    
+    // 分母为 0 或者分子、分母为 i64::MIN（取反会溢出）时返回 None
+    pub fn checked_fraction(numerator: i64, denominator: i64) -> Option<Self> {
+        if denominator == 0 || numerator == i64::MIN || denominator == i64::MIN {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator).max(1);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        if denominator < 0 {
+            Some(Self {
+                numerator: numerator.checked_neg()?,
+                denominator: denominator.checked_neg()?,
+            })
+        } else {
+            Some(Self { numerator, denominator })
+        }
+    }
+    
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+    
+    // 解析玩家的输入：整数（可带负号）、小数或 "分子/分母" 形式的分数；超出范围的数字视为无法解析
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if let Some((numerator, denominator)) = input.split_once('/') {
+            let numerator = parse_integer(numerator)?;
+            let denominator = parse_integer(denominator)?;
+            return Self::checked_fraction(numerator, denominator);
+        }
+        if let Some((whole, decimals)) = input.split_once('.') {
+            if decimals.is_empty() || decimals.len() > 6 || !decimals.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let negative = whole.starts_with('-');
+            let whole = match whole.trim_start_matches('-') {
+                "" => 0,
+                digits => parse_integer(digits)?,
+            };
+            let scale = 10_i64.pow(decimals.len() as u32);
+            let numerator = whole.checked_mul(scale)?.checked_add(decimals.parse::<i64>().ok()?)?;
+            let numerator = if negative { numerator.checked_neg()? } else { numerator };
+            return Self::checked_fraction(numerator, scale);
+        }
+        parse_integer(input).map(Self::integer)
+    }
+}
+
+// 为选择题生成 count 个看起来合理的错误选项：差一、差十、符号错误、分子分母算错等常见失误
+pub fn distractors(answer: Answer, count: usize, rng: &mut impl Rng) -> Vec<Answer> {
+    let Answer { numerator: n, denominator: d } = answer;
+    let mut candidates = if answer.is_integer() {
+        let mut values = vec![n + 1, n - 1, n + 2, n - 2, n + 10, n - 10, n * 2, -n];
+        // 答案不是负数时，负数选项太容易排除
+        if n >= 0 {
+            values.retain(|value| *value >= 0);
+        }
+        values.into_iter().map(Answer::integer).collect::<Vec<_>>()
+    } else {
+        vec![
+            Answer::fraction(n + 1, d),
+            Answer::fraction(n - 1, d),
+            Answer::fraction(n, d + 1),
+            Answer::fraction(n, d * 2),
+            Answer::fraction(d, n),
+            Answer::fraction(-n, d),
+        ]
+    };
+    candidates.shuffle(rng);
+    let mut chosen: Vec<Answer> = Vec::new();
+    for candidate in candidates {
+        if chosen.len() < count && candidate != answer && !chosen.contains(&candidate) {
+            chosen.push(candidate);
+        }
+    }
+    // 候选不够时（比如答案为 0）继续往上数
+    let mut offset = 3;
+    while chosen.len() < count {
+        let candidate = Answer::fraction(n + offset * d, d);
+        if !chosen.contains(&candidate) {
+            chosen.push(candidate);
+        }
+        offset += 1;
+    }
+    chosen
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+fn parse_integer(text: &str) -> Option<i64> {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    // i64::MIN 取反会溢出，和超出范围的数字一样拒绝
+    text.parse().ok().filter(|value| *value != i64::MIN)
+}
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
\ No newline at end of file
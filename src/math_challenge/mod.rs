@@ -0,0 +1,232 @@
+pub mod adaptive;
+pub mod answer;
+pub mod topics;
+
+pub use answer::Answer;
+pub use topics::{GradeLevel, Problem, Topic, MAX_TIER};
+
+use crate::i18n::{tr, Text};
+use ::rand::seq::SliceRandom;
+use ::rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+// 每隔几波题目难度提高一档
+const WAVES_PER_TIER: i32 = 3;
+
+// 数学题难度，决定起始难度档位
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MathLevel {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl MathLevel {
+    pub const ALL: [MathLevel; 3] = [MathLevel::Easy, MathLevel::Normal, MathLevel::Hard];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            MathLevel::Easy => tr(Text::DifficultyEasy),
+            MathLevel::Normal => tr(Text::DifficultyNormal),
+            MathLevel::Hard => tr(Text::DifficultyHard),
+        }
+    }
+    
+    fn base_tier(&self) -> u32 {
+        match self {
+            MathLevel::Easy => 0,
+            MathLevel::Normal => 2,
+            MathLevel::Hard => 4,
+        }
+    }
+    
+    // 难度档位随波数提高
+    pub fn tier_for_wave(&self, wave: i32) -> u32 {
+        (self.base_tier() + ((wave - 1).max(0) / WAVES_PER_TIER) as u32).min(MAX_TIER)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MathChallenge {
+    pub problem: Problem,
+    pub user_input: String,
+    pub is_completed: bool,
+    pub is_correct: bool,
+    pub choices: Vec<Answer>, // 选择题的选项，为空时需要输入答案
+    pub selected: usize,      // 键盘或手柄当前选中的选项
+}
+
+impl MathChallenge {
+    pub const MAX_INPUT_LEN: usize = 8;
+    pub const CHOICES: usize = 4;
+    
+    pub fn new(problem: Problem) -> Self {
+        Self {
+            problem,
+            user_input: String::new(),
+            is_completed: false,
+            is_correct: false,
+            choices: Vec::new(),
+            selected: 0,
+        }
+    }
+    
+    // 从给定的题目类型中随机选一种出题
+    pub fn generate(topics: &[Topic], tier: u32) -> Self {
+        let mut rng = thread_rng();
+        let topic = topics.choose(&mut rng).copied().unwrap_or(Topic::Addition);
+        Self::new(topic.generate(&mut rng, tier))
+    }
+    
+    // 改为选择题：正确答案和三个干扰项随机排列
+    pub fn offer_choices(&mut self, rng: &mut impl Rng) {
+        let mut choices = answer::distractors(self.problem.answer, Self::CHOICES - 1, rng);
+        choices.push(self.problem.answer);
+        choices.shuffle(rng);
+        self.choices = choices;
+        self.selected = 0;
+        self.user_input.clear();
+    }
+    
+    pub fn is_multiple_choice(&self) -> bool {
+        !self.choices.is_empty()
+    }
+    
+    // 选择一个选项作为答案提交
+    pub fn choose(&mut self, index: usize) -> bool {
+        let Some(choice) = self.choices.get(index) else {
+            return false;
+        };
+        self.selected = index;
+        self.user_input = choice.to_string();
+        self.submit_answer()
+    }
+    
+    pub fn get_question_text(&self) -> &str {
+        &self.problem.text
+    }
+    
+    // 只接受能组成整数、小数或分数的字符
+    pub fn add_char(&mut self, c: char) {
+        if self.user_input.len() >= Self::MAX_INPUT_LEN {
+            return;
+        }
+        let input = &self.user_input;
+        let allowed = match c {
+            '0'..='9' => true,
+            '-' => input.is_empty() || input.ends_with('/'),
+            '.' => !input.contains('.') && !input.contains('/'),
+            '/' => !input.contains('.') && !input.contains('/') && input.bytes().any(|b| b.is_ascii_digit()),
+            _ => false,
+        };
+        if allowed {
+            self.user_input.push(c);
+        }
+    }
+    
+    pub fn remove_char(&mut self) {
+        self.user_input.pop();
+    }
+    
+    // 输入无法解析时不算作答，玩家可以继续修改
+    pub fn submit_answer(&mut self) -> bool {
+        if let Some(answer) = Answer::parse(&self.user_input) {
+            self.is_correct = answer == self.problem.answer;
+            self.is_completed = true;
+            self.is_correct
+        } else {
+            false
+        }
+    }
+    
+    pub fn get_user_answer(&self) -> &str {
+        &self.user_input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+    
+    fn type_answer(challenge: &mut MathChallenge, text: &str) {
+        for c in text.chars() {
+            challenge.add_char(c);
+        }
+    }
+    
+    #[test]
+    fn answers_parse_in_all_formats() {
+        assert_eq!(Answer::parse("42"), Some(Answer::integer(42)));
+        assert_eq!(Answer::parse("-7"), Some(Answer::integer(-7)));
+        assert_eq!(Answer::parse("0.5"), Some(Answer::fraction(1, 2)));
+        assert_eq!(Answer::parse("-.25"), Some(Answer::fraction(-1, 4)));
+        assert_eq!(Answer::parse("6/8"), Some(Answer::fraction(3, 4)));
+        assert_eq!(Answer::parse("4/2"), Some(Answer::integer(2)));
+        assert_eq!(Answer::parse("1/-3"), Some(Answer::fraction(-1, 3)));
+        for invalid in ["", "-", "1/0", "1.", "1..2", "a", "--1", "1/2/3"] {
+            assert_eq!(Answer::parse(invalid), None, "{}", invalid);
+        }
+        
+        // 超出 i64 范围的输入不能溢出，只是无法解析
+        assert_eq!(Answer::parse("9223372036854775807"), Some(Answer::integer(i64::MAX)));
+        assert_eq!(Answer::parse("-9223372036854775807/-1"), Some(Answer::integer(i64::MAX)));
+        for overflow in [
+            "999999999999999999.5",
+            "-999999999999999999.5",
+            "9223372036854775.808",
+            "9223372036854775808",
+            "-9223372036854775808",
+            "-9223372036854775808/-1",
+            "1/-9223372036854775808",
+        ] {
+            assert_eq!(Answer::parse(overflow), None, "{}", overflow);
+        }
+    }
+    
+    #[test]
+    fn every_topic_answer_can_be_typed() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for topic in Topic::ALL {
+            for tier in 0..=MAX_TIER {
+                for _ in 0..20 {
+                    let problem = topic.generate(&mut rng, tier);
+                    assert!(problem.text.is_ascii(), "{}", problem.text);
+                    let mut challenge = MathChallenge::new(problem.clone());
+                    type_answer(&mut challenge, &problem.answer.to_string());
+                    assert!(challenge.submit_answer(), "{} -> {}", problem.text, problem.answer);
+                }
+            }
+        }
+    }
+    
+    #[test]
+    fn equations_have_integer_solutions() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..200 {
+            let problem = Topic::Equations.generate(&mut rng, MAX_TIER);
+            assert!(problem.answer.is_integer());
+            assert!(problem.text.ends_with(", x = ?"));
+        }
+    }
+    
+    #[test]
+    fn decimal_input_matches_fraction_answer() {
+        let mut challenge = MathChallenge::new(Problem {
+            topic: Topic::Fractions,
+            tier: 0,
+            text: "1/4 + 1/4 = ?".to_string(),
+            answer: Answer::fraction(1, 2),
+        });
+        type_answer(&mut challenge, "0.5");
+        assert!(challenge.submit_answer());
+    }
+    
+    #[test]
+    fn difficulty_scales_with_wave() {
+        assert_eq!(MathLevel::Easy.tier_for_wave(1), 0);
+        assert_eq!(MathLevel::Easy.tier_for_wave(4), 1);
+        assert_eq!(MathLevel::Hard.tier_for_wave(100), MAX_TIER);
+    }
+}
\ No newline at end of file
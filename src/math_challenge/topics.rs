@@ -0,0 +1,233 @@
+use super::answer::{gcd, Answer};
+use crate::i18n::{tr, Text};
+use ::rand::seq::SliceRandom;
+use ::rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// 难度档位上限；档位越高数字越大、步骤越多
+pub const MAX_TIER: u32 = 9;
+
+// 各档位加减法的数字上限
+const NUMBER_LIMITS: [i64; MAX_TIER as usize + 1] = [10, 20, 50, 100, 200, 500, 1000, 2000, 5000, 10000];
+
+// 生成好的一道题
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Problem {
+    pub topic: Topic,
+    pub tier: u32,
+    pub text: String,
+    pub answer: Answer,
+}
+
+// 题目类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Topic {
+    Addition,
+    Subtraction,
+    Multiplication,
+    Division,
+    MultiStep,
+    Negatives,
+    Fractions,
+    Percentages,
+    Powers,
+    Equations,
+}
+
+impl Topic {
+    pub const ALL: [Topic; 10] = [
+        Topic::Addition,
+        Topic::Subtraction,
+        Topic::Multiplication,
+        Topic::Division,
+        Topic::MultiStep,
+        Topic::Negatives,
+        Topic::Fractions,
+        Topic::Percentages,
+        Topic::Powers,
+        Topic::Equations,
+    ];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Topic::Addition => tr(Text::TopicAddition),
+            Topic::Subtraction => tr(Text::TopicSubtraction),
+            Topic::Multiplication => tr(Text::TopicMultiplication),
+            Topic::Division => tr(Text::TopicDivision),
+            Topic::MultiStep => tr(Text::TopicMultiStep),
+            Topic::Negatives => tr(Text::TopicNegatives),
+            Topic::Fractions => tr(Text::TopicFractions),
+            Topic::Percentages => tr(Text::TopicPercentages),
+            Topic::Powers => tr(Text::TopicPowers),
+            Topic::Equations => tr(Text::TopicEquations),
+        }
+    }
+    
+    // 按难度档位生成一道这个类型的题；题目只用 ASCII 字符（乘除号写作 * 和 /），内置字体中没有 × 和 ÷
+    pub fn generate(&self, rng: &mut impl Rng, tier: u32) -> Problem {
+        let tier = tier.min(MAX_TIER);
+        let limit = NUMBER_LIMITS[tier as usize];
+        let factor = 5 + 2 * tier as i64; // 乘除法因数上限
+        let (text, answer) = match self {
+            Topic::Addition => {
+                let a = rng.gen_range(limit / 10..=limit);
+                let b = rng.gen_range(limit / 10..=limit);
+                (format!("{} + {} = ?", a, b), a + b)
+            }
+            Topic::Subtraction => {
+                let a = rng.gen_range(limit / 5..=limit);
+                let b = rng.gen_range(0..=a);
+                (format!("{} - {} = ?", a, b), a - b)
+            }
+            Topic::Multiplication => {
+                let a = rng.gen_range(2..=factor);
+                let b = rng.gen_range(2..=factor);
+                (format!("{} * {} = ?", a, b), a * b)
+            }
+            Topic::Division => {
+                let divisor = rng.gen_range(2..=factor);
+                let quotient = rng.gen_range(2..=factor);
+                (format!("{} / {} = ?", divisor * quotient, divisor), quotient)
+            }
+            Topic::MultiStep => {
+                let small = limit.min(50);
+                let a = rng.gen_range(1..=small);
+                let b = rng.gen_range(2..=factor);
+                let c = rng.gen_range(2..=factor);
+                match rng.gen_range(0..3) {
+                    0 => (format!("{} + {} * {} = ?", a, b, c), a + b * c),
+                    1 => (format!("({} + {}) * {} = ?", a, b, c), (a + b) * c),
+                    _ => {
+                        let d = rng.gen_range(1..=b * c);
+                        (format!("{} * {} - {} + {} = ?", b, c, d, a), b * c - d + a)
+                    }
+                }
+            }
+            Topic::Negatives => {
+                let a = rng.gen_range(-limit..=limit);
+                let b = rng.gen_range(-limit..=limit);
+                match rng.gen_range(0..3) {
+                    0 => (format!("{} + {} = ?", a, signed(b)), a + b),
+                    1 => (format!("{} - {} = ?", a, signed(b)), a - b),
+                    _ => {
+                        let a = rng.gen_range(-factor..=factor);
+                        let b = rng.gen_range(-factor..=factor);
+                        (format!("{} * {} = ?", a, signed(b)), a * b)
+                    }
+                }
+            }
+            Topic::Fractions => return fractions(rng, tier),
+            Topic::Percentages => {
+                let percent = *[50, 25, 10, 20, 75, 5, 15, 40, 12, 35][..(tier as usize + 1).max(4)]
+                    .choose(rng)
+                    .unwrap_or(&50);
+                // 选一个能让结果为整数的基数
+                let step = 100 / gcd(percent, 100);
+                let base = step * rng.gen_range(1..=(limit / step).max(2));
+                (format!("{}% * {} = ?", percent, base), base * percent / 100)
+            }
+            Topic::Powers => {
+                let exponent = if tier >= 4 && rng.gen_bool(0.4) { 3 } else { 2 };
+                let max_base = if exponent == 3 { 2 + tier as i64 / 2 } else { 4 + 2 * tier as i64 };
+                let base = rng.gen_range(2..=max_base);
+                (format!("{}^{} = ?", base, exponent), base.pow(exponent))
+            }
+            Topic::Equations => {
+                let a = rng.gen_range(1..=factor.min(12));
+                let low = if tier >= 5 { -factor } else { 1 };
+                let x = rng.gen_range(low..=factor);
+                let b = rng.gen_range(-limit.min(100)..=limit.min(100));
+                let lhs = match (a, b) {
+                    (1, 0) => "x".to_string(),
+                    (1, b) => format!("x {} {}", if b < 0 { '-' } else { '+' }, b.abs()),
+                    (a, 0) => format!("{}x", a),
+                    (a, b) => format!("{}x {} {}", a, if b < 0 { '-' } else { '+' }, b.abs()),
+                };
+                (format!("{} = {}, x = ?", lhs, a * x + b), x)
+            }
+        };
+        Problem {
+            topic: *self,
+            tier,
+            text,
+            answer: Answer::integer(answer),
+        }
+    }
+}
+
+// 分数加减法，答案约分后可能是整数
+fn fractions(rng: &mut impl Rng, tier: u32) -> Problem {
+    let max_denominator = 4 + tier as i64;
+    let b = rng.gen_range(2..=max_denominator);
+    // 低档位使用同分母
+    let d = if tier < 3 { b } else { rng.gen_range(2..=max_denominator) };
+    let a = rng.gen_range(1..b);
+    let c = rng.gen_range(1..d);
+    let (op, numerator) = if rng.gen_bool(0.5) {
+        ('+', a * d + c * b)
+    } else {
+        ('-', a * d - c * b)
+    };
+    Problem {
+        topic: Topic::Fractions,
+        tier,
+        text: format!("{}/{} {} {}/{} = ?", a, b, op, c, d),
+        answer: Answer::fraction(numerator, b * d),
+    }
+}
+
+// 负数作为第二个操作数时加上括号，避免出现 "5 - -3"
+fn signed(value: i64) -> String {
+    if value < 0 {
+        format!("({})", value)
+    } else {
+        value.to_string()
+    }
+}
+
+// 年级预设，决定题目类型的组合
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GradeLevel {
+    Grades1To2,
+    Grades3To4,
+    Grades5To6,
+    Grades7To8,
+    Custom, // 使用设置文件中的 math_topics
+}
+
+impl GradeLevel {
+    pub const ALL: [GradeLevel; 5] = [
+        GradeLevel::Grades1To2,
+        GradeLevel::Grades3To4,
+        GradeLevel::Grades5To6,
+        GradeLevel::Grades7To8,
+        GradeLevel::Custom,
+    ];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            GradeLevel::Grades1To2 => tr(Text::Grades1To2),
+            GradeLevel::Grades3To4 => tr(Text::Grades3To4),
+            GradeLevel::Grades5To6 => tr(Text::Grades5To6),
+            GradeLevel::Grades7To8 => tr(Text::Grades7To8),
+            GradeLevel::Custom => tr(Text::GradesCustom),
+        }
+    }
+    
+    // 预设包含的题目类型；自定义但没有选择任何类型时退回到加减法
+    pub fn topics(&self, custom: &[Topic]) -> Vec<Topic> {
+        let count = match self {
+            GradeLevel::Grades1To2 => 2,
+            GradeLevel::Grades3To4 => 4,
+            GradeLevel::Grades5To6 => 5,
+            GradeLevel::Grades7To8 => Topic::ALL.len(),
+            GradeLevel::Custom if custom.is_empty() => 2,
+            GradeLevel::Custom => return custom.to_vec(),
+        };
+        let mut topics = Topic::ALL[..count].to_vec();
+        if *self == GradeLevel::Grades5To6 {
+            topics.extend([Topic::Fractions, Topic::Percentages]);
+        }
+        topics
+    }
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+
+// 成就进度：已解锁的成就（编号 -> 解锁日期）和跨局累计的计数
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AchievementProgress {
+    pub unlocked: BTreeMap<String, String>,
+    pub total_kills: u32,
+    pub math_streak: u32, // 连续答对数学题的次数，答错时清零
+}
+
+impl AchievementProgress {
+    pub fn load() -> Self {
+        super::load_json(ACHIEVEMENTS_FILE)
+    }
+    
+    pub fn save(&self) {
+        if let Err(err) = super::save_json(ACHIEVEMENTS_FILE, self) {
+            eprintln!("无法保存成就: {}", err);
+        }
+    }
+    
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains_key(id)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use crate::game::GameMode;
+use crate::i18n::{format_duration, format_number, tr, trf, Text};
+use crate::systems::RunStats;
+use serde::{Deserialize, Serialize};
+
+const HISTORY_FILE: &str = "stats_history.json";
+
+// 一局游戏的记录
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub date: String,
+    pub mode: GameMode,
+    pub difficulty: f32,
+    pub score: i32,
+    pub wave: i32,
+    pub stats: RunStats,
+}
+
+// 历史统计：最近若干局的记录和所有局的累计数据
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsHistory {
+    pub runs: Vec<RunRecord>,
+    pub runs_played: u32,
+    pub best_score: i32,
+    pub best_wave: i32,
+    pub totals: RunStats,
+}
+
+impl StatsHistory {
+    // 只保留最近的记录，累计数据不受影响
+    pub const MAX_RUNS: usize = 200;
+    
+    pub fn load() -> Self {
+        super::load_json(HISTORY_FILE)
+    }
+    
+    pub fn save(&self) {
+        if let Err(err) = super::save_json(HISTORY_FILE, self) {
+            eprintln!("无法保存统计记录: {}", err);
+        }
+    }
+    
+    pub fn record(&mut self, record: RunRecord) {
+        self.runs_played += 1;
+        self.best_score = self.best_score.max(record.score);
+        self.best_wave = self.best_wave.max(record.wave);
+        self.totals.add(&record.stats);
+        self.runs.push(record);
+        if self.runs.len() > Self::MAX_RUNS {
+            let excess = self.runs.len() - Self::MAX_RUNS;
+            self.runs.drain(..excess);
+        }
+    }
+    
+    // 结算界面中显示的历史总计
+    pub fn summary_lines(&self) -> Vec<(String, String)> {
+        let totals = &self.totals;
+        vec![
+            (tr(Text::StatRunsPlayed).to_string(), format_number(self.runs_played as i64)),
+            (tr(Text::StatBestScore).to_string(), format_number(self.best_score as i64)),
+            (tr(Text::StatBestWave).to_string(), self.best_wave.to_string()),
+            (tr(Text::StatTotalTime).to_string(), format_duration(totals.time_alive)),
+            (tr(Text::StatTotalKills).to_string(), format_number(totals.total_kills() as i64)),
+            (tr(Text::StatAccuracy).to_string(), format!("{:.0}%", totals.accuracy() * 100.0)),
+            (tr(Text::StatDamageDealt).to_string(), format_number(totals.damage_dealt as i64)),
+            (tr(Text::StatDamageTaken).to_string(), format_number(totals.damage_taken as i64)),
+            (tr(Text::StatPowerUps).to_string(), format_number(totals.powerups_collected as i64)),
+            (tr(Text::StatWallsDestroyed).to_string(), format_number(totals.walls_destroyed as i64)),
+            (
+                tr(Text::StatMathAnswered).to_string(),
+                format!("{}/{}", totals.challenges_correct, totals.challenges_answered),
+            ),
+            (
+                tr(Text::StatDistance).to_string(),
+                trf(Text::DistanceMeters, &[&format_number((totals.distance / 10.0) as i64)]),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn record(score: i32, wave: i32, kills: u32) -> RunRecord {
+        RunRecord {
+            date: "2026-01-01".to_string(),
+            mode: GameMode::Classic,
+            difficulty: 1.0,
+            score,
+            wave,
+            stats: RunStats {
+                kills: [kills, 0, 0],
+                time_alive: 60.0,
+                ..Default::default()
+            },
+        }
+    }
+    
+    #[test]
+    fn record_updates_bests_and_totals() {
+        let mut history = StatsHistory::default();
+        history.record(record(500, 3, 4));
+        history.record(record(300, 5, 2));
+        assert_eq!(history.runs_played, 2);
+        assert_eq!(history.best_score, 500);
+        assert_eq!(history.best_wave, 5);
+        assert_eq!(history.totals.total_kills(), 6);
+        assert_eq!(history.totals.time_alive, 120.0);
+        assert_eq!(history.runs.len(), 2);
+    }
+    
+    #[test]
+    fn old_runs_are_trimmed_but_totals_are_kept() {
+        let mut history = StatsHistory::default();
+        for i in 0..StatsHistory::MAX_RUNS + 5 {
+            history.record(record(i as i32, 1, 1));
+        }
+        assert_eq!(history.runs.len(), StatsHistory::MAX_RUNS);
+        // 最早的 5 局被移除，保留最近的记录
+        assert_eq!(history.runs[0].score, 5);
+        assert_eq!(history.runs.last().unwrap().score, StatsHistory::MAX_RUNS as i32 + 4);
+        assert_eq!(history.runs_played, StatsHistory::MAX_RUNS as u32 + 5);
+        assert_eq!(history.totals.total_kills(), StatsHistory::MAX_RUNS as u32 + 5);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const LEADERBOARD_FILE: &str = "leaderboard.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub name: String,
+    pub score: i32,
+    pub wave: i32,
+    pub duration_secs: f64,
+    pub date: String,
+}
+
+// 本地排行榜，按 "难度/模式" 分别保存前 N 名
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    #[serde(default)]
+    pub boards: BTreeMap<String, Vec<ScoreEntry>>,
+    // 上一次输入的玩家名字，作为下次输入的默认值
+    #[serde(default)]
+    pub last_name: String,
+}
+
+impl Leaderboard {
+    pub const MAX_ENTRIES: usize = 10;
+    pub const MAX_NAME_LEN: usize = 12;
+    
+    pub fn load() -> Self {
+        let mut leaderboard: Leaderboard = super::load_json(LEADERBOARD_FILE);
+        // 防止手动修改过的文件破坏排序和数量限制
+        for entries in leaderboard.boards.values_mut() {
+            entries.sort_by_key(|e| std::cmp::Reverse(e.score));
+            entries.truncate(Self::MAX_ENTRIES);
+        }
+        leaderboard
+    }
+    
+    pub fn save(&self) {
+        if let Err(err) = super::save_json(LEADERBOARD_FILE, self) {
+            eprintln!("无法保存排行榜: {}", err);
+        }
+    }
+    
+    pub fn entries(&self, board: &str) -> &[ScoreEntry] {
+        self.boards.get(board).map(|e| e.as_slice()).unwrap_or(&[])
+    }
+    
+    // 分数是否能进入该榜单
+    pub fn qualifies(&self, board: &str, score: i32) -> bool {
+        if score <= 0 {
+            return false;
+        }
+        let entries = self.entries(board);
+        entries.len() < Self::MAX_ENTRIES || entries.iter().any(|e| score > e.score)
+    }
+    
+    // 插入成绩，返回名次（从 0 开始）
+    pub fn insert(&mut self, board: &str, entry: ScoreEntry) -> Option<usize> {
+        self.last_name = entry.name.clone();
+        let entries = self.boards.entry(board.to_string()).or_default();
+        let rank = entries.iter().position(|e| entry.score > e.score).unwrap_or(entries.len());
+        if rank >= Self::MAX_ENTRIES {
+            return None;
+        }
+        entries.insert(rank, entry);
+        entries.truncate(Self::MAX_ENTRIES);
+        Some(rank)
+    }
+    
+    pub fn best_score(&self) -> i32 {
+        self.boards
+            .values()
+            .filter_map(|entries| entries.first())
+            .map(|e| e.score)
+            .max()
+            .unwrap_or(0)
+    }
+}
\ No newline at end of file
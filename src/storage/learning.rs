@@ -0,0 +1,351 @@
+use crate::challenges::ChallengeKind;
+use crate::math_challenge::{Problem, Topic};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+const LEARNING_FILE: &str = "learning.json";
+
+// 答错的题目按间隔重复：答对后分别隔 1、3、6、12 道题再出现，全部答对后移出复习队列
+const REVIEW_INTERVALS: [u32; 4] = [1, 3, 6, 12];
+
+// 一次作答记录
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Attempt {
+    pub date: String,
+    pub topic: Topic,
+    pub tier: u32,
+    pub question: String,
+    pub answer: String,
+    pub given: String, // 超时时为空
+    pub correct: bool,
+    pub seconds: f32,
+}
+
+// 复习队列中的一道题
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReviewItem {
+    pub topic: Topic,
+    pub tier: u32,
+    pub question: String,
+    pub answer: String,
+    pub step: u32, // 已经连续答对的次数，对应 REVIEW_INTERVALS 中的下标
+    pub due: u32,  // 作答总数达到这个值时重新出现
+}
+
+// 某个题型的汇总
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TopicSummary {
+    pub attempts: u32,
+    pub correct: u32,
+    pub total_seconds: f32,
+}
+
+impl TopicSummary {
+    fn add(&mut self, attempt: &Attempt) {
+        self.attempts += 1;
+        self.total_seconds += attempt.seconds;
+        if attempt.correct {
+            self.correct += 1;
+        }
+    }
+    
+    pub fn accuracy(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.correct as f32 / self.attempts as f32
+        }
+    }
+    
+    pub fn average_seconds(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.total_seconds / self.attempts as f32
+        }
+    }
+}
+
+// 一名玩家的学习记录
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LearnerProfile {
+    pub attempts: Vec<Attempt>,
+    pub review: Vec<ReviewItem>,
+    pub challenges: Vec<ChallengeKind>, // 复活时可能出现的挑战，为空时只出数学题
+}
+
+impl LearnerProfile {
+    pub fn revive_challenges(&self) -> Vec<ChallengeKind> {
+        if self.challenges.is_empty() {
+            vec![ChallengeKind::Arithmetic]
+        } else {
+            self.challenges.clone()
+        }
+    }
+    
+    // 开关一种挑战，至少保留一种
+    pub fn toggle_challenge(&mut self, kind: ChallengeKind) {
+        let mut challenges = self.revive_challenges();
+        if let Some(index) = challenges.iter().position(|existing| *existing == kind) {
+            if challenges.len() > 1 {
+                challenges.remove(index);
+            }
+        } else {
+            challenges.push(kind);
+            challenges.sort_by_key(|existing| ChallengeKind::ALL.iter().position(|k| k == existing));
+        }
+        self.challenges = challenges;
+    }
+    
+    // 记录一次作答并更新复习队列
+    pub fn record(&mut self, attempt: Attempt) {
+        let existing = self.review.iter().position(|item| item.question == attempt.question);
+        let next = self.attempts.len() as u32 + 1;
+        match (existing, attempt.correct) {
+            (Some(index), true) => {
+                let item = &mut self.review[index];
+                match REVIEW_INTERVALS.get(item.step as usize) {
+                    Some(interval) => {
+                        item.step += 1;
+                        item.due = next + interval;
+                    }
+                    None => {
+                        self.review.remove(index);
+                    }
+                }
+            }
+            (Some(index), false) => {
+                let item = &mut self.review[index];
+                item.step = 0;
+                item.due = next + 1;
+            }
+            (None, false) => self.review.push(ReviewItem {
+                topic: attempt.topic,
+                tier: attempt.tier,
+                question: attempt.question.clone(),
+                answer: attempt.answer.clone(),
+                step: 0,
+                due: next + 1,
+            }),
+            (None, true) => {}
+        }
+        self.attempts.push(attempt);
+    }
+    
+    // 已经到期、题型也在当前范围内的复习题中最早到期的一道
+    pub fn due_review(&self, topics: &[Topic]) -> Option<&ReviewItem> {
+        let answered = self.attempts.len() as u32;
+        self.review
+            .iter()
+            .filter(|item| item.due <= answered && topics.contains(&item.topic))
+            .min_by_key(|item| item.due)
+    }
+    
+    pub fn summary(&self) -> TopicSummary {
+        let mut summary = TopicSummary::default();
+        self.attempts.iter().for_each(|attempt| summary.add(attempt));
+        summary
+    }
+    
+    // 某个题型最近 recent 次作答的汇总（recent 为 None 时统计全部）
+    pub fn topic_summary(&self, topic: Topic, recent: Option<usize>) -> TopicSummary {
+        let mut summary = TopicSummary::default();
+        self.attempts
+            .iter()
+            .rev()
+            .filter(|attempt| attempt.topic == topic)
+            .take(recent.unwrap_or(usize::MAX))
+            .for_each(|attempt| summary.add(attempt));
+        summary
+    }
+    
+    // 最近 days 个有作答的日期的汇总，按日期从早到晚排列
+    pub fn daily_summaries(&self, days: usize) -> Vec<(String, TopicSummary)> {
+        let mut by_date: BTreeMap<&str, TopicSummary> = BTreeMap::new();
+        for attempt in &self.attempts {
+            by_date.entry(&attempt.date).or_default().add(attempt);
+        }
+        let skip = by_date.len().saturating_sub(days);
+        by_date
+            .into_iter()
+            .skip(skip)
+            .map(|(date, summary)| (date.to_string(), summary))
+            .collect()
+    }
+    
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("date,topic,difficulty,question,answer,given,correct,seconds\n");
+        for attempt in &self.attempts {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.1}\n",
+                attempt.date,
+                csv_field(attempt.topic.name()),
+                attempt.tier,
+                csv_field(&attempt.question),
+                csv_field(&attempt.answer),
+                csv_field(&attempt.given),
+                attempt.correct,
+                attempt.seconds,
+            ));
+        }
+        csv
+    }
+}
+
+// 所有玩家的学习记录，保存在 learning.json 中
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LearningData {
+    pub active: String,
+    pub profiles: BTreeMap<String, LearnerProfile>,
+}
+
+impl Default for LearningData {
+    fn default() -> Self {
+        let mut profiles = BTreeMap::new();
+        profiles.insert(Self::DEFAULT_PROFILE.to_string(), LearnerProfile::default());
+        Self {
+            active: Self::DEFAULT_PROFILE.to_string(),
+            profiles,
+        }
+    }
+}
+
+impl LearningData {
+    pub const DEFAULT_PROFILE: &'static str = "Player";
+    pub const MAX_NAME_LEN: usize = 16;
+    
+    pub fn load() -> Self {
+        let mut data: LearningData = super::load_json(LEARNING_FILE);
+        // 当前档案被手动删除时重新创建
+        data.profiles.entry(data.active.clone()).or_default();
+        data
+    }
+    
+    pub fn save(&self) {
+        if let Err(err) = super::save_json(LEARNING_FILE, self) {
+            eprintln!("无法保存学习记录: {}", err);
+        }
+    }
+    
+    pub fn profile(&self) -> &LearnerProfile {
+        &self.profiles[&self.active]
+    }
+    
+    pub fn profile_mut(&mut self) -> &mut LearnerProfile {
+        self.profiles.entry(self.active.clone()).or_default()
+    }
+    
+    // 记录当前档案的一次作答
+    pub fn record(&mut self, problem: &Problem, given: &str, correct: bool, seconds: f32) {
+        self.profile_mut().record(Attempt {
+            date: super::current_date(),
+            topic: problem.topic,
+            tier: problem.tier,
+            question: problem.text.clone(),
+            answer: problem.answer.to_string(),
+            given: given.to_string(),
+            correct,
+            seconds,
+        });
+    }
+    
+    // 创建（或切换到已有的）档案
+    pub fn select_or_create(&mut self, name: &str) {
+        self.profiles.entry(name.to_string()).or_default();
+        self.active = name.to_string();
+    }
+    
+    // 按名字顺序切换到上一个或下一个档案
+    pub fn cycle(&mut self, direction: i32) {
+        let names: Vec<&String> = self.profiles.keys().collect();
+        let index = names.iter().position(|name| **name == self.active).unwrap_or(0);
+        let next = (index as i32 + direction).rem_euclid(names.len() as i32) as usize;
+        self.active = names[next].clone();
+    }
+    
+    // 把当前档案的全部作答记录导出为 CSV
+    pub fn export_csv(&self) -> io::Result<PathBuf> {
+        let name: String = self
+            .active
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        super::write_text(&format!("learning_{}.csv", name), &self.profile().to_csv())
+    }
+}
+
+// 含逗号、引号或换行的字段加引号
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_challenge::Answer;
+    
+    fn attempt(question: &str, correct: bool) -> Attempt {
+        Attempt {
+            date: "2026-01-01".to_string(),
+            topic: Topic::Addition,
+            tier: 0,
+            question: question.to_string(),
+            answer: "2".to_string(),
+            given: if correct { "2" } else { "3" }.to_string(),
+            correct,
+            seconds: 2.0,
+        }
+    }
+    
+    #[test]
+    fn missed_problems_come_back_until_mastered() {
+        let mut profile = LearnerProfile::default();
+        profile.record(attempt("1 + 1 = ?", false));
+        assert!(profile.due_review(&[Topic::Addition]).is_none());
+        profile.record(attempt("2 + 3 = ?", true));
+        assert_eq!(profile.due_review(&[Topic::Addition]).unwrap().question, "1 + 1 = ?");
+        assert!(profile.due_review(&[Topic::Fractions]).is_none());
+        
+        for _ in 0..=REVIEW_INTERVALS.len() {
+            profile.record(attempt("1 + 1 = ?", true));
+        }
+        assert!(profile.review.is_empty());
+    }
+    
+    #[test]
+    fn wrong_review_answer_resets_the_interval() {
+        let mut profile = LearnerProfile::default();
+        profile.record(attempt("1 + 1 = ?", false));
+        profile.record(attempt("1 + 1 = ?", true));
+        profile.record(attempt("1 + 1 = ?", false));
+        assert_eq!(profile.review.len(), 1);
+        assert_eq!(profile.review[0].step, 0);
+        assert_eq!(profile.review[0].due, profile.attempts.len() as u32 + 1);
+    }
+    
+    #[test]
+    fn csv_quotes_fields_with_commas() {
+        let mut data = LearningData::default();
+        let problem = Problem {
+            topic: Topic::Equations,
+            tier: 3,
+            text: "2x + 1 = 5, x = ?".to_string(),
+            answer: Answer::integer(2),
+        };
+        data.record(&problem, "2", true, 4.0);
+        data.record(&problem, "1,5", false, 1.0);
+        let csv = data.profile().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with(r#"Equations,3,"2x + 1 = 5, x = ?",2,2,true,4.0"#));
+        assert!(lines[2].contains(",\"1,5\",false,"));
+    }
+}
\ No newline at end of file
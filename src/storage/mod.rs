@@ -0,0 +1,122 @@
+pub mod achievements;
+pub mod history;
+pub mod leaderboard;
+pub mod learning;
+pub mod packs;
+pub mod progression;
+pub mod savegame;
+pub mod settings;
+
+pub use achievements::*;
+pub use history::*;
+pub use leaderboard::*;
+pub use learning::*;
+pub use packs::*;
+pub use progression::*;
+pub use savegame::*;
+pub use settings::*;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const APP_DIR_NAME: &str = "tank_battle";
+
+// 存档目录：各平台的用户数据目录下的 tank_battle 文件夹
+// （Linux: ~/.local/share，macOS: ~/Library/Application Support，Windows: %APPDATA%）
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_DIR_NAME))
+}
+
+// 读取 JSON 存档；文件不存在时返回 None，
+// 文件损坏时将其重命名为 .corrupt 备份后返回 None，保证游戏可以继续运行
+pub fn read_json<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    let path = data_dir()?.join(file_name);
+    
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            if err.kind() != io::ErrorKind::NotFound {
+                eprintln!("无法读取存档 {}: {}", path.display(), err);
+            }
+            return None;
+        }
+    };
+    
+    match serde_json::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            eprintln!("存档 {} 已损坏，将重新创建: {}", path.display(), err);
+            let backup = path.with_extension("corrupt");
+            let _ = fs::rename(&path, backup);
+            None
+        }
+    }
+}
+
+// 读取 JSON 存档，读取失败时返回默认值
+pub fn load_json<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    read_json(file_name).unwrap_or_default()
+}
+
+// 写入 JSON 存档；先写入临时文件再重命名，避免写到一半崩溃导致存档损坏
+pub fn save_json<T: Serialize>(file_name: &str, value: &T) -> io::Result<()> {
+    let dir = data_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "找不到用户数据目录"))?;
+    fs::create_dir_all(&dir)?;
+    
+    let path = dir.join(file_name);
+    let tmp_path = path.with_extension("tmp");
+    let contents = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, &path)
+}
+
+pub fn file_exists(file_name: &str) -> bool {
+    data_dir().is_some_and(|dir| dir.join(file_name).exists())
+}
+
+// 删除存档文件；文件本来就不存在时不视为错误
+pub fn remove_file(file_name: &str) -> io::Result<()> {
+    let Some(path) = data_dir().map(|dir| dir.join(file_name)) else {
+        return Ok(());
+    };
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+// 当前日期（UTC），格式为 YYYY-MM-DD
+pub fn current_date() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// 将 1970-01-01 起的天数转换为公历日期（Howard Hinnant 的算法）
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+// 把文本写入数据目录下的文件（用于导出），返回完整路径
+pub fn write_text(file_name: &str, contents: &str) -> io::Result<PathBuf> {
+    let dir = data_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "找不到用户数据目录"))?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(file_name);
+    fs::write(&path, contents)?;
+    Ok(path)
+}
\ No newline at end of file
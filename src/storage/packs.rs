@@ -0,0 +1,75 @@
+use crate::challenges::{PackError, QuestionPack, SAMPLE_PACK};
+use std::fs;
+
+// 题库放在数据目录下的 packs 文件夹中，每个 .json 或 .toml 文件是一个题库
+const PACKS_DIR: &str = "packs";
+
+// 可以使用的题库和无法使用的题库文件
+#[derive(Clone, Debug, Default)]
+pub struct QuestionPacks {
+    pub packs: Vec<QuestionPack>,
+    pub errors: Vec<PackError>,
+}
+
+impl QuestionPacks {
+    // 读取随程序附带的示例题库和 packs 文件夹中的所有题库，名字重复时保留先读到的
+    pub fn load() -> Self {
+        let mut loaded = Self::default();
+        loaded.add(SAMPLE_PACK.0, SAMPLE_PACK.1);
+        
+        let Some(dir) = super::data_dir().map(|dir| dir.join(PACKS_DIR)) else {
+            return loaded;
+        };
+        // 提前建好文件夹，方便老师找到放题库的位置
+        let _ = fs::create_dir_all(&dir);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return loaded;
+        };
+        let mut paths: Vec<_> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+        paths.sort();
+        for path in paths {
+            let is_pack = path.extension().is_some_and(|ext| ext == "json" || ext == "toml");
+            let Some(file) = path.file_name().map(|name| name.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            if !is_pack {
+                continue;
+            }
+            match fs::read_to_string(&path) {
+                Ok(contents) => loaded.add(&file, &contents),
+                Err(err) => loaded.errors.push(PackError {
+                    file,
+                    problems: vec![format!("cannot read the file: {}", err)],
+                }),
+            }
+        }
+        for error in &loaded.errors {
+            eprintln!("题库无法使用 {}", error);
+        }
+        loaded
+    }
+    
+    fn add(&mut self, file: &str, contents: &str) {
+        match QuestionPack::parse(file, contents) {
+            Ok(pack) if self.find(&pack.name).is_some() => self.errors.push(PackError {
+                file: file.to_string(),
+                problems: vec![format!("another pack is already named \"{}\"", pack.name)],
+            }),
+            Ok(pack) => self.packs.push(pack),
+            Err(err) => self.errors.push(err),
+        }
+    }
+    
+    pub fn find(&self, name: &str) -> Option<&QuestionPack> {
+        self.packs.iter().find(|pack| pack.name == name)
+    }
+    
+    // 在 "内置题目" 和各个题库之间切换
+    pub fn cycle(&self, current: Option<&str>, direction: i32) -> Option<String> {
+        let mut names: Vec<Option<&str>> = vec![None];
+        names.extend(self.packs.iter().map(|pack| Some(pack.name.as_str())));
+        let index = names.iter().position(|name| *name == current).unwrap_or(0);
+        let next = (index as i32 + direction).rem_euclid(names.len() as i32) as usize;
+        names[next].map(str::to_string)
+    }
+}
\ No newline at end of file
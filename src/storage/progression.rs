@@ -0,0 +1,234 @@
+use crate::entities::{Tank, Weapon};
+use crate::i18n::{tr, Text};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const PROGRESSION_FILE: &str = "progression.json";
+
+// 商店中可以购买的永久升级
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Upgrade {
+    MaxHealth,
+    Speed,
+    FireRate,
+    StartingShield,
+    HeavyCannon,
+    TwinCannon,
+}
+
+impl Upgrade {
+    pub const ALL: [Upgrade; 6] = [
+        Upgrade::MaxHealth,
+        Upgrade::Speed,
+        Upgrade::FireRate,
+        Upgrade::StartingShield,
+        Upgrade::HeavyCannon,
+        Upgrade::TwinCannon,
+    ];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Upgrade::MaxHealth => tr(Text::UpgradeMaxHealth),
+            Upgrade::Speed => tr(Text::UpgradeSpeed),
+            Upgrade::FireRate => tr(Text::UpgradeFireRate),
+            Upgrade::StartingShield => tr(Text::UpgradeStartingShield),
+            Upgrade::HeavyCannon => tr(Text::UpgradeHeavyCannon),
+            Upgrade::TwinCannon => tr(Text::UpgradeTwinCannon),
+        }
+    }
+    
+    pub fn description(&self) -> &'static str {
+        match self {
+            Upgrade::MaxHealth => tr(Text::UpgradeMaxHealthDescription),
+            Upgrade::Speed => tr(Text::UpgradeSpeedDescription),
+            Upgrade::FireRate => tr(Text::UpgradeFireRateDescription),
+            Upgrade::StartingShield => tr(Text::UpgradeStartingShieldDescription),
+            Upgrade::HeavyCannon => tr(Text::UpgradeHeavyCannonDescription),
+            Upgrade::TwinCannon => tr(Text::UpgradeTwinCannonDescription),
+        }
+    }
+    
+    pub fn max_level(&self) -> u32 {
+        match self {
+            Upgrade::MaxHealth | Upgrade::Speed | Upgrade::FireRate => 4,
+            Upgrade::StartingShield => 3,
+            Upgrade::HeavyCannon | Upgrade::TwinCannon => 1,
+        }
+    }
+    
+    // 从 level 升到下一级的价格
+    pub fn cost(&self, level: u32) -> u32 {
+        match self {
+            Upgrade::MaxHealth | Upgrade::Speed | Upgrade::FireRate => 60 * (level + 1),
+            Upgrade::StartingShield => 80 * (level + 1),
+            Upgrade::HeavyCannon => 300,
+            Upgrade::TwinCannon => 250,
+        }
+    }
+}
+
+// 跨局保留的成长数据：废料（货币）和已购买的升级等级
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progression {
+    pub scrap: u32,
+    pub lifetime_scrap: u32,
+    pub levels: BTreeMap<Upgrade, u32>,
+    pub vanilla: bool, // 开启后开局不使用任何升级，成绩记入普通排行榜
+}
+
+impl Progression {
+    pub fn load() -> Self {
+        let mut progression: Progression = super::load_json(PROGRESSION_FILE);
+        // 防止手动修改过的文件超过等级上限
+        for (upgrade, level) in progression.levels.iter_mut() {
+            *level = (*level).min(upgrade.max_level());
+        }
+        progression
+    }
+    
+    pub fn save(&self) {
+        if let Err(err) = super::save_json(PROGRESSION_FILE, self) {
+            eprintln!("无法保存升级进度: {}", err);
+        }
+    }
+    
+    pub fn level(&self, upgrade: Upgrade) -> u32 {
+        self.levels.get(&upgrade).copied().unwrap_or(0)
+    }
+    
+    // 下一级的价格，已满级时为 None
+    pub fn next_cost(&self, upgrade: Upgrade) -> Option<u32> {
+        let level = self.level(upgrade);
+        (level < upgrade.max_level()).then(|| upgrade.cost(level))
+    }
+    
+    pub fn buy(&mut self, upgrade: Upgrade) -> bool {
+        match self.next_cost(upgrade) {
+            Some(cost) if cost <= self.scrap => {
+                self.scrap -= cost;
+                *self.levels.entry(upgrade).or_insert(0) += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    // 本局是否会使用升级
+    pub fn is_upgraded(&self) -> bool {
+        !self.vanilla && self.levels.values().any(|&level| level > 0)
+    }
+    
+    // 一局结束时获得的废料
+    pub fn scrap_for_run(score: i32, wave: i32) -> u32 {
+        (score.max(0) / 100 + (wave - 1).max(0) * 5) as u32
+    }
+    
+    pub fn award(&mut self, scrap: u32) {
+        self.scrap += scrap;
+        self.lifetime_scrap += scrap;
+    }
+    
+    // 开局时把升级应用到玩家坦克上
+    pub fn apply(&self, tank: &mut Tank) {
+        if self.vanilla {
+            return;
+        }
+        tank.max_health += 25 * self.level(Upgrade::MaxHealth) as i32;
+        tank.health = tank.max_health;
+        tank.speed *= 1.0 + 0.04 * self.level(Upgrade::Speed) as f32;
+        tank.shot_cooldown *= 1.0 - 0.08 * self.level(Upgrade::FireRate) as f64;
+        let shield = self.level(Upgrade::StartingShield);
+        if shield > 0 {
+            tank.add_shield(3.0 * shield as f64);
+        }
+        if self.level(Upgrade::HeavyCannon) > 0 {
+            tank.arsenal.push(Weapon::HeavyCannon);
+        }
+        if self.level(Upgrade::TwinCannon) > 0 {
+            tank.arsenal.push(Weapon::TwinCannon);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn upgraded() -> Progression {
+        let mut progression = Progression::default();
+        progression.levels.insert(Upgrade::MaxHealth, 2);
+        progression.levels.insert(Upgrade::Speed, 1);
+        progression.levels.insert(Upgrade::FireRate, 4);
+        progression.levels.insert(Upgrade::StartingShield, 1);
+        progression.levels.insert(Upgrade::HeavyCannon, 1);
+        progression
+    }
+    
+    #[test]
+    fn buying_spends_scrap_and_needs_enough() {
+        let mut progression = Progression::default();
+        progression.award(100);
+        assert_eq!(progression.next_cost(Upgrade::MaxHealth), Some(60));
+        assert!(progression.buy(Upgrade::MaxHealth));
+        assert_eq!(progression.scrap, 40);
+        assert_eq!(progression.level(Upgrade::MaxHealth), 1);
+        
+        // 第二级需要 120，废料不够时不扣费也不升级
+        assert_eq!(progression.next_cost(Upgrade::MaxHealth), Some(120));
+        assert!(!progression.buy(Upgrade::MaxHealth));
+        assert_eq!(progression.scrap, 40);
+        assert_eq!(progression.level(Upgrade::MaxHealth), 1);
+        assert_eq!(progression.lifetime_scrap, 100);
+    }
+    
+    #[test]
+    fn maxed_upgrades_cannot_be_bought() {
+        let mut progression = Progression::default();
+        progression.award(1000);
+        assert!(progression.buy(Upgrade::TwinCannon));
+        assert_eq!(progression.next_cost(Upgrade::TwinCannon), None);
+        assert!(!progression.buy(Upgrade::TwinCannon));
+        assert_eq!(progression.scrap, 750);
+    }
+    
+    #[test]
+    fn upgrades_apply_to_the_player_tank() {
+        let progression = upgraded();
+        assert!(progression.is_upgraded());
+        let mut tank = Tank::new_player(0.0, 0.0);
+        let base = Tank::new_player(0.0, 0.0);
+        progression.apply(&mut tank);
+        assert_eq!(tank.max_health, base.max_health + 50);
+        assert_eq!(tank.health, tank.max_health);
+        assert!((tank.speed - base.speed * 1.04).abs() < 1e-3);
+        assert!((tank.shot_cooldown - base.shot_cooldown * 0.68).abs() < 1e-9);
+        assert!(tank.shield.is_some());
+        assert!(tank.arsenal.contains(&Weapon::HeavyCannon));
+        assert!(!tank.arsenal.contains(&Weapon::TwinCannon));
+    }
+    
+    #[test]
+    fn vanilla_runs_ignore_upgrades() {
+        let mut progression = upgraded();
+        progression.vanilla = true;
+        // 普通模式的成绩不标记为升级过，和没有升级的玩家同榜比较
+        assert!(!progression.is_upgraded());
+        let mut tank = Tank::new_player(0.0, 0.0);
+        let base = Tank::new_player(0.0, 0.0);
+        progression.apply(&mut tank);
+        assert_eq!(tank.max_health, base.max_health);
+        assert_eq!(tank.speed, base.speed);
+        assert_eq!(tank.shot_cooldown, base.shot_cooldown);
+        assert!(tank.shield.is_none());
+        assert_eq!(tank.arsenal, base.arsenal);
+        assert!(!Progression::default().is_upgraded());
+    }
+    
+    #[test]
+    fn scrap_for_run_counts_score_and_waves() {
+        assert_eq!(Progression::scrap_for_run(1250, 1), 12);
+        assert_eq!(Progression::scrap_for_run(1250, 4), 27);
+        assert_eq!(Progression::scrap_for_run(-50, 0), 0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use crate::entities::*;
+use crate::game::GameMode;
+use crate::systems::{AchievementRun, RunStats, SpawnSystem, SupplyDrop};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+const SAVE_FILE: &str = "savegame.json";
+// 存档格式发生不兼容的变化时递增，旧版本的存档将被忽略
+const SAVE_VERSION: u32 = 2;
+
+// 进行中的一局游戏的快照（所有计时器都是相对时间，读档后可以直接继续）
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub mode: GameMode,
+    pub difficulty: f32,
+    pub score: i32,
+    pub wave: i32,
+    pub enemies_killed_this_wave: i32,
+    pub enemies_per_wave: i32,
+    pub time_since_difficulty_increase: f64,
+    pub run_time: f64,
+    pub player_tank: Tank,
+    pub enemies: Arena<Enemy>,
+    pub bullets: Vec<Bullet>,
+    pub explosions: Vec<Explosion>,
+    pub obstacles: Vec<Obstacle>,
+    pub powerups: Vec<PowerUp>,
+    #[serde(default)]
+    pub drones: Vec<Drone>,
+    pub spawn_system: SpawnSystem,
+    #[serde(default)]
+    pub stats: RunStats,
+    #[serde(default)]
+    pub achievement_run: AchievementRun,
+    #[serde(default)]
+    pub upgraded: bool, // 本局使用了升级，成绩记入单独的排行榜
+    #[serde(default)]
+    pub revives_used: u32,
+    #[serde(default)]
+    pub revive_streak: u32,
+    #[serde(default)]
+    pub supply_drop: Option<SupplyDrop>, // 保存时还没答完的补给箱题目
+}
+
+impl SaveGame {
+    pub const VERSION: u32 = SAVE_VERSION;
+    
+    pub fn exists() -> bool {
+        super::file_exists(SAVE_FILE)
+    }
+    
+    // 读取存档；版本不符或数据不一致时视为没有存档
+    pub fn load() -> Option<Self> {
+        let save: SaveGame = super::read_json(SAVE_FILE)?;
+        if save.version != SAVE_VERSION {
+            eprintln!("存档版本 {} 与当前版本 {} 不兼容，已忽略", save.version, SAVE_VERSION);
+            return None;
+        }
+        if !save.enemies.is_consistent() {
+            eprintln!("存档中的敌人数据不完整，已忽略");
+            return None;
+        }
+        Some(save)
+    }
+    
+    pub fn save(&self) -> io::Result<()> {
+        super::save_json(SAVE_FILE, self)
+    }
+    
+    // 读档后删除存档，避免同一局被反复读取
+    pub fn delete() {
+        if let Err(err) = super::remove_file(SAVE_FILE) {
+            eprintln!("无法删除存档: {}", err);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,367 @@
+use crate::game::{difficulty_label, DIFFICULTIES};
+use crate::i18n::{tr, trf, Locale, Text};
+use crate::math_challenge::{GradeLevel, MathLevel, Topic};
+use crate::systems::{Action, Bindings, ParticleQuality, ScoringRules};
+use serde::{Deserialize, Serialize};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+// 可选的答题限时（秒）和每局复活次数，0 分别表示不限时和不限次数
+pub const MATH_TIME_LIMITS: [u32; 6] = [0, 10, 15, 20, 30, 45];
+pub const MATH_REVIVES: [u32; 5] = [0, 1, 2, 3, 5];
+
+// 可选的窗口分辨率
+pub const RESOLUTIONS: [(u32, u32); 5] = [(800, 600), (1024, 768), (1280, 720), (1600, 900), (1920, 1080)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum WindowMode {
+    Windowed,
+    Fullscreen,
+}
+
+// 色盲友好配色方案
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ColorblindMode {
+    Off,
+    Deuteranopia, // 绿色弱
+    Protanopia,   // 红色弱
+    Tritanopia,   // 蓝黄色弱
+}
+
+impl ColorblindMode {
+    pub const ALL: [ColorblindMode; 4] = [
+        ColorblindMode::Off,
+        ColorblindMode::Deuteranopia,
+        ColorblindMode::Protanopia,
+        ColorblindMode::Tritanopia,
+    ];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorblindMode::Off => tr(Text::Off),
+            ColorblindMode::Deuteranopia => tr(Text::ColorDeuteranopia),
+            ColorblindMode::Protanopia => tr(Text::ColorProtanopia),
+            ColorblindMode::Tritanopia => tr(Text::ColorTritanopia),
+        }
+    }
+}
+
+// 玩家设置，保存在 settings.json 中；新增字段使用默认值，旧的配置文件仍然可以读取
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub language: Option<Locale>, // 界面语言，None 表示跟随系统
+    pub window_mode: WindowMode,
+    pub resolution: usize, // RESOLUTIONS 中的下标
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub hud_scale: f32,
+    pub colorblind: ColorblindMode,
+    pub screen_shake: bool,
+    pub particle_quality: ParticleQuality,
+    pub default_difficulty: usize, // DIFFICULTIES 中的下标
+    pub math_challenge: bool,      // 关闭后死亡直接结束本局
+    pub math_level: MathLevel,
+    pub math_grade: GradeLevel,
+    pub math_topics: Vec<Topic>, // 年级预设为 Custom 时使用的题目类型，只能手动修改配置文件
+    pub math_time_limit: u32, // 答题限时（秒），0 表示不限时
+    pub math_revives: u32,    // 每局可以复活的次数，0 表示不限
+    pub math_choices: bool,   // 以选择题形式出题，可以用鼠标、数字键或手柄作答
+    pub learning_mode: bool,  // 按学习记录出题：复习答错的题并侧重薄弱题型
+    pub supply_drops: bool, // 道具中出现数学补给箱，答对题目获得更强的效果
+    pub question_pack: Option<String>, // 数学题改为从这个题库出题，None 表示使用内置题目
+    pub bindings: Bindings,
+    pub scoring: ScoringRules, // 不在设置界面中显示，只能手动修改配置文件
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            language: None,
+            window_mode: WindowMode::Windowed,
+            resolution: 0,
+            master_volume: 0.8,
+            music_volume: 0.6,
+            sfx_volume: 0.8,
+            hud_scale: 1.0,
+            colorblind: ColorblindMode::Off,
+            screen_shake: true,
+            particle_quality: ParticleQuality::High,
+            default_difficulty: 0,
+            math_challenge: true,
+            math_level: MathLevel::Normal,
+            math_grade: GradeLevel::Grades3To4,
+            math_topics: Topic::ALL.to_vec(),
+            math_time_limit: 30,
+            math_revives: 0,
+            math_choices: false,
+            learning_mode: false,
+            supply_drops: false,
+            question_pack: None,
+            bindings: Bindings::default(),
+            scoring: ScoringRules::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub const MIN_HUD_SCALE: f32 = 0.75;
+    pub const MAX_HUD_SCALE: f32 = 2.0;
+    
+    pub fn load() -> Self {
+        let mut settings: Settings = super::load_json(SETTINGS_FILE);
+        // 防止手动修改过的文件超出取值范围
+        settings.resolution = settings.resolution.min(RESOLUTIONS.len() - 1);
+        settings.default_difficulty = settings.default_difficulty.min(DIFFICULTIES.len() - 1);
+        settings.master_volume = settings.master_volume.clamp(0.0, 1.0);
+        settings.music_volume = settings.music_volume.clamp(0.0, 1.0);
+        settings.sfx_volume = settings.sfx_volume.clamp(0.0, 1.0);
+        settings.hud_scale = settings.hud_scale.clamp(Self::MIN_HUD_SCALE, Self::MAX_HUD_SCALE);
+        settings.bindings.fill_missing();
+        settings
+    }
+    
+    pub fn save(&self) {
+        if let Err(err) = super::save_json(SETTINGS_FILE, self) {
+            eprintln!("无法保存设置: {}", err);
+        }
+    }
+    
+    pub fn window_size(&self) -> (u32, u32) {
+        RESOLUTIONS[self.resolution.min(RESOLUTIONS.len() - 1)]
+    }
+}
+
+// 设置界面中的每一行
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SettingsItem {
+    Language,
+    WindowMode,
+    Resolution,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    HudScale,
+    Colorblind,
+    ScreenShake,
+    ParticleQuality,
+    DefaultDifficulty,
+    MathChallenge,
+    MathLevel,
+    MathGrade,
+    MathTimeLimit,
+    MathRevives,
+    MathChoices,
+    LearningMode,
+    QuestionPack,
+    SupplyDrops,
+    Bind(Action),
+    ResetControls,
+}
+
+impl SettingsItem {
+    pub const GENERAL: [SettingsItem; 20] = [
+        SettingsItem::Language,
+        SettingsItem::WindowMode,
+        SettingsItem::Resolution,
+        SettingsItem::MasterVolume,
+        SettingsItem::MusicVolume,
+        SettingsItem::SfxVolume,
+        SettingsItem::HudScale,
+        SettingsItem::Colorblind,
+        SettingsItem::ScreenShake,
+        SettingsItem::ParticleQuality,
+        SettingsItem::DefaultDifficulty,
+        SettingsItem::MathChallenge,
+        SettingsItem::MathLevel,
+        SettingsItem::MathGrade,
+        SettingsItem::MathTimeLimit,
+        SettingsItem::MathRevives,
+        SettingsItem::MathChoices,
+        SettingsItem::LearningMode,
+        SettingsItem::QuestionPack,
+        SettingsItem::SupplyDrops,
+    ];
+    
+    // 设置界面中的所有行：常规设置、可重新绑定的按键和恢复默认按键
+    pub fn all() -> Vec<SettingsItem> {
+        let mut items = SettingsItem::GENERAL.to_vec();
+        items.extend(Action::REBINDABLE.iter().map(|&action| SettingsItem::Bind(action)));
+        items.push(SettingsItem::ResetControls);
+        items
+    }
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsItem::Language => tr(Text::SettingLanguage),
+            SettingsItem::WindowMode => tr(Text::SettingWindowMode),
+            SettingsItem::Resolution => tr(Text::SettingResolution),
+            SettingsItem::MasterVolume => tr(Text::SettingMasterVolume),
+            SettingsItem::MusicVolume => tr(Text::SettingMusicVolume),
+            SettingsItem::SfxVolume => tr(Text::SettingSfxVolume),
+            SettingsItem::HudScale => tr(Text::SettingHudScale),
+            SettingsItem::Colorblind => tr(Text::SettingColorblind),
+            SettingsItem::ScreenShake => tr(Text::SettingScreenShake),
+            SettingsItem::ParticleQuality => tr(Text::SettingParticleQuality),
+            SettingsItem::DefaultDifficulty => tr(Text::SettingDefaultDifficulty),
+            SettingsItem::MathChallenge => tr(Text::SettingReviveChallenge),
+            SettingsItem::MathLevel => tr(Text::SettingMathLevel),
+            SettingsItem::MathGrade => tr(Text::SettingMathTopics),
+            SettingsItem::MathTimeLimit => tr(Text::SettingMathTimeLimit),
+            SettingsItem::MathRevives => tr(Text::SettingRevives),
+            SettingsItem::MathChoices => tr(Text::SettingMultipleChoice),
+            SettingsItem::LearningMode => tr(Text::SettingLearningMode),
+            SettingsItem::QuestionPack => tr(Text::SettingQuestionPack),
+            SettingsItem::SupplyDrops => tr(Text::SettingSupplyDrops),
+            SettingsItem::Bind(action) => action.name(),
+            SettingsItem::ResetControls => tr(Text::SettingResetControls),
+        }
+    }
+    
+    pub fn value_text(&self, settings: &Settings) -> String {
+        let on_off = |value: bool| tr(if value { Text::On } else { Text::Off }).to_string();
+        match self {
+            SettingsItem::Language => match settings.language {
+                Some(locale) => locale.name().to_string(),
+                None => trf(Text::LanguageSystem, &[&Locale::from_env().name()]),
+            },
+            SettingsItem::WindowMode => match settings.window_mode {
+                WindowMode::Windowed => tr(Text::Windowed).to_string(),
+                WindowMode::Fullscreen => tr(Text::Fullscreen).to_string(),
+            },
+            SettingsItem::Resolution => {
+                let (w, h) = settings.window_size();
+                format!("{}x{}", w, h)
+            }
+            SettingsItem::MasterVolume => format!("{:.0}%", settings.master_volume * 100.0),
+            SettingsItem::MusicVolume => format!("{:.0}%", settings.music_volume * 100.0),
+            SettingsItem::SfxVolume => format!("{:.0}%", settings.sfx_volume * 100.0),
+            SettingsItem::HudScale => format!("{:.0}%", settings.hud_scale * 100.0),
+            SettingsItem::Colorblind => settings.colorblind.name().to_string(),
+            SettingsItem::ScreenShake => on_off(settings.screen_shake),
+            SettingsItem::ParticleQuality => settings.particle_quality.name().to_string(),
+            SettingsItem::DefaultDifficulty => difficulty_label(settings.default_difficulty).to_string(),
+            SettingsItem::MathChallenge => on_off(settings.math_challenge),
+            SettingsItem::MathLevel => settings.math_level.name().to_string(),
+            SettingsItem::MathGrade => settings.math_grade.name().to_string(),
+            SettingsItem::MathTimeLimit => match settings.math_time_limit {
+                0 => tr(Text::Off).to_string(),
+                seconds => trf(Text::Seconds, &[&seconds]),
+            },
+            SettingsItem::MathRevives => match settings.math_revives {
+                0 => tr(Text::Unlimited).to_string(),
+                revives => revives.to_string(),
+            },
+            SettingsItem::MathChoices => on_off(settings.math_choices),
+            SettingsItem::LearningMode => on_off(settings.learning_mode),
+            SettingsItem::SupplyDrops => on_off(settings.supply_drops),
+            SettingsItem::QuestionPack => settings.question_pack.clone().unwrap_or_else(|| tr(Text::BuiltIn).to_string()),
+            SettingsItem::Bind(action) => settings.bindings.describe(*action),
+            SettingsItem::ResetControls => String::new(),
+        }
+    }
+    
+    // 滑块类设置项的当前比例（0.0 ~ 1.0），其他设置项返回 None
+    pub fn slider_value(&self, settings: &Settings) -> Option<f32> {
+        match self {
+            SettingsItem::MasterVolume => Some(settings.master_volume),
+            SettingsItem::MusicVolume => Some(settings.music_volume),
+            SettingsItem::SfxVolume => Some(settings.sfx_volume),
+            SettingsItem::HudScale => Some(
+                (settings.hud_scale - Settings::MIN_HUD_SCALE) / (Settings::MAX_HUD_SCALE - Settings::MIN_HUD_SCALE),
+            ),
+            _ => None,
+        }
+    }
+    
+    // 向左（-1）或向右（+1）调整设置项
+    pub fn adjust(&self, settings: &mut Settings, direction: i32) {
+        let step_volume = |value: f32| ((value + direction as f32 * 0.1) * 10.0).round() / 10.0;
+        let cycle = |index: usize, len: usize| (index as i32 + direction).rem_euclid(len as i32) as usize;
+        
+        match self {
+            SettingsItem::Language => {
+                // 依次为跟随系统和各种语言
+                let options: Vec<Option<Locale>> =
+                    std::iter::once(None).chain(Locale::ALL.iter().copied().map(Some)).collect();
+                let index = options.iter().position(|l| *l == settings.language).unwrap_or(0);
+                settings.language = options[cycle(index, options.len())];
+            }
+            SettingsItem::WindowMode => {
+                settings.window_mode = match settings.window_mode {
+                    WindowMode::Windowed => WindowMode::Fullscreen,
+                    WindowMode::Fullscreen => WindowMode::Windowed,
+                };
+            }
+            SettingsItem::Resolution => {
+                settings.resolution = cycle(settings.resolution, RESOLUTIONS.len());
+            }
+            SettingsItem::MasterVolume => {
+                settings.master_volume = step_volume(settings.master_volume).clamp(0.0, 1.0);
+            }
+            SettingsItem::MusicVolume => {
+                settings.music_volume = step_volume(settings.music_volume).clamp(0.0, 1.0);
+            }
+            SettingsItem::SfxVolume => {
+                settings.sfx_volume = step_volume(settings.sfx_volume).clamp(0.0, 1.0);
+            }
+            SettingsItem::HudScale => {
+                settings.hud_scale = (settings.hud_scale + direction as f32 * 0.25)
+                    .clamp(Settings::MIN_HUD_SCALE, Settings::MAX_HUD_SCALE);
+            }
+            SettingsItem::Colorblind => {
+                let index = ColorblindMode::ALL.iter().position(|m| *m == settings.colorblind).unwrap_or(0);
+                settings.colorblind = ColorblindMode::ALL[cycle(index, ColorblindMode::ALL.len())];
+            }
+            SettingsItem::ScreenShake => {
+                settings.screen_shake = !settings.screen_shake;
+            }
+            SettingsItem::ParticleQuality => {
+                let index = ParticleQuality::ALL.iter().position(|q| *q == settings.particle_quality).unwrap_or(0);
+                settings.particle_quality = ParticleQuality::ALL[cycle(index, ParticleQuality::ALL.len())];
+            }
+            SettingsItem::DefaultDifficulty => {
+                settings.default_difficulty = cycle(settings.default_difficulty, DIFFICULTIES.len());
+            }
+            SettingsItem::MathChallenge => {
+                settings.math_challenge = !settings.math_challenge;
+            }
+            SettingsItem::MathLevel => {
+                let index = MathLevel::ALL.iter().position(|l| *l == settings.math_level).unwrap_or(0);
+                settings.math_level = MathLevel::ALL[cycle(index, MathLevel::ALL.len())];
+            }
+            SettingsItem::MathGrade => {
+                let index = GradeLevel::ALL.iter().position(|g| *g == settings.math_grade).unwrap_or(0);
+                settings.math_grade = GradeLevel::ALL[cycle(index, GradeLevel::ALL.len())];
+            }
+            SettingsItem::MathTimeLimit => {
+                let index = MATH_TIME_LIMITS.iter().position(|t| *t == settings.math_time_limit).unwrap_or(0);
+                settings.math_time_limit = MATH_TIME_LIMITS[cycle(index, MATH_TIME_LIMITS.len())];
+            }
+            SettingsItem::MathRevives => {
+                let index = MATH_REVIVES.iter().position(|r| *r == settings.math_revives).unwrap_or(0);
+                settings.math_revives = MATH_REVIVES[cycle(index, MATH_REVIVES.len())];
+            }
+            SettingsItem::MathChoices => {
+                settings.math_choices = !settings.math_choices;
+            }
+            SettingsItem::LearningMode => {
+                settings.learning_mode = !settings.learning_mode;
+            }
+            SettingsItem::SupplyDrops => {
+                settings.supply_drops = !settings.supply_drops;
+            }
+            // 按键绑定需要等待玩家按下新按键，题库列表需要读取文件，都由设置界面处理
+            SettingsItem::Bind(_) | SettingsItem::QuestionPack => {}
+            SettingsItem::ResetControls => {
+                settings.bindings = Bindings::default();
+            }
+        }
+    }
+    
+    // 修改后需要重新设置窗口的设置项
+    pub fn affects_window(&self) -> bool {
+        matches!(self, SettingsItem::WindowMode | SettingsItem::Resolution)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,239 @@
+use super::GameEvent;
+use crate::i18n::locale;
+use crate::storage::{current_date, AchievementProgress};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// 成就定义随程序一起编译，修改数据文件即可增删成就
+const DEFINITIONS: &str = include_str!("../../assets/achievements.json");
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub goal: Goal,
+}
+
+// 成就的解锁条件
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Goal {
+    TotalKills { count: u32 },                  // 累计击毁坦克数
+    ClearWave { wave: i32 },                    // 清除指定波次
+    ClearWaveWithoutPowerUp { wave: i32 },      // 本局未拾取道具时清除指定波次
+    ClearWaveWithoutTouchingWall { wave: i32 }, // 本局未碰到墙时清除指定波次
+    VolleyKills { count: u32 },                 // 一次齐射击毁的坦克数
+    MathStreak { count: u32 },                  // 连续答对数学题
+    RunScore { score: i32 },                    // 单局得分
+}
+
+impl Achievement {
+    // 当前语言的名称和说明，没有翻译时使用定义文件中的英文
+    pub fn title(&self) -> &str {
+        locale().achievement(&self.id).map_or(self.name.as_str(), |(name, _)| name)
+    }
+    
+    pub fn summary(&self) -> &str {
+        locale().achievement(&self.id).map_or(self.description.as_str(), |(_, description)| description)
+    }
+}
+
+pub fn load_definitions() -> Vec<Achievement> {
+    serde_json::from_str(DEFINITIONS).expect("assets/achievements.json 格式错误")
+}
+
+// 只在一局之内有效的成就状态，随存档一起保存
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AchievementRun {
+    pub powerup_collected: bool,
+    pub touched_wall: bool,
+    // 每次齐射的击杀数；齐射编号只在一波之内有意义，每波开始时清空
+    #[serde(skip)]
+    pub volley_kills: HashMap<u32, u32>,
+}
+
+pub struct AchievementTracker {
+    pub definitions: Vec<Achievement>,
+    pub progress: AchievementProgress,
+    pub run: AchievementRun,
+}
+
+impl AchievementTracker {
+    pub fn new(progress: AchievementProgress) -> Self {
+        Self {
+            definitions: load_definitions(),
+            progress,
+            run: AchievementRun::default(),
+        }
+    }
+    
+    pub fn reset_run(&mut self) {
+        self.run = AchievementRun::default();
+    }
+    
+    // 处理一个游戏事件，返回因此新解锁的成就；由调用方决定何时保存进度
+    pub fn handle_event(&mut self, event: &GameEvent) -> Vec<Achievement> {
+        match event {
+            GameEvent::TankDestroyed { volley, by_player: true, .. } => {
+                self.progress.total_kills += 1;
+                if let Some(volley) = volley {
+                    *self.run.volley_kills.entry(*volley).or_insert(0) += 1;
+                }
+            }
+            GameEvent::PowerUpCollected { .. } => self.run.powerup_collected = true,
+            GameEvent::PlayerBlocked => self.run.touched_wall = true,
+            GameEvent::WaveStarted { .. } => self.run.volley_kills.clear(),
+            GameEvent::ChallengeAnswered { correct } => {
+                self.progress.math_streak = if *correct { self.progress.math_streak + 1 } else { 0 };
+            }
+            _ => {}
+        }
+        
+        let unlocked: Vec<Achievement> = self
+            .definitions
+            .iter()
+            .filter(|achievement| !self.progress.is_unlocked(&achievement.id))
+            .filter(|achievement| self.is_met(achievement.goal, event))
+            .cloned()
+            .collect();
+        for achievement in &unlocked {
+            self.progress.unlocked.insert(achievement.id.clone(), current_date());
+        }
+        unlocked
+    }
+    
+    fn is_met(&self, goal: Goal, event: &GameEvent) -> bool {
+        match (goal, event) {
+            (Goal::TotalKills { count }, _) => self.progress.total_kills >= count,
+            (Goal::MathStreak { count }, _) => self.progress.math_streak >= count,
+            (Goal::ClearWave { wave }, GameEvent::WaveCleared { wave: cleared }) => *cleared >= wave,
+            (Goal::ClearWaveWithoutPowerUp { wave }, GameEvent::WaveCleared { wave: cleared }) => {
+                *cleared >= wave && !self.run.powerup_collected
+            }
+            (Goal::ClearWaveWithoutTouchingWall { wave }, GameEvent::WaveCleared { wave: cleared }) => {
+                *cleared >= wave && !self.run.touched_wall
+            }
+            (Goal::VolleyKills { count }, GameEvent::TankDestroyed { volley: Some(volley), by_player: true, .. }) => {
+                self.run.volley_kills.get(volley).copied().unwrap_or(0) >= count
+            }
+            (Goal::RunScore { score }, GameEvent::RunEnded { score: final_score }) => *final_score >= score,
+            _ => false,
+        }
+    }
+    
+    // 有累计进度的成就返回 (当前, 目标)，用于成就界面的进度条
+    pub fn progress_of(&self, goal: Goal) -> Option<(u32, u32)> {
+        match goal {
+            Goal::TotalKills { count } => Some((self.progress.total_kills.min(count), count)),
+            Goal::MathStreak { count } => Some((self.progress.math_streak.min(count), count)),
+            _ => None,
+        }
+    }
+    
+    pub fn unlocked_count(&self) -> usize {
+        self.definitions
+            .iter()
+            .filter(|achievement| self.progress.is_unlocked(&achievement.id))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Arena, Position, PowerUpType, Weapon};
+    use crate::i18n::Locale;
+    
+    fn tracker() -> AchievementTracker {
+        AchievementTracker::new(AchievementProgress::default())
+    }
+    
+    fn kill(volley: Option<u32>) -> GameEvent {
+        GameEvent::TankDestroyed {
+            id: Arena::new().insert(()),
+            position: Position::new(0.0, 0.0),
+            weapon: Weapon::Cannon,
+            volley,
+            by_player: true,
+        }
+    }
+    
+    fn unlocked_ids(unlocked: Vec<Achievement>) -> Vec<String> {
+        unlocked.into_iter().map(|achievement| achievement.id).collect()
+    }
+    
+    #[test]
+    fn definitions_parse_with_unique_ids() {
+        let definitions = load_definitions();
+        assert!(!definitions.is_empty());
+        let mut ids: Vec<&str> = definitions.iter().map(|achievement| achievement.id.as_str()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), definitions.len());
+    }
+    
+    #[test]
+    fn every_achievement_is_translated() {
+        for achievement in load_definitions() {
+            for locale in Locale::ALL {
+                if locale != Locale::En {
+                    assert!(locale.achievement(&achievement.id).is_some(), "{:?} {}", locale, achievement.id);
+                }
+            }
+        }
+    }
+    
+    #[test]
+    fn volley_kills_are_counted_per_volley() {
+        let mut tracker = tracker();
+        tracker.progress.unlocked.insert("first_blood".to_string(), String::new());
+        assert!(tracker.handle_event(&kill(Some(1))).is_empty());
+        assert!(tracker.handle_event(&kill(Some(2))).is_empty());
+        assert_eq!(unlocked_ids(tracker.handle_event(&kill(Some(2)))), ["two_birds"]);
+        assert_eq!(unlocked_ids(tracker.handle_event(&kill(Some(2)))), ["buckshot"]);
+    }
+    
+    #[test]
+    fn kills_by_enemy_explosions_do_not_count() {
+        let mut tracker = tracker();
+        let enemy_kill = GameEvent::TankDestroyed {
+            id: Arena::new().insert(()),
+            position: Position::new(0.0, 0.0),
+            weapon: Weapon::Cannon,
+            volley: None,
+            by_player: false,
+        };
+        assert!(tracker.handle_event(&enemy_kill).is_empty());
+        assert_eq!(tracker.progress.total_kills, 0);
+    }
+    
+    #[test]
+    fn power_up_blocks_purist() {
+        let mut tracker = tracker();
+        tracker.handle_event(&GameEvent::PowerUpCollected {
+            power_type: PowerUpType::Health,
+            position: Position::new(0.0, 0.0),
+        });
+        let unlocked = unlocked_ids(tracker.handle_event(&GameEvent::WaveCleared { wave: 10 }));
+        assert!(unlocked.contains(&"holding_the_line".to_string()));
+        assert!(unlocked.contains(&"ghost_driver".to_string()));
+        assert!(!unlocked.contains(&"purist".to_string()));
+    }
+    
+    #[test]
+    fn wrong_answer_resets_math_streak() {
+        let mut tracker = tracker();
+        for _ in 0..4 {
+            tracker.handle_event(&GameEvent::ChallengeAnswered { correct: true });
+        }
+        tracker.handle_event(&GameEvent::ChallengeAnswered { correct: false });
+        assert_eq!(tracker.progress.math_streak, 0);
+        for _ in 0..4 {
+            tracker.handle_event(&GameEvent::ChallengeAnswered { correct: true });
+        }
+        let unlocked = tracker.handle_event(&GameEvent::ChallengeAnswered { correct: true });
+        assert_eq!(unlocked_ids(unlocked), ["mathlete"]);
+    }
+}
\ No newline at end of file
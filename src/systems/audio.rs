@@ -0,0 +1,336 @@
+use super::GameEvent;
+use crate::entities::{Position, Weapon};
+
+#[cfg(feature = "audio")]
+use super::synth;
+#[cfg(feature = "audio")]
+use macroquad::audio::{load_sound_from_bytes, play_sound, set_sound_volume, stop_sound, PlaySoundParams, Sound};
+#[cfg(feature = "audio")]
+use std::collections::HashMap;
+
+// 声像分为几个固定档位，每个档位预先生成一份立体声音效（macroquad 不支持实时声像）
+const PAN_STEPS: usize = 5;
+// 声源偏离玩家多远时完全偏向一侧
+const PAN_DISTANCE: f32 = 400.0;
+// 距离衰减：在这个距离上音量减半
+const HALF_VOLUME_DISTANCE: f32 = 350.0;
+// 超过这个距离的声音直接忽略
+const MAX_HEARING_DISTANCE: f32 = 1200.0;
+// 音量低于这个值时不再播放
+const MIN_VOLUME: f32 = 0.03;
+// 清波音效之后多久播放下一波开始的音效
+const WAVE_START_DELAY: f32 = 1.2;
+// 背景音乐在平静和紧张之间切换的速度
+const MUSIC_FADE_SPEED: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    Cannon,
+    Mortar,
+    MineDrop,
+    Hit,
+    WallBreak,
+    Explosion,
+    PowerUp,
+    WaveStart,
+    WaveClear,
+    MathPrompt,
+    GameOver,
+}
+
+impl SoundEffect {
+    #[cfg(feature = "audio")]
+    const ALL: [SoundEffect; 11] = [
+        SoundEffect::Cannon,
+        SoundEffect::Mortar,
+        SoundEffect::MineDrop,
+        SoundEffect::Hit,
+        SoundEffect::WallBreak,
+        SoundEffect::Explosion,
+        SoundEffect::PowerUp,
+        SoundEffect::WaveStart,
+        SoundEffect::WaveClear,
+        SoundEffect::MathPrompt,
+        SoundEffect::GameOver,
+    ];
+    
+    // 界面类音效不随位置变化，只生成居中的一份
+    #[cfg(feature = "audio")]
+    fn is_positional(&self) -> bool {
+        !matches!(
+            self,
+            SoundEffect::WaveStart | SoundEffect::WaveClear | SoundEffect::MathPrompt | SoundEffect::GameOver
+        )
+    }
+    
+    // 各音效之间的相对音量
+    fn gain(&self) -> f32 {
+        match self {
+            SoundEffect::Cannon => 0.45,
+            SoundEffect::Mortar => 0.7,
+            SoundEffect::MineDrop => 0.5,
+            SoundEffect::Hit => 0.5,
+            SoundEffect::WallBreak => 0.7,
+            SoundEffect::Explosion => 0.9,
+            SoundEffect::PowerUp => 0.7,
+            SoundEffect::WaveStart | SoundEffect::WaveClear => 0.8,
+            SoundEffect::MathPrompt | SoundEffect::GameOver => 0.9,
+        }
+    }
+}
+
+// 已加载的音效和音乐
+#[cfg(feature = "audio")]
+struct SoundBank {
+    effects: HashMap<(SoundEffect, usize), Sound>,
+    calm_music: Sound,
+    intense_music: Sound,
+}
+
+// 音频系统：把声音事件转换成带声像和距离衰减的音效，并根据战斗激烈程度混合两段背景音乐。
+// 未启用 audio 特性、没有音频设备或加载失败时保持静音，游戏照常运行
+pub struct AudioSystem {
+    #[cfg(feature = "audio")]
+    bank: Option<SoundBank>,
+    master_volume: f32,
+    music_volume: f32,
+    sfx_volume: f32,
+    music_playing: bool,
+    music_mix: f32, // 0.0 为平静音乐，1.0 为紧张音乐
+    delayed: Vec<(f32, SoundEffect)>,
+    played: Vec<(SoundEffect, usize)>, // 本帧已经播放的音效和声像档位
+}
+
+impl AudioSystem {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "audio")]
+            bank: None,
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            music_playing: false,
+            music_mix: 0.0,
+            delayed: Vec::new(),
+            played: Vec::new(),
+        }
+    }
+    
+    // 生成并加载所有音效，启动时调用一次
+    #[cfg(feature = "audio")]
+    pub async fn load(&mut self) {
+        if !device_available() {
+            eprintln!("没有找到音频设备，游戏将保持静音");
+            return;
+        }
+        
+        let mut effects = HashMap::new();
+        for effect in SoundEffect::ALL {
+            let samples = synth::effect(effect);
+            let buckets: Vec<usize> = if effect.is_positional() {
+                (0..PAN_STEPS).collect()
+            } else {
+                vec![PAN_STEPS / 2]
+            };
+            for bucket in buckets {
+                let Some(sound) = load_wav(&synth::encode_wav(&samples, bucket_pan(bucket))).await else {
+                    return;
+                };
+                effects.insert((effect, bucket), sound);
+            }
+        }
+        
+        let Some(calm_music) = load_wav(&synth::encode_wav(&synth::calm_music(), 0.0)).await else {
+            return;
+        };
+        let Some(intense_music) = load_wav(&synth::encode_wav(&synth::intense_music(), 0.0)).await else {
+            return;
+        };
+        self.bank = Some(SoundBank { effects, calm_music, intense_music });
+    }
+    
+    #[cfg(not(feature = "audio"))]
+    pub async fn load(&mut self) {}
+    
+    pub fn set_volumes(&mut self, master: f32, music: f32, sfx: f32) {
+        self.master_volume = master;
+        self.music_volume = music;
+        self.sfx_volume = sfx;
+        self.apply_music_volume();
+    }
+    
+    // 把游戏事件转换成音效；listener 为玩家位置，用于计算声像和距离衰减
+    pub fn handle_event(&mut self, event: &GameEvent, listener: Position) {
+        let (effect, position, volume) = match event {
+            GameEvent::ShotFired { weapon, position, .. } => {
+                let effect = match weapon {
+                    Weapon::Cannon | Weapon::HeavyCannon | Weapon::TwinCannon => SoundEffect::Cannon,
+                    Weapon::Mortar => SoundEffect::Mortar,
+                    Weapon::MineLayer => SoundEffect::MineDrop,
+                };
+                (effect, Some(*position), 1.0)
+            }
+            GameEvent::BulletHit { position, .. } => (SoundEffect::Hit, Some(*position), 1.0),
+            GameEvent::ObstacleDestroyed { position, .. } => (SoundEffect::WallBreak, Some(*position), 1.0),
+            // 爆炸越大声音越响
+            GameEvent::ExplosionStarted { position, radius, .. } => {
+                (SoundEffect::Explosion, Some(*position), (radius / 100.0).clamp(0.5, 1.0))
+            }
+            GameEvent::PowerUpCollected { position, .. } => (SoundEffect::PowerUp, Some(*position), 1.0),
+            // 清波之后的新一波等清波音效播放完再提示
+            GameEvent::WaveStarted { wave } if *wave > 1 => {
+                self.delayed.push((WAVE_START_DELAY, SoundEffect::WaveStart));
+                return;
+            }
+            GameEvent::WaveStarted { .. } => (SoundEffect::WaveStart, None, 1.0),
+            GameEvent::WaveCleared { .. } => (SoundEffect::WaveClear, None, 1.0),
+            GameEvent::ChallengeStarted => (SoundEffect::MathPrompt, None, 1.0),
+            GameEvent::RunEnded { .. } => {
+                self.delayed.clear();
+                (SoundEffect::GameOver, None, 1.0)
+            }
+            _ => return,
+        };
+        
+        let (bucket, attenuation) = match position {
+            Some(position) => spatialize(position, listener),
+            None => (PAN_STEPS / 2, 1.0),
+        };
+        let volume = volume * attenuation * effect.gain() * self.sfx_volume * self.master_volume;
+        // 同一帧中同一音效在同一声像档位只播放一次，避免大量敌人同时开火时音量叠加
+        if volume < MIN_VOLUME || self.played.contains(&(effect, bucket)) {
+            return;
+        }
+        self.played.push((effect, bucket));
+        self.play(effect, bucket, volume);
+    }
+    
+    // 每个渲染帧调用一次：播放到期的延迟音效
+    pub fn update(&mut self, dt: f32) {
+        self.played.clear();
+        
+        let mut due = Vec::new();
+        self.delayed.retain_mut(|(delay, effect)| {
+            *delay -= dt;
+            if *delay <= 0.0 {
+                due.push(*effect);
+            }
+            *delay > 0.0
+        });
+        for effect in due {
+            self.play(effect, PAN_STEPS / 2, effect.gain() * self.sfx_volume * self.master_volume);
+        }
+    }
+    
+    // 更新背景音乐；intensity 为 0.0（平静）到 1.0（激烈），两段音乐平滑交叉淡入淡出
+    pub fn update_music(&mut self, active: bool, intensity: f32, dt: f32) {
+        if active != self.music_playing {
+            self.music_playing = active;
+            if active {
+                self.music_mix = intensity;
+                self.start_music();
+            } else {
+                self.stop_music();
+            }
+        }
+        if !active {
+            return;
+        }
+        
+        let step = MUSIC_FADE_SPEED * dt;
+        self.music_mix += (intensity.clamp(0.0, 1.0) - self.music_mix).clamp(-step, step);
+        self.apply_music_volume();
+    }
+    
+    #[cfg(feature = "audio")]
+    fn music_volumes(&self) -> (f32, f32) {
+        let volume = self.music_volume * self.master_volume;
+        (volume * (1.0 - self.music_mix), volume * self.music_mix)
+    }
+    
+    #[cfg(feature = "audio")]
+    fn play(&self, effect: SoundEffect, bucket: usize, volume: f32) {
+        if let Some(sound) = self.bank.as_ref().and_then(|bank| bank.effects.get(&(effect, bucket))) {
+            play_sound(sound, PlaySoundParams { looped: false, volume });
+        }
+    }
+    
+    #[cfg(not(feature = "audio"))]
+    fn play(&self, _effect: SoundEffect, _bucket: usize, _volume: f32) {}
+    
+    // 两段音乐长度相同并且同时开始，交叉淡入淡出时节拍保持一致
+    #[cfg(feature = "audio")]
+    fn start_music(&self) {
+        if let Some(bank) = &self.bank {
+            let (calm, intense) = self.music_volumes();
+            play_sound(&bank.calm_music, PlaySoundParams { looped: true, volume: calm });
+            play_sound(&bank.intense_music, PlaySoundParams { looped: true, volume: intense });
+        }
+    }
+    
+    #[cfg(not(feature = "audio"))]
+    fn start_music(&self) {}
+    
+    #[cfg(feature = "audio")]
+    fn stop_music(&self) {
+        if let Some(bank) = &self.bank {
+            stop_sound(&bank.calm_music);
+            stop_sound(&bank.intense_music);
+        }
+    }
+    
+    #[cfg(not(feature = "audio"))]
+    fn stop_music(&self) {}
+    
+    #[cfg(feature = "audio")]
+    fn apply_music_volume(&self) {
+        if let (Some(bank), true) = (&self.bank, self.music_playing) {
+            let (calm, intense) = self.music_volumes();
+            set_sound_volume(&bank.calm_music, calm);
+            set_sound_volume(&bank.intense_music, intense);
+        }
+    }
+    
+    #[cfg(not(feature = "audio"))]
+    fn apply_music_volume(&self) {}
+}
+
+// 根据声源相对玩家的位置计算声像档位和距离衰减
+fn spatialize(position: Position, listener: Position) -> (usize, f32) {
+    let distance = position.distance_to(&listener);
+    if distance > MAX_HEARING_DISTANCE {
+        return (PAN_STEPS / 2, 0.0);
+    }
+    let ratio = distance / HALF_VOLUME_DISTANCE;
+    let attenuation = 1.0 / (1.0 + ratio * ratio);
+    
+    let pan = ((position.x - listener.x) / PAN_DISTANCE).clamp(-1.0, 1.0);
+    let bucket = ((pan + 1.0) / 2.0 * (PAN_STEPS - 1) as f32).round() as usize;
+    (bucket, attenuation)
+}
+
+#[cfg(feature = "audio")]
+fn bucket_pan(bucket: usize) -> f32 {
+    bucket as f32 / (PAN_STEPS - 1) as f32 * 2.0 - 1.0
+}
+
+#[cfg(feature = "audio")]
+async fn load_wav(bytes: &[u8]) -> Option<Sound> {
+    match load_sound_from_bytes(bytes).await {
+        Ok(sound) => Some(sound),
+        Err(err) => {
+            eprintln!("无法加载音效: {}", err);
+            None
+        }
+    }
+}
+
+// Linux 上没有声卡设备时 macroquad 的音频线程无法启动，此时跳过加载，避免每次播放都报错
+#[cfg(feature = "audio")]
+fn device_available() -> bool {
+    if cfg!(target_os = "linux") {
+        std::path::Path::new("/dev/snd").exists()
+    } else {
+        true
+    }
+}
\ No newline at end of file
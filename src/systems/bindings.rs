@@ -0,0 +1,309 @@
+use crate::i18n::{tr, trf, Text};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+// 玩家可以触发的动作，按键和手柄按钮都先映射到动作再交给游戏逻辑
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Fire,
+    NextWeapon,
+    Pause,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    Confirm,
+    Back,
+}
+
+impl Action {
+    // 可以在设置界面中重新绑定的动作（菜单操作保持默认，避免玩家把自己锁在菜单外）
+    pub const REBINDABLE: [Action; 7] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Fire,
+        Action::NextWeapon,
+        Action::Pause,
+    ];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveUp => tr(Text::ActionMoveUp),
+            Action::MoveDown => tr(Text::ActionMoveDown),
+            Action::MoveLeft => tr(Text::ActionMoveLeft),
+            Action::MoveRight => tr(Text::ActionMoveRight),
+            Action::Fire => tr(Text::ActionFire),
+            Action::NextWeapon => tr(Text::ActionNextWeapon),
+            Action::Pause => tr(Text::ActionPause),
+            Action::MenuUp => tr(Text::ActionMenuUp),
+            Action::MenuDown => tr(Text::ActionMenuDown),
+            Action::MenuLeft => tr(Text::ActionMenuLeft),
+            Action::MenuRight => tr(Text::ActionMenuRight),
+            Action::Confirm => tr(Text::ActionConfirm),
+            Action::Back => tr(Text::ActionBack),
+        }
+    }
+}
+
+// 手柄按钮（按 Xbox 布局的位置命名，与具体手柄型号无关）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PadButton {
+    South, // A / ×
+    East,  // B / ○
+    West,  // X / □
+    North, // Y / △
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl PadButton {
+    pub const ALL: [PadButton; 14] = [
+        PadButton::South,
+        PadButton::East,
+        PadButton::West,
+        PadButton::North,
+        PadButton::LeftBumper,
+        PadButton::RightBumper,
+        PadButton::LeftTrigger,
+        PadButton::RightTrigger,
+        PadButton::Select,
+        PadButton::Start,
+        PadButton::DPadUp,
+        PadButton::DPadDown,
+        PadButton::DPadLeft,
+        PadButton::DPadRight,
+    ];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            PadButton::South => "South",
+            PadButton::East => "East",
+            PadButton::West => "West",
+            PadButton::North => "North",
+            PadButton::LeftBumper => "LB",
+            PadButton::RightBumper => "RB",
+            PadButton::LeftTrigger => "LT",
+            PadButton::RightTrigger => "RT",
+            PadButton::Select => "Select",
+            PadButton::Start => "Start",
+            PadButton::DPadUp => "DPadUp",
+            PadButton::DPadDown => "DPadDown",
+            PadButton::DPadLeft => "DPadLeft",
+            PadButton::DPadRight => "DPadRight",
+        }
+    }
+}
+
+// 可以绑定的按键及其在配置文件中的名称
+const KEY_NAMES: [(KeyCode, &str); 74] = [
+    (KeyCode::A, "A"), (KeyCode::B, "B"), (KeyCode::C, "C"), (KeyCode::D, "D"),
+    (KeyCode::E, "E"), (KeyCode::F, "F"), (KeyCode::G, "G"), (KeyCode::H, "H"),
+    (KeyCode::I, "I"), (KeyCode::J, "J"), (KeyCode::K, "K"), (KeyCode::L, "L"),
+    (KeyCode::M, "M"), (KeyCode::N, "N"), (KeyCode::O, "O"), (KeyCode::P, "P"),
+    (KeyCode::Q, "Q"), (KeyCode::R, "R"), (KeyCode::S, "S"), (KeyCode::T, "T"),
+    (KeyCode::U, "U"), (KeyCode::V, "V"), (KeyCode::W, "W"), (KeyCode::X, "X"),
+    (KeyCode::Y, "Y"), (KeyCode::Z, "Z"),
+    (KeyCode::Key0, "0"), (KeyCode::Key1, "1"), (KeyCode::Key2, "2"), (KeyCode::Key3, "3"),
+    (KeyCode::Key4, "4"), (KeyCode::Key5, "5"), (KeyCode::Key6, "6"), (KeyCode::Key7, "7"),
+    (KeyCode::Key8, "8"), (KeyCode::Key9, "9"),
+    (KeyCode::Up, "Up"), (KeyCode::Down, "Down"), (KeyCode::Left, "Left"), (KeyCode::Right, "Right"),
+    (KeyCode::Space, "Space"), (KeyCode::Enter, "Enter"), (KeyCode::Escape, "Escape"),
+    (KeyCode::Tab, "Tab"), (KeyCode::Backspace, "Backspace"),
+    (KeyCode::LeftShift, "LeftShift"), (KeyCode::RightShift, "RightShift"),
+    (KeyCode::LeftControl, "LeftControl"), (KeyCode::RightControl, "RightControl"),
+    (KeyCode::LeftAlt, "LeftAlt"), (KeyCode::RightAlt, "RightAlt"),
+    (KeyCode::F1, "F1"), (KeyCode::F2, "F2"), (KeyCode::F3, "F3"), (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"), (KeyCode::F6, "F6"), (KeyCode::F7, "F7"), (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"), (KeyCode::F10, "F10"), (KeyCode::F11, "F11"), (KeyCode::F12, "F12"),
+    (KeyCode::Kp0, "Kp0"), (KeyCode::Kp1, "Kp1"), (KeyCode::Kp2, "Kp2"), (KeyCode::Kp3, "Kp3"),
+    (KeyCode::Kp4, "Kp4"), (KeyCode::Kp5, "Kp5"), (KeyCode::Kp6, "Kp6"), (KeyCode::Kp7, "Kp7"),
+    (KeyCode::Kp8, "Kp8"), (KeyCode::Kp9, "Kp9"), (KeyCode::KpEnter, "KpEnter"),
+];
+
+const MOUSE_NAMES: [(MouseButton, &str); 3] = [
+    (MouseButton::Left, "Left"),
+    (MouseButton::Right, "Right"),
+    (MouseButton::Middle, "Middle"),
+];
+
+// 一个具体的输入：键盘按键、鼠标按键或手柄按钮
+// 配置文件中保存为 "Key:W"、"Mouse:Left"、"Pad:South" 形式的字符串
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Pad(PadButton),
+}
+
+impl Binding {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::Pad(_))
+    }
+    
+    // 界面中显示的名称，例如 "W"、"Mouse Left"、"DPadUp"
+    pub fn short_name(&self) -> String {
+        match self {
+            Binding::Key(_) => self.to_string().trim_start_matches("Key:").to_string(),
+            Binding::Mouse(_) => self.to_string().replace("Mouse:", "Mouse "),
+            Binding::Pad(button) => button.name().to_string(),
+        }
+    }
+    
+    // 是否可以保存到配置文件（不在按键表中的按键无法绑定）
+    pub fn is_bindable(&self) -> bool {
+        match self {
+            Binding::Key(key) => KEY_NAMES.iter().any(|(k, _)| k == key),
+            Binding::Mouse(button) => MOUSE_NAMES.iter().any(|(b, _)| b == button),
+            Binding::Pad(_) => true,
+        }
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(key) => {
+                let name = KEY_NAMES.iter().find(|(k, _)| k == key).map(|(_, n)| *n).unwrap_or("?");
+                write!(f, "Key:{}", name)
+            }
+            Binding::Mouse(button) => {
+                let name = MOUSE_NAMES.iter().find(|(b, _)| b == button).map(|(_, n)| *n).unwrap_or("?");
+                write!(f, "Mouse:{}", name)
+            }
+            Binding::Pad(button) => write!(f, "Pad:{}", button.name()),
+        }
+    }
+}
+
+impl From<Binding> for String {
+    fn from(binding: Binding) -> String {
+        binding.to_string()
+    }
+}
+
+impl TryFrom<String> for Binding {
+    type Error = String;
+    
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let (device, name) = value.split_once(':').ok_or_else(|| format!("无效的按键绑定: {}", value))?;
+        let binding = match device {
+            "Key" => KEY_NAMES.iter().find(|(_, n)| *n == name).map(|(k, _)| Binding::Key(*k)),
+            "Mouse" => MOUSE_NAMES.iter().find(|(_, n)| *n == name).map(|(b, _)| Binding::Mouse(*b)),
+            "Pad" => PadButton::ALL.iter().find(|b| b.name() == name).map(|b| Binding::Pad(*b)),
+            _ => None,
+        };
+        binding.ok_or_else(|| format!("未知的按键: {}", value))
+    }
+}
+
+// 动作到输入的映射，保存在设置文件中
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Bindings {
+    pub actions: BTreeMap<Action, Vec<Binding>>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        use Binding::{Key, Mouse, Pad};
+        
+        let defaults = [
+            (Action::MoveUp, vec![Key(KeyCode::W), Key(KeyCode::Up), Pad(PadButton::DPadUp)]),
+            (Action::MoveDown, vec![Key(KeyCode::S), Key(KeyCode::Down), Pad(PadButton::DPadDown)]),
+            (Action::MoveLeft, vec![Key(KeyCode::A), Key(KeyCode::Left), Pad(PadButton::DPadLeft)]),
+            (Action::MoveRight, vec![Key(KeyCode::D), Key(KeyCode::Right), Pad(PadButton::DPadRight)]),
+            (Action::Fire, vec![Mouse(MouseButton::Left), Key(KeyCode::Space), Pad(PadButton::RightTrigger)]),
+            (Action::NextWeapon, vec![Key(KeyCode::Q), Pad(PadButton::North)]),
+            (Action::Pause, vec![Key(KeyCode::Escape), Pad(PadButton::Start)]),
+            (Action::MenuUp, vec![Key(KeyCode::Up), Key(KeyCode::W), Pad(PadButton::DPadUp)]),
+            (Action::MenuDown, vec![Key(KeyCode::Down), Key(KeyCode::S), Pad(PadButton::DPadDown)]),
+            (Action::MenuLeft, vec![Key(KeyCode::Left), Key(KeyCode::A), Pad(PadButton::DPadLeft)]),
+            (Action::MenuRight, vec![Key(KeyCode::Right), Key(KeyCode::D), Pad(PadButton::DPadRight)]),
+            (Action::Confirm, vec![Key(KeyCode::Enter), Key(KeyCode::KpEnter), Pad(PadButton::South)]),
+            (Action::Back, vec![Key(KeyCode::Escape), Pad(PadButton::East)]),
+        ];
+        
+        Self {
+            actions: defaults.into_iter().collect(),
+        }
+    }
+}
+
+impl Bindings {
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.actions.get(&action).map(|b| b.as_slice()).unwrap_or(&[])
+    }
+    
+    // 配置文件中缺少的动作使用默认绑定（例如新版本增加了动作）
+    pub fn fill_missing(&mut self) {
+        for (action, bindings) in Bindings::default().actions {
+            self.actions.entry(action).or_insert(bindings);
+        }
+    }
+    
+    // 重新绑定：替换同类设备（键鼠或手柄）的第一个绑定，另一类设备的绑定保持不变
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.actions.entry(action).or_default();
+        bindings.retain(|b| *b != binding);
+        match bindings.iter().position(|b| b.is_gamepad() == binding.is_gamepad()) {
+            Some(index) => bindings[index] = binding,
+            None => bindings.insert(0, binding),
+        }
+    }
+    
+    // 设置界面中显示的绑定文本，例如 "W / Up / DPadUp"
+    pub fn describe(&self, action: Action) -> String {
+        let names: Vec<String> = self.get(action).iter().map(|binding| binding.short_name()).collect();
+        if names.is_empty() {
+            "-".to_string()
+        } else {
+            names.join(" / ")
+        }
+    }
+    
+    // 动作的第一个键鼠绑定，用于 HUD 中的操作提示
+    pub fn primary(&self, action: Action) -> String {
+        self.get(action)
+            .iter()
+            .find(|binding| !binding.is_gamepad())
+            .map(|binding| binding.short_name())
+            .unwrap_or_else(|| "-".to_string())
+    }
+    
+    // HUD 中显示的操作说明，随按键绑定变化
+    pub fn controls_help(&self) -> Vec<String> {
+        vec![
+            trf(
+                Text::HelpMove,
+                &[&format!(
+                    "{}/{}/{}/{}",
+                    self.primary(Action::MoveUp),
+                    self.primary(Action::MoveLeft),
+                    self.primary(Action::MoveDown),
+                    self.primary(Action::MoveRight)
+                )],
+            ),
+            tr(Text::HelpAim).to_string(),
+            trf(Text::HelpShoot, &[&self.primary(Action::Fire)]),
+            trf(Text::HelpSwitchWeapon, &[&self.primary(Action::NextWeapon)]),
+            trf(Text::HelpPause, &[&self.primary(Action::Pause)]),
+        ]
+    }
+}
\ No newline at end of file
@@ -0,0 +1,117 @@
+use super::GameEvent;
+use crate::entities::{Position, WORLD_HEIGHT, WORLD_WIDTH};
+use macroquad::prelude::*;
+use ::rand::{thread_rng, Rng};
+
+pub struct GameCamera {
+    pub position: Vec2,
+    prev_position: Vec2,
+    pub smoothing: f32,
+    pub shake_enabled: bool,
+    trauma: f32,
+    shake_offset: Vec2,
+}
+
+impl GameCamera {
+    pub fn new() -> Self {
+        Self {
+            position: vec2(WORLD_WIDTH / 2.0, WORLD_HEIGHT / 2.0),
+            prev_position: vec2(WORLD_WIDTH / 2.0, WORLD_HEIGHT / 2.0),
+            smoothing: 6.0,
+            shake_enabled: true,
+            trauma: 0.0,
+            shake_offset: Vec2::ZERO,
+        }
+    }
+    
+    // 立即对准目标，用于开局或读档时避免镜头从远处滑过来
+    pub fn snap_to(&mut self, target: Position) {
+        self.position = Self::clamp_to_world(vec2(target.x, target.y));
+        self.prev_position = self.position;
+        self.trauma = 0.0;
+        self.shake_offset = Vec2::ZERO;
+    }
+    
+    // 平滑跟随目标（指数插值，与帧率无关），每个逻辑帧调用一次
+    pub fn follow(&mut self, target: Position, dt: f32) {
+        self.prev_position = self.position;
+        let target = Self::clamp_to_world(vec2(target.x, target.y));
+        let t = 1.0 - (-self.smoothing * dt).exp();
+        self.position += (target - self.position) * t;
+    }
+    
+    // 增加震屏强度，范围 0.0 ~ 1.0
+    pub fn add_shake(&mut self, amount: f32) {
+        if self.shake_enabled {
+            self.trauma = (self.trauma + amount).min(1.0);
+        }
+    }
+    
+    // 爆炸按与玩家的距离震屏，玩家受伤时轻微震屏
+    pub fn handle_event(&mut self, event: &GameEvent, listener: Position) {
+        match event {
+            GameEvent::ExplosionStarted { position, radius, .. } => {
+                let distance = position.distance_to(&listener);
+                let falloff = (1.0 - distance / 600.0).max(0.0);
+                self.add_shake(radius / 150.0 * falloff);
+            }
+            GameEvent::PlayerDamaged { .. } => self.add_shake(0.25),
+            _ => {}
+        }
+    }
+    
+    pub fn update(&mut self, dt: f32) {
+        if self.trauma > 0.0 {
+            // 震动幅度与强度的平方成正比，小震动更柔和
+            let mut rng = thread_rng();
+            let magnitude = self.trauma * self.trauma * 18.0;
+            self.shake_offset = vec2(
+                rng.gen_range(-1.0..1.0) * magnitude,
+                rng.gen_range(-1.0..1.0) * magnitude,
+            );
+            self.trauma = (self.trauma - dt * 1.5).max(0.0);
+        } else {
+            self.shake_offset = Vec2::ZERO;
+        }
+    }
+    
+    // alpha 为渲染插值系数，与实体的插值保持一致以避免画面抖动
+    pub fn camera2d(&self, alpha: f32) -> Camera2D {
+        let position = self.prev_position.lerp(self.position, alpha);
+        Camera2D {
+            target: position + self.shake_offset,
+            zoom: vec2(2.0 / screen_width(), 2.0 / screen_height()),
+            ..Default::default()
+        }
+    }
+    
+    // 当前可见的世界区域
+    pub fn view_rect(&self) -> Rect {
+        let w = screen_width();
+        let h = screen_height();
+        Rect::new(self.position.x - w / 2.0, self.position.y - h / 2.0, w, h)
+    }
+    
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.camera2d(1.0).screen_to_world(point)
+    }
+    
+    // 限制镜头不超出世界边界；窗口比世界大时居中显示
+    fn clamp_to_world(target: Vec2) -> Vec2 {
+        let half_w = screen_width() / 2.0;
+        let half_h = screen_height() / 2.0;
+        
+        let x = if WORLD_WIDTH <= half_w * 2.0 {
+            WORLD_WIDTH / 2.0
+        } else {
+            target.x.clamp(half_w, WORLD_WIDTH - half_w)
+        };
+        let y = if WORLD_HEIGHT <= half_h * 2.0 {
+            WORLD_HEIGHT / 2.0
+        } else {
+            target.y.clamp(half_h, WORLD_HEIGHT - half_h)
+        };
+        
+        vec2(x, y)
+    }
+}
\ No newline at end of file
@@ -1,28 +1,68 @@
 use crate::entities::*;
+use super::{EventQueue, GameEvent, HitTarget};
 
+// 被摧毁的敌方坦克生命值降为 0，产生 TankDestroyed 事件，由调用方统一移除
 pub fn check_bullet_tank_collisions(
     bullets: &mut Vec<Bullet>,
-    tanks: &mut Vec<Tank>,
+    enemies: &mut Arena<Enemy>,
     player_tank: &mut Tank,
-) -> Vec<usize> {
-    let mut tanks_to_remove = Vec::new();
+    explosions: &mut Vec<Explosion>,
+    events: &mut EventQueue,
+) {
     let mut bullets_to_remove = Vec::new();
     
-    for (bullet_idx, bullet) in bullets.iter().enumerate() {
+    for (bullet_idx, bullet) in bullets.iter_mut().enumerate() {
+        // 空中的迫击炮弹和未激活的地雷不参与碰撞
+        if bullet.is_airborne() || !bullet.is_armed() {
+            continue;
+        }
+        
         // 检查子弹与玩家坦克的碰撞
         if !bullet.from_player && bullet.collides_with_circle(&player_tank.position, player_tank.size) {
-            if player_tank.take_damage(bullet.damage) {
+            if bullet.is_explosive() {
+                explosions.push(Explosion::new_mine(bullet.position.x, bullet.position.y, false));
+            } else {
+                events.push(GameEvent::BulletHit {
+                    position: bullet.position,
+                    target: HitTarget::Player,
+                    damage: bullet.damage,
+                });
                 // 玩家死亡将在游戏主循环中处理
+                damage_player(player_tank, bullet.damage, events);
             }
             bullets_to_remove.push(bullet_idx);
             continue;
         }
         
         // 检查子弹与敌方坦克的碰撞
-        for (tank_idx, tank) in tanks.iter_mut().enumerate() {
+        for (id, enemy) in enemies.iter_mut() {
+            let tank = &mut enemy.tank;
+            // 本帧已经被摧毁的坦克和已经穿过的坦克不再被击中，避免重复结算
+            if tank.health <= 0 || bullet.pierced.contains(&id) {
+                continue;
+            }
             if bullet.from_player && bullet.collides_with_circle(&tank.position, tank.size) {
-                if tank.take_damage(bullet.damage) {
-                    tanks_to_remove.push(tank_idx);
+                if bullet.is_explosive() {
+                    // 地雷爆炸的伤害在爆炸结算中处理
+                    explosions.push(Explosion::new_mine(bullet.position.x, bullet.position.y, true));
+                } else {
+                    events.push(GameEvent::BulletHit {
+                        position: bullet.position,
+                        target: HitTarget::Enemy(id),
+                        damage: bullet.damage,
+                    });
+                    events.push(GameEvent::EnemyDamaged { id, damage: bullet.damage });
+                    if tank.take_damage(bullet.damage) {
+                        // 无人机射出的子弹不属于任何一次齐射
+                        let volley = (bullet.volley != 0).then_some(bullet.volley);
+                        destroy_tank(id, tank, volley, true, explosions, events);
+                    }
+                    // 穿甲弹穿过坦克后继续飞行
+                    if bullet.pierce > 0 {
+                        bullet.pierce -= 1;
+                        bullet.pierced.push(id);
+                        continue;
+                    }
                 }
                 bullets_to_remove.push(bullet_idx);
                 break;
@@ -38,21 +78,39 @@ pub fn check_bullet_tank_collisions(
             bullets.remove(idx);
         }
     }
-    
-    tanks_to_remove
 }
 
-pub fn check_bullet_obstacle_collisions(bullets: &mut Vec<Bullet>, obstacles: &mut Vec<Obstacle>) {
+pub fn check_bullet_obstacle_collisions(
+    bullets: &mut Vec<Bullet>,
+    obstacles: &mut Vec<Obstacle>,
+    explosions: &mut Vec<Explosion>,
+    events: &mut EventQueue,
+) {
     let mut bullets_to_remove = Vec::new();
     let mut obstacles_to_remove = Vec::new();
     
-    for (bullet_idx, bullet) in bullets.iter().enumerate() {
+    for (bullet_idx, bullet) in bullets.iter_mut().enumerate() {
+        // 迫击炮弹越过障碍物，地雷静止不动
+        if bullet.kind != BulletKind::Shell {
+            continue;
+        }
+        
         for (obstacle_idx, obstacle) in obstacles.iter_mut().enumerate() {
             if obstacle.collides_with_circle(&bullet.position, bullet.size) {
-                if obstacle.take_damage(bullet.damage) {
-                    obstacles_to_remove.push(obstacle_idx);
+                events.push(GameEvent::BulletHit {
+                    position: bullet.position,
+                    target: HitTarget::Obstacle,
+                    damage: bullet.damage,
+                });
+                if obstacle.take_damage(bullet.damage) && !obstacles_to_remove.iter().any(|(idx, _)| *idx == obstacle_idx) {
+                    obstacles_to_remove.push((obstacle_idx, bullet.from_player));
+                }
+                // 还有反弹次数的炮弹弹开，否则消失
+                if bullet.ricochet > 0 {
+                    bullet.bounce(obstacle);
+                } else {
+                    bullets_to_remove.push(bullet_idx);
                 }
-                bullets_to_remove.push(bullet_idx);
                 break;
             }
         }
@@ -67,11 +125,121 @@ pub fn check_bullet_obstacle_collisions(bullets: &mut Vec<Bullet>, obstacles: &m
         }
     }
     
+    remove_destroyed_obstacles(obstacles, obstacles_to_remove, explosions, events);
+}
+
+// 结算所有尚未引爆的爆炸，包括爆炸桶和坦克殉爆引起的连锁反应
+pub fn check_explosion_collisions(
+    explosions: &mut Vec<Explosion>,
+    enemies: &mut Arena<Enemy>,
+    player_tank: &mut Tank,
+    obstacles: &mut Vec<Obstacle>,
+    events: &mut EventQueue,
+) {
+    // 新产生的爆炸会追加到列表末尾，因此按索引循环直到没有新的爆炸
+    let mut idx = 0;
+    while idx < explosions.len() {
+        if explosions[idx].detonated {
+            idx += 1;
+            continue;
+        }
+        explosions[idx].detonated = true;
+        let explosion = explosions[idx].clone();
+        events.push(GameEvent::ExplosionStarted {
+            position: explosion.position,
+            radius: explosion.radius,
+            from_player: explosion.from_player,
+        });
+        
+        // 玩家自己的爆炸不会伤害玩家
+        if !explosion.from_player {
+            let damage = explosion.damage_at(&player_tank.position, player_tank.size);
+            if damage > 0 {
+                damage_player(player_tank, damage, events);
+                player_tank.apply_knockback(explosion.knockback_at(&player_tank.position));
+            }
+        }
+        
+        for (id, enemy) in enemies.iter_mut() {
+            let tank = &mut enemy.tank;
+            // 已经被摧毁的坦克不再重复结算
+            if tank.health <= 0 {
+                continue;
+            }
+            let damage = explosion.damage_at(&tank.position, tank.size);
+            if damage > 0 {
+                tank.apply_knockback(explosion.knockback_at(&tank.position));
+                events.push(GameEvent::EnemyDamaged { id, damage });
+                if tank.take_damage(damage) {
+                    destroy_tank(id, tank, None, explosion.by_player, explosions, events);
+                }
+            }
+        }
+        
+        let mut obstacles_to_remove = Vec::new();
+        for (obstacle_idx, obstacle) in obstacles.iter_mut().enumerate() {
+            if explosion.reaches_rect(obstacle.position.x, obstacle.position.y, obstacle.width, obstacle.height) {
+                let damage = explosion.damage_at(&obstacle.center(), obstacle.width.min(obstacle.height) / 2.0);
+                if obstacle.take_damage(damage) {
+                    obstacles_to_remove.push((obstacle_idx, explosion.by_player));
+                }
+            }
+        }
+        remove_destroyed_obstacles(obstacles, obstacles_to_remove, explosions, events);
+        
+        idx += 1;
+    }
+}
+
+// 玩家受到伤害，护盾挡下的伤害不产生事件
+fn damage_player(player_tank: &mut Tank, damage: i32, events: &mut EventQueue) {
+    let health_before = player_tank.health;
+    player_tank.take_damage(damage);
+    if player_tank.health < health_before {
+        events.push(GameEvent::PlayerDamaged { damage: health_before - player_tank.health });
+    }
+}
+
+// 敌方坦克被摧毁时殉爆；volley 为击毁它的那次齐射，爆炸击毁时为 None，by_player 为是否由玩家击毁
+fn destroy_tank(
+    id: EntityId,
+    tank: &Tank,
+    volley: Option<u32>,
+    by_player: bool,
+    explosions: &mut Vec<Explosion>,
+    events: &mut EventQueue,
+) {
+    explosions.push(Explosion::new_tank_death(tank.position.x, tank.position.y, by_player));
+    events.push(GameEvent::TankDestroyed {
+        id,
+        position: tank.position,
+        weapon: tank.weapon,
+        volley,
+        by_player,
+    });
+}
+
+// 移除被摧毁的障碍物，爆炸桶被摧毁时产生新的爆炸；每项为 (障碍物索引, 是否由玩家摧毁)
+fn remove_destroyed_obstacles(
+    obstacles: &mut Vec<Obstacle>,
+    mut obstacles_to_remove: Vec<(usize, bool)>,
+    explosions: &mut Vec<Explosion>,
+    events: &mut EventQueue,
+) {
     obstacles_to_remove.sort_unstable();
     obstacles_to_remove.reverse();
-    for idx in obstacles_to_remove {
+    for (idx, by_player) in obstacles_to_remove {
         if idx < obstacles.len() {
-            obstacles.remove(idx);
+            let obstacle = obstacles.remove(idx);
+            events.push(GameEvent::ObstacleDestroyed {
+                position: obstacle.center(),
+                explosive: obstacle.explosive,
+                debris_color: obstacle.debris_color(),
+            });
+            if obstacle.explosive {
+                let center = obstacle.center();
+                explosions.push(Explosion::new_barrel(center.x, center.y, by_player));
+            }
         }
     }
 }
@@ -127,14 +295,16 @@ pub fn check_tank_obstacle_collisions(tank: &mut Tank, obstacles: &[Obstacle]) {
 
 
 
-pub fn check_powerup_collisions(tank: &mut Tank, powerups: &mut Vec<PowerUp>) -> Vec<PowerUpType> {
-    let mut collected_powerups = Vec::new();
+pub fn check_powerup_collisions(tank: &mut Tank, powerups: &mut Vec<PowerUp>, events: &mut EventQueue) {
     let mut powerups_to_remove = Vec::new();
     
     for (idx, powerup) in powerups.iter_mut().enumerate() {
         if powerup.collides_with_circle(&tank.position, tank.size) {
             powerup.collect();
-            collected_powerups.push(powerup.power_type.clone());
+            events.push(GameEvent::PowerUpCollected {
+                power_type: powerup.power_type.clone(),
+                position: powerup.position,
+            });
             powerups_to_remove.push(idx);
         }
     }
@@ -146,6 +316,65 @@ pub fn check_powerup_collisions(tank: &mut Tank, powerups: &mut Vec<PowerUp>) ->
             powerups.remove(idx);
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{ScoreTracker, ScoringRules};
+    
+    fn enemy(x: f32, y: f32, health: i32) -> Enemy {
+        let mut tank = Tank::new_enemy(x, y);
+        tank.health = health;
+        Enemy::new(tank, 1.0)
+    }
+    
+    // 结算爆炸并把产生的事件交给计分，返回 (得分, 玩家击毁数, 所有被击毁的坦克数)
+    fn detonate(explosion: Explosion, enemies: &mut Arena<Enemy>, obstacles: &mut Vec<Obstacle>) -> (i32, usize, usize) {
+        let mut player = Tank::new_player(2000.0, 2000.0);
+        let mut explosions = vec![explosion];
+        let mut events = EventQueue::new();
+        check_explosion_collisions(&mut explosions, enemies, &mut player, obstacles, &mut events);
+        
+        let mut scoring = ScoreTracker::new(ScoringRules::default());
+        let events = events.take();
+        let score = events.iter().map(|event| scoring.handle_event(event)).sum();
+        let kills = |by: Option<bool>| {
+            events
+                .iter()
+                .filter(|event| match event {
+                    GameEvent::TankDestroyed { by_player, .. } => by.is_none_or(|by| by == *by_player),
+                    _ => false,
+                })
+                .count()
+        };
+        (score, kills(Some(true)), kills(None))
+    }
+    
+    #[test]
+    fn enemy_mortar_kills_do_not_score() {
+        let mut enemies = Arena::new();
+        enemies.insert(enemy(100.0, 100.0, 50));
+        let (score, player_kills, kills) = detonate(Explosion::new_mortar(100.0, 100.0, false), &mut enemies, &mut Vec::new());
+        assert_eq!((score, player_kills, kills), (0, 0, 1));
+    }
     
-    collected_powerups
+    #[test]
+    fn chain_reactions_keep_the_owner() {
+        // 玩家的迫击炮击毁 A，A 的殉爆再击毁 B，两次都算玩家的击杀
+        let mut enemies = Arena::new();
+        enemies.insert(enemy(100.0, 100.0, 10));
+        enemies.insert(enemy(150.0, 100.0, 10));
+        let (score, player_kills, kills) = detonate(Explosion::new_mortar(40.0, 100.0, true), &mut enemies, &mut Vec::new());
+        assert_eq!((player_kills, kills), (2, 2));
+        assert!(score > 0);
+        
+        // 敌人的地雷引爆爆炸桶，爆炸桶击毁的坦克不算玩家的击杀
+        let mut enemies = Arena::new();
+        enemies.insert(enemy(200.0, 112.0, 10));
+        let mut obstacles = vec![Obstacle::new_barrel(100.0, 100.0)];
+        let (score, player_kills, kills) = detonate(Explosion::new_mine(112.0, 112.0, false), &mut enemies, &mut obstacles);
+        assert!(obstacles.is_empty());
+        assert_eq!((score, player_kills, kills), (0, 0, 1));
+    }
 }
\ No newline at end of file
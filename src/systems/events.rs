@@ -0,0 +1,67 @@
+use crate::entities::{EntityId, Position, PowerUpType, Weapon};
+use macroquad::prelude::*;
+
+// 子弹命中的对象
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HitTarget {
+    Player,
+    Enemy(EntityId),
+    Obstacle,
+}
+
+// 游戏逻辑产生的事件。碰撞、生成等系统只负责产生事件，
+// 计分、音效、粒子、镜头震动等作为消费者各自处理，新功能只需要处理关心的事件
+// 部分字段目前还没有消费者读取，留给统计等后续功能使用
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub enum GameEvent {
+    ShotFired {
+        weapon: Weapon,
+        position: Position,
+        muzzle: Position,
+        angle: f32,
+        from_player: bool,
+        bullets: u32,
+    },
+    BulletHit { position: Position, target: HitTarget, damage: i32 },
+    PlayerDamaged { damage: i32 },
+    PlayerBlocked, // 玩家坦克被障碍物挡住，贴着墙移动时每帧都会产生
+    EnemyDamaged { id: EntityId, damage: i32 },
+    // by_player 为是否由玩家击毁，敌人的迫击炮、地雷和不是玩家引起的连锁爆炸击毁的坦克为 false
+    TankDestroyed { id: EntityId, position: Position, weapon: Weapon, volley: Option<u32>, by_player: bool },
+    ObstacleDestroyed { position: Position, explosive: bool, debris_color: Color },
+    ExplosionStarted { position: Position, radius: f32, from_player: bool },
+    PowerUpCollected { power_type: PowerUpType, position: Position },
+    WaveStarted { wave: i32 },
+    WaveCleared { wave: i32 },
+    PlayerDied,
+    ChallengeStarted,
+    ChallengeAnswered { correct: bool },
+    SupplyDropAnswered { correct: bool, tier: u32, position: Position }, // 补给箱题目，tier 为题目难度档位
+    RunEnded { score: i32 },
+}
+
+// 事件队列：产生的事件先排队，每个逻辑帧结束后统一分发
+#[derive(Default)]
+pub struct EventQueue {
+    events: Vec<GameEvent>,
+}
+
+impl EventQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn push(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+    
+    // 取出所有待处理的事件
+    pub fn take(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+    
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+use super::PadButton;
+use macroquad::prelude::*;
+
+// 摇杆死区，避免手柄回中不准导致坦克缓慢漂移
+pub const STICK_DEADZONE: f32 = 0.2;
+
+// 手柄状态，每个渲染帧调用一次 update
+// 启用 gamepad 特性时使用 gilrs 读取手柄；未启用或初始化失败时所有按钮都视为未按下
+pub struct Gamepads {
+    #[cfg(feature = "gamepad")]
+    gilrs: Option<gilrs::Gilrs>,
+    down: Vec<PadButton>,
+    pressed: Vec<PadButton>,
+    left_stick: Vec2,
+    right_stick: Vec2,
+}
+
+impl Gamepads {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "gamepad")]
+            gilrs: match gilrs::Gilrs::new() {
+                Ok(gilrs) => Some(gilrs),
+                Err(err) => {
+                    eprintln!("无法初始化手柄: {}", err);
+                    None
+                }
+            },
+            down: Vec::new(),
+            pressed: Vec::new(),
+            left_stick: Vec2::ZERO,
+            right_stick: Vec2::ZERO,
+        }
+    }
+    
+    #[cfg(feature = "gamepad")]
+    pub fn update(&mut self) {
+        use gilrs::{Axis, Button};
+        
+        let Some(gilrs) = &mut self.gilrs else {
+            return;
+        };
+        // 处理完所有事件后再读取手柄的当前状态
+        while gilrs.next_event().is_some() {}
+        
+        let previous = std::mem::take(&mut self.down);
+        self.left_stick = Vec2::ZERO;
+        self.right_stick = Vec2::ZERO;
+        
+        // 使用第一个已连接的手柄
+        if let Some((_, gamepad)) = gilrs.gamepads().find(|(_, g)| g.is_connected()) {
+            for button in PadButton::ALL {
+                let gilrs_button = match button {
+                    PadButton::South => Button::South,
+                    PadButton::East => Button::East,
+                    PadButton::West => Button::West,
+                    PadButton::North => Button::North,
+                    PadButton::LeftBumper => Button::LeftTrigger,
+                    PadButton::RightBumper => Button::RightTrigger,
+                    PadButton::LeftTrigger => Button::LeftTrigger2,
+                    PadButton::RightTrigger => Button::RightTrigger2,
+                    PadButton::Select => Button::Select,
+                    PadButton::Start => Button::Start,
+                    PadButton::DPadUp => Button::DPadUp,
+                    PadButton::DPadDown => Button::DPadDown,
+                    PadButton::DPadLeft => Button::DPadLeft,
+                    PadButton::DPadRight => Button::DPadRight,
+                };
+                if gamepad.is_pressed(gilrs_button) {
+                    self.down.push(button);
+                }
+            }
+            
+            // gilrs 的 Y 轴向上为正，屏幕坐标向下为正
+            self.left_stick = vec2(gamepad.value(Axis::LeftStickX), -gamepad.value(Axis::LeftStickY));
+            self.right_stick = vec2(gamepad.value(Axis::RightStickX), -gamepad.value(Axis::RightStickY));
+        }
+        
+        self.pressed = self.down.iter().copied().filter(|b| !previous.contains(b)).collect();
+    }
+    
+    #[cfg(not(feature = "gamepad"))]
+    pub fn update(&mut self) {}
+    
+    pub fn is_down(&self, button: PadButton) -> bool {
+        self.down.contains(&button)
+    }
+    
+    pub fn is_pressed(&self, button: PadButton) -> bool {
+        self.pressed.contains(&button)
+    }
+    
+    // 本帧新按下的按钮，用于重新绑定
+    pub fn last_pressed(&self) -> Option<PadButton> {
+        self.pressed.last().copied()
+    }
+    
+    pub fn left_stick(&self) -> Vec2 {
+        self.left_stick
+    }
+    
+    pub fn right_stick(&self) -> Vec2 {
+        self.right_stick
+    }
+}
\ No newline at end of file
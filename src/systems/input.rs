@@ -1,40 +1,191 @@
+use super::{Action, Binding, Bindings, Gamepads, STICK_DEADZONE};
 use crate::entities::*;
 use macroquad::prelude::*;
 
-pub fn handle_player_input(player_tank: &mut Tank, dt: f32) -> Vec<Bullet> {
-    let mut bullets = Vec::new();
+// 输入来源：真实设备或测试中的模拟输入
+pub trait InputSource {
+    fn is_down(&self, binding: Binding) -> bool;
+    fn is_pressed(&self, binding: Binding) -> bool;
+    // 屏幕坐标
+    fn mouse_position(&self) -> Vec2;
+    fn left_stick(&self) -> Vec2;
+    fn right_stick(&self) -> Vec2;
+    // 本帧新按下的输入，用于重新绑定
+    fn last_pressed(&self) -> Option<Binding>;
+}
+
+// 键盘、鼠标和手柄
+pub struct DeviceInput {
+    pub gamepads: Gamepads,
+}
+
+impl DeviceInput {
+    pub fn new() -> Self {
+        Self {
+            gamepads: Gamepads::new(),
+        }
+    }
+    
+    // 每个渲染帧开始时调用
+    pub fn update(&mut self) {
+        self.gamepads.update();
+    }
+}
+
+impl InputSource for DeviceInput {
+    fn is_down(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => is_key_down(key),
+            Binding::Mouse(button) => is_mouse_button_down(button),
+            Binding::Pad(button) => self.gamepads.is_down(button),
+        }
+    }
     
-    // 移动控制 - 支持长按方向键，增加加速度效果
-    let mut move_x = 0.0;
-    let mut move_y = 0.0;
+    fn is_pressed(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => is_key_pressed(key),
+            Binding::Mouse(button) => is_mouse_button_pressed(button),
+            Binding::Pad(button) => self.gamepads.is_pressed(button),
+        }
+    }
     
-    // 检查所有方向键的长按状态
-    if is_key_down(KeyCode::W) || is_key_down(KeyCode::Up) {
-        move_y -= 1.0;
+    fn mouse_position(&self) -> Vec2 {
+        mouse_position().into()
     }
-    if is_key_down(KeyCode::S) || is_key_down(KeyCode::Down) {
-        move_y += 1.0;
+    
+    fn left_stick(&self) -> Vec2 {
+        self.gamepads.left_stick()
     }
-    if is_key_down(KeyCode::A) || is_key_down(KeyCode::Left) {
-        move_x -= 1.0;
+    
+    fn right_stick(&self) -> Vec2 {
+        self.gamepads.right_stick()
     }
-    if is_key_down(KeyCode::D) || is_key_down(KeyCode::Right) {
-        move_x += 1.0;
+    
+    fn last_pressed(&self) -> Option<Binding> {
+        if let Some(key) = get_last_key_pressed() {
+            return Some(Binding::Key(key));
+        }
+        for button in [MouseButton::Left, MouseButton::Right, MouseButton::Middle] {
+            if is_mouse_button_pressed(button) {
+                return Some(Binding::Mouse(button));
+            }
+        }
+        self.gamepads.last_pressed().map(Binding::Pad)
+    }
+}
+
+// 忽略部分按键和按钮的输入，其余输入原样转发
+pub struct MaskedInput<'a> {
+    input: &'a dyn InputSource,
+    masked: &'a [Binding],
+}
+
+impl<'a> MaskedInput<'a> {
+    pub fn new(input: &'a dyn InputSource, masked: &'a [Binding]) -> Self {
+        Self { input, masked }
+    }
+}
+
+impl InputSource for MaskedInput<'_> {
+    fn is_down(&self, binding: Binding) -> bool {
+        !self.masked.contains(&binding) && self.input.is_down(binding)
+    }
+    
+    fn is_pressed(&self, binding: Binding) -> bool {
+        !self.masked.contains(&binding) && self.input.is_pressed(binding)
+    }
+    
+    fn mouse_position(&self) -> Vec2 {
+        self.input.mouse_position()
     }
     
+    fn left_stick(&self) -> Vec2 {
+        self.input.left_stick()
+    }
+    
+    fn right_stick(&self) -> Vec2 {
+        self.input.right_stick()
+    }
+    
+    fn last_pressed(&self) -> Option<Binding> {
+        self.input.last_pressed().filter(|binding| !self.masked.contains(binding))
+    }
+}
+
+// 一个逻辑帧中玩家的操作
+#[derive(Clone, Copy, Debug)]
+pub struct PlayerControls {
+    pub movement: Vec2,     // 长度不超过 1，模拟摇杆可以小于 1
+    pub aim: Option<Vec2>,  // 右摇杆瞄准方向（单位向量），未使用右摇杆时为 None
+    pub fire: bool,
+    pub mouse_world: Position,
+}
+
+impl Bindings {
+    pub fn down(&self, input: &dyn InputSource, action: Action) -> bool {
+        self.get(action).iter().any(|&binding| input.is_down(binding))
+    }
+    
+    pub fn pressed(&self, input: &dyn InputSource, action: Action) -> bool {
+        self.get(action).iter().any(|&binding| input.is_pressed(binding))
+    }
+    
+    // 移动方向：按键为数字输入，左摇杆超过死区时使用模拟输入
+    pub fn movement(&self, input: &dyn InputSource) -> Vec2 {
+        let stick = input.left_stick();
+        if stick.length() > STICK_DEADZONE {
+            return stick.clamp_length_max(1.0);
+        }
+        
+        let mut movement = Vec2::ZERO;
+        if self.down(input, Action::MoveUp) {
+            movement.y -= 1.0;
+        }
+        if self.down(input, Action::MoveDown) {
+            movement.y += 1.0;
+        }
+        if self.down(input, Action::MoveLeft) {
+            movement.x -= 1.0;
+        }
+        if self.down(input, Action::MoveRight) {
+            movement.x += 1.0;
+        }
+        // 标准化方向向量，确保对角线移动不会更快
+        movement.normalize_or_zero()
+    }
+    
+    // 双摇杆瞄准：右摇杆超过死区时返回瞄准方向
+    pub fn aim(&self, input: &dyn InputSource) -> Option<Vec2> {
+        let stick = input.right_stick();
+        (stick.length() > STICK_DEADZONE).then(|| stick.normalize())
+    }
+    
+    pub fn player_controls(&self, input: &dyn InputSource, mouse_world: Position) -> PlayerControls {
+        PlayerControls {
+            movement: self.movement(input),
+            aim: self.aim(input),
+            fire: self.down(input, Action::Fire),
+            mouse_world,
+        }
+    }
+}
+
+pub fn handle_player_input(player_tank: &mut Tank, dt: f32, controls: &PlayerControls) -> Vec<Bullet> {
+    let mut bullets = Vec::new();
+    
+    // 移动控制 - 支持长按方向键和模拟摇杆，增加加速度效果
+    let move_x = controls.movement.x;
+    let move_y = controls.movement.y;
+    
     // 计算目标速度
-    let move_length = ((move_x * move_x + move_y * move_y) as f32).sqrt();
+    let move_length = (move_x * move_x + move_y * move_y).sqrt();
     let target_velocity_x;
     let target_velocity_y;
     
     if move_length > 0.0 {
-        // 标准化方向向量，确保对角线移动不会更快
-        let normalized_x = move_x / move_length;
-        let normalized_y = move_y / move_length;
-        
-        // 计算目标速度
-        target_velocity_x = normalized_x * player_tank.speed;
-        target_velocity_y = normalized_y * player_tank.speed;
+        // 输入已经标准化，模拟摇杆推得越远速度越快
+        target_velocity_x = move_x * player_tank.speed;
+        target_velocity_y = move_y * player_tank.speed;
     } else {
         // 没有按键时目标速度为0
         target_velocity_x = 0.0;
@@ -75,33 +226,184 @@ pub fn handle_player_input(player_tank: &mut Tank, dt: f32) -> Vec<Bullet> {
         player_tank.velocity.y = target_velocity_y;
     }
     
-    // 转向控制 - 坦克整体转向
-    if move_length > 0.0 {
+    // 转向控制 - 坦克整体转向；使用右摇杆瞄准时朝向瞄准方向
+    if let Some(aim) = controls.aim {
+        player_tank.angle = aim.y.atan2(aim.x);
+    } else if move_length > 0.0 {
         // 根据移动方向设置坦克角度
         player_tank.angle = move_y.atan2(move_x);
     }
     
     // 射击控制
-    if is_mouse_button_down(MouseButton::Left) || is_key_down(KeyCode::Space) {
-        if player_tank.can_shoot() {
-            player_tank.shoot();
-            
-            if player_tank.scatter_shot {
-                // 散弹射击
-                let spread_angles = [-0.3, -0.15, 0.0, 0.15, 0.3];
-                for &spread in &spread_angles {
-                    let bullet_x = player_tank.position.x + player_tank.angle.cos() * (player_tank.size + 5.0);
-                    let bullet_y = player_tank.position.y + player_tank.angle.sin() * (player_tank.size + 5.0);
-                    bullets.push(Bullet::new_scatter(bullet_x, bullet_y, player_tank.angle, spread, true));
-                }
-            } else {
-                // 普通射击
-                let bullet_x = player_tank.position.x + player_tank.angle.cos() * (player_tank.size + 5.0);
-                let bullet_y = player_tank.position.y + player_tank.angle.sin() * (player_tank.size + 5.0);
-                bullets.push(Bullet::new(bullet_x, bullet_y, player_tank.angle, true));
+    if controls.fire && player_tank.can_shoot() {
+        // 迫击炮落点为鼠标位置（右摇杆瞄准时为瞄准方向的最远射程），超出射程时落在射程边缘
+        let range = player_tank.weapon.range();
+        let mouse_world = match controls.aim {
+            Some(aim) => Position::new(
+                player_tank.position.x + aim.x * range,
+                player_tank.position.y + aim.y * range,
+            ),
+            None => controls.mouse_world,
+        };
+        let dx = mouse_world.x - player_tank.position.x;
+        let dy = mouse_world.y - player_tank.position.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+        let target = if distance > range && distance > 0.0 {
+            Position::new(
+                player_tank.position.x + dx / distance * range,
+                player_tank.position.y + dy / distance * range,
+            )
+        } else {
+            mouse_world
+        };
+        bullets.extend(player_tank.fire(target));
+    }
+    
+    bullets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::PadButton;
+    
+    // 测试用的模拟输入
+    #[derive(Default)]
+    struct FakeInput {
+        down: Vec<Binding>,
+        pressed: Vec<Binding>,
+        left_stick: Vec2,
+        right_stick: Vec2,
+    }
+    
+    impl FakeInput {
+        fn holding(bindings: &[Binding]) -> Self {
+            Self {
+                down: bindings.to_vec(),
+                ..Default::default()
             }
         }
     }
     
-    bullets
+    impl InputSource for FakeInput {
+        fn is_down(&self, binding: Binding) -> bool {
+            self.down.contains(&binding)
+        }
+        
+        fn is_pressed(&self, binding: Binding) -> bool {
+            self.pressed.contains(&binding)
+        }
+        
+        fn mouse_position(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+        
+        fn left_stick(&self) -> Vec2 {
+            self.left_stick
+        }
+        
+        fn right_stick(&self) -> Vec2 {
+            self.right_stick
+        }
+        
+        fn last_pressed(&self) -> Option<Binding> {
+            self.pressed.last().copied()
+        }
+    }
+    
+    #[test]
+    fn keys_map_to_movement() {
+        let bindings = Bindings::default();
+        let input = FakeInput::holding(&[Binding::Key(KeyCode::W)]);
+        assert_eq!(bindings.movement(&input), vec2(0.0, -1.0));
+        
+        // 对角线移动不会更快
+        let input = FakeInput::holding(&[Binding::Key(KeyCode::Up), Binding::Key(KeyCode::D)]);
+        let movement = bindings.movement(&input);
+        assert!((movement.length() - 1.0).abs() < 1e-5);
+        assert!(movement.x > 0.0 && movement.y < 0.0);
+    }
+    
+    #[test]
+    fn analog_stick_respects_deadzone() {
+        let bindings = Bindings::default();
+        let mut input = FakeInput {
+            left_stick: vec2(0.1, 0.05),
+            ..Default::default()
+        };
+        assert_eq!(bindings.movement(&input), Vec2::ZERO);
+        
+        // 超过死区时保留推杆幅度
+        input.left_stick = vec2(0.5, 0.0);
+        assert_eq!(bindings.movement(&input), vec2(0.5, 0.0));
+        
+        input.right_stick = vec2(0.0, 0.9);
+        assert_eq!(bindings.aim(&input), Some(vec2(0.0, 1.0)));
+    }
+    
+    #[test]
+    fn masked_inputs_do_not_move_the_tank() {
+        let mut bindings = Bindings::default();
+        bindings.rebind(Action::MoveUp, Binding::Pad(PadButton::DPadUp));
+        let input = FakeInput::holding(&[Binding::Pad(PadButton::DPadUp), Binding::Key(KeyCode::D)]);
+        assert_eq!(bindings.movement(&input), vec2(1.0, -1.0).normalize());
+        
+        // 被用来作答的十字键不再控制移动，其余按键照常
+        let masked = MaskedInput::new(&input, &[Binding::Pad(PadButton::DPadUp)]);
+        assert_eq!(bindings.movement(&masked), vec2(1.0, 0.0));
+    }
+    
+    #[test]
+    fn actions_follow_rebinding() {
+        let mut bindings = Bindings::default();
+        let input = FakeInput {
+            pressed: vec![Binding::Key(KeyCode::E)],
+            ..Default::default()
+        };
+        assert!(!bindings.pressed(&input, Action::NextWeapon));
+        
+        bindings.rebind(Action::NextWeapon, Binding::Key(KeyCode::E));
+        assert!(bindings.pressed(&input, Action::NextWeapon));
+        // 替换原来的键盘绑定，手柄绑定保持不变
+        assert_eq!(
+            bindings.get(Action::NextWeapon),
+            &[Binding::Key(KeyCode::E), Binding::Pad(PadButton::North)]
+        );
+    }
+    
+    #[test]
+    fn bindings_round_trip_through_json() {
+        let mut bindings = Bindings::default();
+        bindings.rebind(Action::Fire, Binding::Pad(PadButton::RightBumper));
+        let json = serde_json::to_string(&bindings).unwrap();
+        let loaded: Bindings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, bindings);
+        
+        assert!(Binding::try_from("Key:Nope".to_string()).is_err());
+    }
+    
+    #[test]
+    fn missing_actions_use_defaults() {
+        let mut bindings: Bindings = serde_json::from_str(r#"{"Fire": ["Key:F"]}"#).unwrap();
+        bindings.fill_missing();
+        assert_eq!(bindings.get(Action::Fire), &[Binding::Key(KeyCode::F)]);
+        assert_eq!(bindings.get(Action::Pause), Bindings::default().get(Action::Pause));
+    }
+    
+    #[test]
+    fn fire_control_shoots_along_aim() {
+        let mut tank = Tank::new_player(100.0, 100.0);
+        let controls = PlayerControls {
+            movement: Vec2::ZERO,
+            aim: Some(vec2(0.0, 1.0)),
+            fire: true,
+            mouse_world: Position::new(0.0, 0.0),
+        };
+        let bullets = handle_player_input(&mut tank, 1.0 / 60.0, &controls);
+        assert_eq!(bullets.len(), 1);
+        assert!(bullets[0].velocity.y > 0.0);
+        
+        // 冷却中不能再次射击
+        assert!(handle_player_input(&mut tank, 1.0 / 60.0, &controls).is_empty());
+    }
 }
\ No newline at end of file
@@ -1,7 +1,31 @@
+pub mod achievements;
+pub mod audio;
+pub mod bindings;
+pub mod camera;
 pub mod collision;
+pub mod events;
+pub mod gamepad;
 pub mod input;
+pub mod particles;
+pub mod perks;
+pub mod scoring;
 pub mod spawning;
+pub mod stats;
+pub mod supply;
+#[cfg(feature = "audio")]
+pub mod synth;
 
+pub use achievements::*;
+pub use audio::*;
+pub use bindings::*;
+pub use camera::*;
 pub use collision::*;
+pub use events::*;
+pub use gamepad::*;
 pub use input::*;
-pub use spawning::*;
\ No newline at end of file
+pub use particles::*;
+pub use perks::*;
+pub use scoring::*;
+pub use spawning::*;
+pub use stats::*;
+pub use supply::*;
\ No newline at end of file
@@ -0,0 +1,422 @@
+use super::GameEvent;
+use crate::entities::{Position, Weapon};
+use crate::i18n::{tr, Text};
+use macroquad::prelude::*;
+use ::rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ParticleQuality {
+    Off,
+    Low,
+    High,
+}
+
+impl ParticleQuality {
+    pub const ALL: [ParticleQuality; 3] = [ParticleQuality::Off, ParticleQuality::Low, ParticleQuality::High];
+    
+    // 低配机器使用较小的粒子上限
+    pub fn max_particles(&self) -> usize {
+        match self {
+            ParticleQuality::Off => 0,
+            ParticleQuality::Low => 300,
+            ParticleQuality::High => 2000,
+        }
+    }
+    
+    pub fn max_decals(&self) -> usize {
+        match self {
+            ParticleQuality::Off => 0,
+            ParticleQuality::Low => 100,
+            ParticleQuality::High => 600,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParticleQuality::Off => tr(Text::Off),
+            ParticleQuality::Low => tr(Text::QualityLow),
+            ParticleQuality::High => tr(Text::QualityHigh),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParticleShape {
+    Circle,
+    Square,
+    Line, // 沿速度方向拉长的线段（火花）
+}
+
+#[derive(Clone, Copy)]
+pub struct Particle {
+    pub position: Position,
+    pub velocity: Vec2,
+    pub color: Color,
+    pub start_size: f32,
+    pub end_size: f32,
+    pub angle: f32,
+    pub drag: f32,
+    pub lifetime: f32,
+    pub max_lifetime: f32,
+    pub shape: ParticleShape,
+    pub alive: bool,
+}
+
+impl Particle {
+    fn new(x: f32, y: f32, velocity: Vec2, color: Color, size: f32, max_lifetime: f32) -> Self {
+        Self {
+            position: Position::new(x, y),
+            velocity,
+            color,
+            start_size: size,
+            end_size: size,
+            angle: 0.0,
+            drag: 0.0,
+            lifetime: 0.0,
+            max_lifetime,
+            shape: ParticleShape::Circle,
+            alive: true,
+        }
+    }
+    
+    fn update(&mut self, dt: f32) {
+        self.position.x += self.velocity.x * dt;
+        self.position.y += self.velocity.y * dt;
+        self.velocity *= (-self.drag * dt).exp();
+        self.lifetime += dt;
+        if self.lifetime >= self.max_lifetime {
+            self.alive = false;
+        }
+    }
+    
+    fn draw(&self) {
+        let progress = (self.lifetime / self.max_lifetime).clamp(0.0, 1.0);
+        let size = self.start_size + (self.end_size - self.start_size) * progress;
+        let mut color = self.color;
+        color.a *= 1.0 - progress;
+        
+        match self.shape {
+            ParticleShape::Circle => {
+                draw_circle(self.position.x, self.position.y, size, color);
+            }
+            ParticleShape::Square => {
+                draw_rectangle_ex(
+                    self.position.x,
+                    self.position.y,
+                    size,
+                    size * 0.5,
+                    DrawRectangleParams {
+                        offset: vec2(0.5, 0.5),
+                        rotation: self.angle,
+                        color,
+                    },
+                );
+            }
+            ParticleShape::Line => {
+                let length = self.velocity.length().max(1.0);
+                let dir = self.velocity / length;
+                let tail = (length * 0.03).clamp(2.0, 8.0);
+                draw_line(
+                    self.position.x,
+                    self.position.y,
+                    self.position.x - dir.x * tail,
+                    self.position.y - dir.y * tail,
+                    size,
+                    color,
+                );
+            }
+        }
+    }
+}
+
+// 固定容量的粒子池，满了之后覆盖最旧的粒子，避免运行时反复分配内存
+struct ParticlePool {
+    particles: Vec<Particle>,
+    capacity: usize,
+    next_slot: usize,
+}
+
+impl ParticlePool {
+    fn new(capacity: usize) -> Self {
+        Self {
+            particles: Vec::with_capacity(capacity),
+            capacity,
+            next_slot: 0,
+        }
+    }
+    
+    fn spawn(&mut self, particle: Particle) {
+        if self.capacity == 0 {
+            return;
+        }
+        
+        if self.particles.len() < self.capacity {
+            self.particles.push(particle);
+            return;
+        }
+        
+        // 优先复用已经消亡的粒子槽位
+        if let Some(slot) = self.particles.iter().position(|p| !p.alive) {
+            self.particles[slot] = particle;
+            return;
+        }
+        
+        self.particles[self.next_slot] = particle;
+        self.next_slot = (self.next_slot + 1) % self.capacity;
+    }
+    
+    fn update(&mut self, dt: f32) {
+        for particle in self.particles.iter_mut().filter(|p| p.alive) {
+            particle.update(dt);
+        }
+    }
+    
+    fn draw(&self) {
+        for particle in self.particles.iter().filter(|p| p.alive) {
+            particle.draw();
+        }
+    }
+    
+    fn resize(&mut self, capacity: usize) {
+        self.particles.truncate(capacity);
+        self.capacity = capacity;
+        self.next_slot = 0;
+    }
+    
+    fn clear(&mut self) {
+        self.particles.clear();
+        self.next_slot = 0;
+    }
+}
+
+pub struct ParticleSystem {
+    // 地面层：履带印等贴花，绘制在所有实体之下
+    ground: ParticlePool,
+    // 空中层：火光、火花、烟雾等，绘制在所有实体之上
+    air: ParticlePool,
+    pub quality: ParticleQuality,
+}
+
+impl ParticleSystem {
+    pub fn new(quality: ParticleQuality) -> Self {
+        Self {
+            ground: ParticlePool::new(quality.max_decals()),
+            air: ParticlePool::new(quality.max_particles()),
+            quality,
+        }
+    }
+    
+    pub fn set_quality(&mut self, quality: ParticleQuality) {
+        self.quality = quality;
+        self.ground.resize(quality.max_decals());
+        self.air.resize(quality.max_particles());
+    }
+    
+    pub fn clear(&mut self) {
+        self.ground.clear();
+        self.air.clear();
+    }
+    
+    pub fn update(&mut self, dt: f32) {
+        self.ground.update(dt);
+        self.air.update(dt);
+    }
+    
+    // 根据游戏事件产生对应的特效
+    pub fn handle_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ShotFired { weapon, muzzle, angle, .. } if *weapon != Weapon::MineLayer => {
+                self.emit_muzzle_flash(*muzzle, *angle);
+            }
+            GameEvent::BulletHit { position, .. } => self.emit_impact_sparks(*position),
+            GameEvent::ObstacleDestroyed { position, debris_color, .. } => self.emit_debris(*position, *debris_color),
+            GameEvent::ExplosionStarted { position, radius, .. } => self.emit_explosion(*position, *radius),
+            _ => {}
+        }
+    }
+    
+    pub fn draw_ground(&self) {
+        self.ground.draw();
+    }
+    
+    pub fn draw_air(&self) {
+        self.air.draw();
+    }
+    
+    // 开火时的炮口火光
+    pub fn emit_muzzle_flash(&mut self, position: Position, angle: f32) {
+        let mut rng = thread_rng();
+        for _ in 0..6 {
+            let spread = rng.gen_range(-0.35..0.35);
+            let speed = rng.gen_range(80.0..180.0);
+            let velocity = Vec2::from_angle(angle + spread) * speed;
+            let mut particle = Particle::new(
+                position.x,
+                position.y,
+                velocity,
+                Color::new(1.0, rng.gen_range(0.7..1.0), 0.3, 1.0),
+                rng.gen_range(2.5..4.5),
+                rng.gen_range(0.06..0.12),
+            );
+            particle.end_size = 0.5;
+            particle.drag = 8.0;
+            self.air.spawn(particle);
+        }
+    }
+    
+    // 子弹飞行轨迹，每帧调用
+    pub fn emit_bullet_trail(&mut self, position: Position, color: Color) {
+        let mut particle = Particle::new(
+            position.x,
+            position.y,
+            Vec2::ZERO,
+            Color::new(color.r, color.g, color.b, 0.5),
+            1.8,
+            0.15,
+        );
+        particle.end_size = 0.3;
+        self.air.spawn(particle);
+    }
+    
+    // 子弹命中时的火花
+    pub fn emit_impact_sparks(&mut self, position: Position) {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = rng.gen_range(100.0..260.0);
+            let mut particle = Particle::new(
+                position.x,
+                position.y,
+                Vec2::from_angle(angle) * speed,
+                Color::new(1.0, rng.gen_range(0.6..0.9), 0.2, 1.0),
+                1.5,
+                rng.gen_range(0.15..0.3),
+            );
+            particle.shape = ParticleShape::Line;
+            particle.drag = 5.0;
+            self.air.spawn(particle);
+        }
+    }
+    
+    // 墙壁被摧毁时的碎片
+    pub fn emit_debris(&mut self, position: Position, color: Color) {
+        let mut rng = thread_rng();
+        for _ in 0..14 {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = rng.gen_range(40.0..180.0);
+            let shade = rng.gen_range(0.7..1.1);
+            let mut particle = Particle::new(
+                position.x + rng.gen_range(-10.0..10.0),
+                position.y + rng.gen_range(-10.0..10.0),
+                Vec2::from_angle(angle) * speed,
+                Color::new(color.r * shade, color.g * shade, color.b * shade, 1.0),
+                rng.gen_range(3.0..7.0),
+                rng.gen_range(0.5..1.0),
+            );
+            particle.shape = ParticleShape::Square;
+            particle.angle = angle;
+            particle.drag = 4.0;
+            self.air.spawn(particle);
+        }
+    }
+    
+    // 受损坦克冒出的烟雾，每帧调用，受损越严重烟越多
+    pub fn emit_smoke(&mut self, position: Position, health_ratio: f32, dt: f32) {
+        let mut rng = thread_rng();
+        let intensity = (1.0 - health_ratio * 2.0).clamp(0.0, 1.0);
+        if intensity <= 0.0 || rng.gen::<f32>() > intensity * 20.0 * dt {
+            return;
+        }
+        
+        let gray = rng.gen_range(0.2..0.4);
+        let mut particle = Particle::new(
+            position.x + rng.gen_range(-6.0..6.0),
+            position.y + rng.gen_range(-6.0..6.0),
+            vec2(rng.gen_range(-10.0..10.0), rng.gen_range(-40.0..-20.0)),
+            Color::new(gray, gray, gray, 0.6),
+            rng.gen_range(3.0..5.0),
+            rng.gen_range(0.8..1.4),
+        );
+        particle.end_size = 12.0;
+        particle.drag = 0.5;
+        self.air.spawn(particle);
+    }
+    
+    // 坦克行驶留下的履带印，每帧调用，按移动距离概率生成
+    pub fn emit_tread_marks(&mut self, position: Position, angle: f32, size: f32, distance: f32) {
+        let mut rng = thread_rng();
+        if rng.gen::<f32>() > distance / 10.0 {
+            return;
+        }
+        
+        // 两条履带分别位于坦克两侧
+        let side = Vec2::from_angle(angle + std::f32::consts::FRAC_PI_2) * size * 0.6;
+        for offset in [side, -side] {
+            let mut particle = Particle::new(
+                position.x + offset.x,
+                position.y + offset.y,
+                Vec2::ZERO,
+                Color::new(0.25, 0.22, 0.18, 0.5),
+                6.0,
+                6.0,
+            );
+            particle.shape = ParticleShape::Square;
+            particle.angle = angle;
+            self.ground.spawn(particle);
+        }
+    }
+    
+    // 爆炸：火球、烟雾和火花
+    pub fn emit_explosion(&mut self, position: Position, radius: f32) {
+        let mut rng = thread_rng();
+        let count = (radius / 3.0) as usize;
+        
+        for _ in 0..count {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = rng.gen_range(0.5..2.5) * radius;
+            let mut particle = Particle::new(
+                position.x,
+                position.y,
+                Vec2::from_angle(angle) * speed,
+                Color::new(1.0, rng.gen_range(0.3..0.8), 0.0, 1.0),
+                rng.gen_range(3.0..6.0),
+                rng.gen_range(0.3..0.6),
+            );
+            particle.end_size = 1.0;
+            particle.drag = 4.0;
+            self.air.spawn(particle);
+        }
+        
+        for _ in 0..count / 2 {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = rng.gen_range(0.2..0.8) * radius;
+            let gray = rng.gen_range(0.15..0.35);
+            let mut particle = Particle::new(
+                position.x,
+                position.y,
+                Vec2::from_angle(angle) * speed,
+                Color::new(gray, gray, gray, 0.7),
+                rng.gen_range(5.0..9.0),
+                rng.gen_range(0.8..1.6),
+            );
+            particle.end_size = radius * 0.3;
+            particle.drag = 2.0;
+            self.air.spawn(particle);
+        }
+        
+        self.emit_impact_sparks(position);
+        
+        // 焦痕
+        let mut scorch = Particle::new(
+            position.x,
+            position.y,
+            Vec2::ZERO,
+            Color::new(0.05, 0.05, 0.05, 0.6),
+            radius * 0.4,
+            10.0,
+        );
+        scorch.end_size = radius * 0.4;
+        self.ground.spawn(scorch);
+    }
+}
\ No newline at end of file
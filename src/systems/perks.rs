@@ -0,0 +1,185 @@
+use crate::entities::Tank;
+use crate::i18n::{tr, Text};
+use ::rand::Rng;
+
+// 强化的稀有度，越稀有出现的权重越低
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+}
+
+impl Rarity {
+    pub fn weight(&self) -> u32 {
+        match self {
+            Rarity::Common => 10,
+            Rarity::Rare => 4,
+            Rarity::Epic => 1,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rarity::Common => tr(Text::RarityCommon),
+            Rarity::Rare => tr(Text::RarityRare),
+            Rarity::Epic => tr(Text::RarityEpic),
+        }
+    }
+}
+
+// 波次间隙可以选择的本局强化
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Perk {
+    FastReload,
+    Regen,
+    Armor,
+    BiggerShield,
+    Pierce,
+    Ricochet,
+    Drone,
+}
+
+impl Perk {
+    pub const ALL: [Perk; 7] = [
+        Perk::FastReload,
+        Perk::Regen,
+        Perk::Armor,
+        Perk::BiggerShield,
+        Perk::Pierce,
+        Perk::Ricochet,
+        Perk::Drone,
+    ];
+    pub const MAX_DRONES: usize = 3;
+    
+    pub fn rarity(&self) -> Rarity {
+        match self {
+            Perk::FastReload | Perk::Regen | Perk::Armor | Perk::BiggerShield => Rarity::Common,
+            Perk::Pierce | Perk::Ricochet => Rarity::Rare,
+            Perk::Drone => Rarity::Epic,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Perk::FastReload => tr(Text::PerkFastReload),
+            Perk::Regen => tr(Text::PerkRegen),
+            Perk::Armor => tr(Text::PerkArmor),
+            Perk::BiggerShield => tr(Text::PerkBiggerShield),
+            Perk::Pierce => tr(Text::PerkPierce),
+            Perk::Ricochet => tr(Text::PerkRicochet),
+            Perk::Drone => tr(Text::PerkDrone),
+        }
+    }
+    
+    pub fn description(&self) -> &'static str {
+        match self {
+            Perk::FastReload => tr(Text::PerkFastReloadDescription),
+            Perk::Regen => tr(Text::PerkRegenDescription),
+            Perk::Armor => tr(Text::PerkArmorDescription),
+            Perk::BiggerShield => tr(Text::PerkBiggerShieldDescription),
+            Perk::Pierce => tr(Text::PerkPierceDescription),
+            Perk::Ricochet => tr(Text::PerkRicochetDescription),
+            Perk::Drone => tr(Text::PerkDroneDescription),
+        }
+    }
+    
+    // 已经叠加到上限的强化不再出现
+    pub fn is_available(&self, tank: &Tank, drones: usize) -> bool {
+        match self {
+            Perk::FastReload => tank.shot_cooldown > 0.1,
+            Perk::Regen => tank.perks.regen < 5.0,
+            Perk::Armor | Perk::BiggerShield => true,
+            Perk::Pierce => tank.perks.pierce < 3,
+            Perk::Ricochet => tank.perks.ricochet < 3,
+            Perk::Drone => drones < Self::MAX_DRONES,
+        }
+    }
+    
+    // 把强化应用到玩家坦克上；无人机由调用方生成
+    pub fn apply(&self, tank: &mut Tank) {
+        match self {
+            Perk::FastReload => tank.shot_cooldown *= 0.88,
+            Perk::Regen => tank.perks.regen += 1.0,
+            Perk::Armor => {
+                tank.max_health += 30;
+                tank.heal(30);
+            }
+            Perk::BiggerShield => tank.perks.shield_bonus += 0.5,
+            Perk::Pierce => tank.perks.pierce += 1,
+            Perk::Ricochet => tank.perks.ricochet += 1,
+            Perk::Drone => {}
+        }
+    }
+}
+
+// 按稀有度权重不重复地抽取最多 count 个强化
+pub fn roll_perks(rng: &mut impl Rng, count: usize, available: impl Fn(Perk) -> bool) -> Vec<Perk> {
+    let mut pool: Vec<Perk> = Perk::ALL.iter().copied().filter(|perk| available(*perk)).collect();
+    let mut offers = Vec::new();
+    while offers.len() < count && !pool.is_empty() {
+        let total: u32 = pool.iter().map(|perk| perk.rarity().weight()).sum();
+        let mut roll = rng.gen_range(0..total);
+        let index = pool
+            .iter()
+            .position(|perk| {
+                let weight = perk.rarity().weight();
+                if roll < weight {
+                    true
+                } else {
+                    roll -= weight;
+                    false
+                }
+            })
+            .unwrap_or(0);
+        offers.push(pool.remove(index));
+    }
+    offers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+    
+    #[test]
+    fn offers_are_distinct() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let offers = roll_perks(&mut rng, 3, |_| true);
+            assert_eq!(offers.len(), 3);
+            assert!(offers.iter().all(|perk| offers.iter().filter(|other| *other == perk).count() == 1));
+        }
+    }
+    
+    #[test]
+    fn unavailable_perks_are_never_offered() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..100 {
+            let offers = roll_perks(&mut rng, 3, |perk| perk != Perk::Drone);
+            assert!(!offers.contains(&Perk::Drone));
+        }
+        assert_eq!(roll_perks(&mut rng, 3, |perk| perk == Perk::Armor), [Perk::Armor]);
+    }
+    
+    #[test]
+    fn common_perks_are_offered_more_often() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            let perk = roll_perks(&mut rng, 1, |_| true)[0];
+            counts[perk.rarity() as usize] += 1;
+        }
+        assert!(counts[0] > counts[1] && counts[1] > counts[2]);
+    }
+    
+    #[test]
+    fn pierce_is_capped() {
+        let mut tank = Tank::new_player(0.0, 0.0);
+        for _ in 0..3 {
+            Perk::Pierce.apply(&mut tank);
+        }
+        assert!(!Perk::Pierce.is_available(&tank, 0));
+    }
+}
\ No newline at end of file
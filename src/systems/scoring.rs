@@ -0,0 +1,372 @@
+use super::{GameEvent, HitTarget};
+use crate::entities::{Position, PowerUpType};
+use serde::{Deserialize, Serialize};
+
+// 得分飘字显示的时间（秒）
+pub const POPUP_LIFETIME: f32 = 1.2;
+
+// 计分规则；保存在 settings.json 的 scoring 字段中，可以手动调整
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringRules {
+    pub kill_points: i32,
+    pub combo_window: f32, // 两次击杀间隔不超过这个时间（秒）时连击继续
+    pub combo_step: f32, // 每多一次连击增加的倍率
+    pub max_combo_multiplier: f32,
+    pub no_damage_kills_per_step: u32, // 每连续这么多次未受伤的击杀，倍率增加一档
+    pub no_damage_step: f32,
+    pub max_no_damage_multiplier: f32,
+    pub accuracy_min_shots: u32, // 开火次数达到这个值后才计算命中率奖励
+    pub accuracy_threshold: f32, // 命中率超过这个值时开始奖励
+    pub max_accuracy_bonus: f32, // 命中率 100% 时增加的倍率
+    pub supply_drop_points: i32, // 答对补给箱题目的得分，乘以题目难度档位 + 1
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            kill_points: 100,
+            combo_window: 3.0,
+            combo_step: 0.5,
+            max_combo_multiplier: 4.0,
+            no_damage_kills_per_step: 3,
+            no_damage_step: 0.25,
+            max_no_damage_multiplier: 2.0,
+            accuracy_min_shots: 10,
+            accuracy_threshold: 0.5,
+            max_accuracy_bonus: 0.5,
+            supply_drop_points: 50,
+        }
+    }
+}
+
+// 各类游戏事件的基础得分
+pub fn points_for(event: &GameEvent, rules: &ScoringRules) -> i32 {
+    match event {
+        GameEvent::TankDestroyed { by_player: true, .. } => rules.kill_points,
+        GameEvent::PowerUpCollected { power_type, .. } => match power_type {
+            PowerUpType::Health => 20,
+            PowerUpType::Shield => 30,
+            PowerUpType::ScatterShot => 25,
+            PowerUpType::SpeedBoost => 25,
+            PowerUpType::Damage => 40,
+            PowerUpType::SupplyDrop => 10,
+        },
+        // 清波奖励为下一波的波数 × 50
+        GameEvent::WaveCleared { wave } => (wave + 1) * 50,
+        GameEvent::SupplyDropAnswered { correct: true, tier, .. } => rules.supply_drop_points * (*tier as i32 + 1),
+        _ => 0,
+    }
+}
+
+// 在得分位置向上飘动的分数
+#[derive(Clone, Debug)]
+pub struct ScorePopup {
+    pub position: Position,
+    pub points: i32,
+    pub multiplier: f32,
+    pub age: f32,
+}
+
+// 本局的计分状态：连击、未受伤击杀数和命中率
+pub struct ScoreTracker {
+    pub rules: ScoringRules,
+    pub combo: u32,
+    pub combo_timer: f32,
+    pub kills_without_damage: u32,
+    pub shells_fired: u32,
+    pub shells_hit: u32,
+    pub popups: Vec<ScorePopup>,
+}
+
+impl ScoreTracker {
+    pub fn new(rules: ScoringRules) -> Self {
+        Self {
+            rules,
+            combo: 0,
+            combo_timer: 0.0,
+            kills_without_damage: 0,
+            shells_fired: 0,
+            shells_hit: 0,
+            popups: Vec::new(),
+        }
+    }
+    
+    // 新的一局开始时清空计分状态，保留规则
+    pub fn reset(&mut self) {
+        *self = Self::new(self.rules.clone());
+    }
+    
+    // 每个逻辑帧调用一次
+    pub fn update(&mut self, dt: f32) {
+        if self.combo_timer > 0.0 {
+            self.combo_timer -= dt;
+            if self.combo_timer <= 0.0 {
+                self.combo_timer = 0.0;
+                self.combo = 0;
+            }
+        }
+        
+        for popup in &mut self.popups {
+            popup.age += dt;
+        }
+        self.popups.retain(|popup| popup.age < POPUP_LIFETIME);
+    }
+    
+    // 处理一个游戏事件，返回应加的分数
+    pub fn handle_event(&mut self, event: &GameEvent) -> i32 {
+        match event {
+            // 只有炮弹会直接命中坦克，迫击炮和地雷通过爆炸结算，不计入命中率
+            GameEvent::ShotFired { weapon, from_player: true, bullets, .. } if weapon.fires_shells() => {
+                self.shells_fired += bullets;
+                0
+            }
+            GameEvent::BulletHit { target: HitTarget::Enemy(_), .. } => {
+                self.shells_hit += 1;
+                0
+            }
+            GameEvent::PlayerDamaged { .. } => {
+                self.kills_without_damage = 0;
+                0
+            }
+            // 不是玩家击毁的坦克不得分，也不延续连击
+            GameEvent::TankDestroyed { position, by_player: true, .. } => {
+                self.combo = if self.combo_timer > 0.0 { self.combo + 1 } else { 1 };
+                self.combo_timer = self.rules.combo_window;
+                self.kills_without_damage += 1;
+                
+                let multiplier = self.multiplier();
+                let points = (points_for(event, &self.rules) as f32 * multiplier).round() as i32;
+                self.add_popup(*position, points, multiplier);
+                points
+            }
+            GameEvent::PowerUpCollected { position, .. } | GameEvent::SupplyDropAnswered { position, .. } => {
+                let points = points_for(event, &self.rules);
+                self.add_popup(*position, points, 1.0);
+                points
+            }
+            _ => points_for(event, &self.rules),
+        }
+    }
+    
+    // 当前击杀的总倍率
+    pub fn multiplier(&self) -> f32 {
+        self.combo_multiplier() * self.no_damage_multiplier() * self.accuracy_multiplier()
+    }
+    
+    pub fn combo_multiplier(&self) -> f32 {
+        let extra = self.combo.saturating_sub(1) as f32 * self.rules.combo_step;
+        (1.0 + extra).min(self.rules.max_combo_multiplier)
+    }
+    
+    pub fn no_damage_multiplier(&self) -> f32 {
+        let steps = self.kills_without_damage / self.rules.no_damage_kills_per_step.max(1);
+        (1.0 + steps as f32 * self.rules.no_damage_step).min(self.rules.max_no_damage_multiplier)
+    }
+    
+    pub fn accuracy(&self) -> f32 {
+        if self.shells_fired == 0 {
+            return 0.0;
+        }
+        // 散弹可能一发命中多次，命中率最多按 100% 计算
+        (self.shells_hit as f32 / self.shells_fired as f32).min(1.0)
+    }
+    
+    pub fn accuracy_multiplier(&self) -> f32 {
+        let threshold = self.rules.accuracy_threshold;
+        if self.shells_fired < self.rules.accuracy_min_shots || self.accuracy() <= threshold || threshold >= 1.0 {
+            return 1.0;
+        }
+        1.0 + self.rules.max_accuracy_bonus * (self.accuracy() - threshold) / (1.0 - threshold)
+    }
+    
+    // 连击剩余时间的比例，用于 HUD 的连击条
+    pub fn combo_time_ratio(&self) -> f32 {
+        if self.rules.combo_window <= 0.0 {
+            return 0.0;
+        }
+        (self.combo_timer / self.rules.combo_window).clamp(0.0, 1.0)
+    }
+    
+    fn add_popup(&mut self, position: Position, points: i32, multiplier: f32) {
+        if points > 0 {
+            self.popups.push(ScorePopup { position, points, multiplier, age: 0.0 });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Arena, EntityId, Weapon};
+    
+    fn test_id() -> EntityId {
+        Arena::new().insert(())
+    }
+    
+    fn kill() -> GameEvent {
+        GameEvent::TankDestroyed {
+            id: test_id(),
+            position: Position::new(0.0, 0.0),
+            weapon: Weapon::Cannon,
+            volley: Some(1),
+            by_player: true,
+        }
+    }
+    
+    fn shots(bullets: u32) -> GameEvent {
+        GameEvent::ShotFired {
+            weapon: Weapon::Cannon,
+            position: Position::new(0.0, 0.0),
+            muzzle: Position::new(0.0, 0.0),
+            angle: 0.0,
+            from_player: true,
+            bullets,
+        }
+    }
+    
+    fn enemy_hit() -> GameEvent {
+        GameEvent::BulletHit {
+            position: Position::new(0.0, 0.0),
+            target: HitTarget::Enemy(test_id()),
+            damage: 25,
+        }
+    }
+    
+    #[test]
+    fn single_kill_scores_base_points() {
+        let mut tracker = ScoreTracker::new(ScoringRules::default());
+        assert_eq!(tracker.handle_event(&kill()), 100);
+        assert_eq!(tracker.combo, 1);
+        assert_eq!(tracker.popups.len(), 1);
+    }
+    
+    #[test]
+    fn rapid_kills_build_combo() {
+        let rules = ScoringRules {
+            no_damage_step: 0.0,
+            ..Default::default()
+        };
+        let mut tracker = ScoreTracker::new(rules);
+        tracker.handle_event(&kill());
+        tracker.update(1.0);
+        // 第二次击杀：倍率 1.5
+        assert_eq!(tracker.handle_event(&kill()), 150);
+        tracker.update(1.0);
+        assert_eq!(tracker.handle_event(&kill()), 200);
+        assert_eq!(tracker.combo, 3);
+    }
+    
+    #[test]
+    fn combo_expires_after_window() {
+        let mut tracker = ScoreTracker::new(ScoringRules::default());
+        tracker.handle_event(&kill());
+        tracker.update(3.5);
+        assert_eq!(tracker.combo, 0);
+        assert_eq!(tracker.handle_event(&kill()), 100);
+    }
+    
+    #[test]
+    fn combo_multiplier_is_capped() {
+        let rules = ScoringRules {
+            max_combo_multiplier: 2.0,
+            no_damage_step: 0.0,
+            ..Default::default()
+        };
+        let mut tracker = ScoreTracker::new(rules);
+        for _ in 0..10 {
+            tracker.handle_event(&kill());
+        }
+        assert_eq!(tracker.combo_multiplier(), 2.0);
+        assert_eq!(tracker.handle_event(&kill()), 200);
+    }
+    
+    #[test]
+    fn taking_damage_resets_no_damage_multiplier() {
+        let rules = ScoringRules {
+            combo_step: 0.0,
+            ..Default::default()
+        };
+        let mut tracker = ScoreTracker::new(rules);
+        tracker.handle_event(&kill());
+        tracker.handle_event(&kill());
+        // 第三次未受伤的击杀：倍率 1.25
+        assert_eq!(tracker.handle_event(&kill()), 125);
+        
+        tracker.handle_event(&GameEvent::PlayerDamaged { damage: 10 });
+        assert_eq!(tracker.kills_without_damage, 0);
+        assert_eq!(tracker.handle_event(&kill()), 100);
+    }
+    
+    #[test]
+    fn accuracy_bonus_needs_enough_shots() {
+        let rules = ScoringRules {
+            combo_step: 0.0,
+            no_damage_step: 0.0,
+            ..Default::default()
+        };
+        let mut tracker = ScoreTracker::new(rules);
+        tracker.handle_event(&shots(4));
+        for _ in 0..4 {
+            tracker.handle_event(&enemy_hit());
+        }
+        // 只开火 4 次，还不计算命中率奖励
+        assert_eq!(tracker.accuracy_multiplier(), 1.0);
+        
+        tracker.handle_event(&shots(6));
+        for _ in 0..6 {
+            tracker.handle_event(&enemy_hit());
+        }
+        // 10 发全部命中：倍率 1.5
+        assert_eq!(tracker.accuracy(), 1.0);
+        assert_eq!(tracker.handle_event(&kill()), 150);
+    }
+    
+    #[test]
+    fn low_accuracy_gives_no_bonus() {
+        let mut tracker = ScoreTracker::new(ScoringRules::default());
+        tracker.handle_event(&shots(20));
+        for _ in 0..5 {
+            tracker.handle_event(&enemy_hit());
+        }
+        assert_eq!(tracker.accuracy(), 0.25);
+        assert_eq!(tracker.accuracy_multiplier(), 1.0);
+    }
+    
+    #[test]
+    fn pickups_and_waves_are_not_multiplied() {
+        let mut tracker = ScoreTracker::new(ScoringRules::default());
+        tracker.handle_event(&kill());
+        tracker.handle_event(&kill());
+        let pickup = GameEvent::PowerUpCollected {
+            power_type: PowerUpType::Shield,
+            position: Position::new(0.0, 0.0),
+        };
+        assert_eq!(tracker.handle_event(&pickup), 30);
+        assert_eq!(tracker.handle_event(&GameEvent::WaveCleared { wave: 1 }), 100);
+    }
+    
+    #[test]
+    fn supply_drop_bonus_scales_with_difficulty() {
+        let mut tracker = ScoreTracker::new(ScoringRules::default());
+        let answer = |correct, tier| GameEvent::SupplyDropAnswered {
+            correct,
+            tier,
+            position: Position::new(0.0, 0.0),
+        };
+        assert_eq!(tracker.handle_event(&answer(true, 0)), 50);
+        assert_eq!(tracker.handle_event(&answer(true, 4)), 250);
+        assert_eq!(tracker.handle_event(&answer(false, 4)), 0);
+        assert_eq!(tracker.popups.len(), 2);
+    }
+    
+    #[test]
+    fn popups_expire() {
+        let mut tracker = ScoreTracker::new(ScoringRules::default());
+        tracker.handle_event(&kill());
+        tracker.update(POPUP_LIFETIME / 2.0);
+        assert_eq!(tracker.popups.len(), 1);
+        tracker.update(POPUP_LIFETIME);
+        assert!(tracker.popups.is_empty());
+    }
+}
\ No newline at end of file
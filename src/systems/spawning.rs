@@ -1,11 +1,14 @@
+use super::SUPPLY_DROP_CHANCE;
 use crate::entities::*;
 use macroquad::prelude::*;
 use ::rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SpawnSystem {
-    pub last_enemy_spawn: f64,
+    pub time_since_enemy_spawn: f64,
     pub enemy_spawn_interval: f64,
-    pub last_powerup_spawn: f64,
+    pub time_since_powerup_spawn: f64,
     pub powerup_spawn_interval: f64,
     pub max_enemies: usize,
     pub difficulty_multiplier: f32,
@@ -14,22 +17,39 @@ pub struct SpawnSystem {
 impl SpawnSystem {
     pub fn new(difficulty: f32) -> Self {
         Self {
-            last_enemy_spawn: 0.0,
+            time_since_enemy_spawn: 0.0,
             enemy_spawn_interval: 3.0 / difficulty as f64,
-            last_powerup_spawn: 0.0,
+            time_since_powerup_spawn: 0.0,
             powerup_spawn_interval: 8.0,
             max_enemies: 4, // 固定最大敌方坦克数量为4辆
             difficulty_multiplier: difficulty,
         }
     }
     
-    pub fn update(&mut self, enemies: &mut Vec<Tank>, powerups: &mut Vec<PowerUp>, obstacles: &[Obstacle]) {
-        let current_time = get_time();
+    // ai_difficulty 为本局选择的难度，决定新敌人AI的攻击性
+    pub fn update(
+        &mut self,
+        enemies: &mut Arena<Enemy>,
+        powerups: &mut Vec<PowerUp>,
+        obstacles: &[Obstacle],
+        ai_difficulty: f32,
+        supply_drops: bool,
+        dt: f32,
+    ) {
+        self.time_since_enemy_spawn += dt as f64;
+        self.time_since_powerup_spawn += dt as f64;
         
         // 生成敌人
-        if current_time - self.last_enemy_spawn > self.enemy_spawn_interval && enemies.len() < self.max_enemies {
+        if self.time_since_enemy_spawn > self.enemy_spawn_interval && enemies.len() < self.max_enemies {
             if let Some(spawn_pos) = self.find_safe_spawn_position(obstacles) {
-                let mut enemy = Tank::new_enemy(spawn_pos.x, spawn_pos.y);
+                // 随机决定敌人武器：大部分使用加农炮，少数使用迫击炮或地雷
+                let mut rng = thread_rng();
+                let weapon = match rng.gen_range(0..10) {
+                    0..=6 => Weapon::Cannon,
+                    7 | 8 => Weapon::Mortar,
+                    _ => Weapon::MineLayer,
+                };
+                let mut enemy = Tank::new_enemy_with_weapon(spawn_pos.x, spawn_pos.y, weapon);
                 // 根据难度调整敌人属性
                 enemy.health = (enemy.health as f32 * self.difficulty_multiplier) as i32;
                 // 调整速度：容易模式稍微快一些，其他模式按原来的逻辑
@@ -41,28 +61,34 @@ impl SpawnSystem {
                     // 其他模式：按原来的公式
                     enemy.speed *= 1.0 + (self.difficulty_multiplier - 1.0) * 0.5;
                 }
-                enemies.push(enemy);
-                self.last_enemy_spawn = current_time;
+                enemies.insert(Enemy::new(enemy, ai_difficulty));
+                self.time_since_enemy_spawn = 0.0;
             }
         }
         
         // 生成道具
-        if current_time - self.last_powerup_spawn > self.powerup_spawn_interval {
+        if self.time_since_powerup_spawn > self.powerup_spawn_interval {
             if let Some(spawn_pos) = self.find_safe_spawn_position(obstacles) {
-                powerups.push(PowerUp::new_random(spawn_pos.x, spawn_pos.y));
-                self.last_powerup_spawn = current_time;
+                // 开启数学补给箱时，部分道具替换为补给箱
+                let powerup = if supply_drops && thread_rng().gen_bool(SUPPLY_DROP_CHANCE) {
+                    PowerUp::new_supply_drop(spawn_pos.x, spawn_pos.y)
+                } else {
+                    PowerUp::new_random(spawn_pos.x, spawn_pos.y)
+                };
+                powerups.push(powerup);
+                self.time_since_powerup_spawn = 0.0;
             }
         }
     }
     
     fn find_safe_spawn_position(&self, obstacles: &[Obstacle]) -> Option<Position> {
         let mut rng = thread_rng();
-        let screen_width = screen_width();
-        let screen_height = screen_height();
+        let world_width = WORLD_WIDTH;
+        let world_height = WORLD_HEIGHT;
         
         for _ in 0..20 {  // 最多尝试20次
-            let x = rng.gen_range(50.0..screen_width - 50.0);
-            let y = rng.gen_range(50.0..screen_height - 50.0);
+            let x = rng.gen_range(50.0..world_width - 50.0);
+            let y = rng.gen_range(50.0..world_height - 50.0);
             let pos = Position::new(x, y);
             
             // 检查是否与障碍物重叠
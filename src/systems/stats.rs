@@ -0,0 +1,243 @@
+use super::{GameEvent, HitTarget};
+use crate::entities::Weapon;
+use crate::i18n::{format_duration, format_number, tr, trf, Text};
+use serde::{Deserialize, Serialize};
+
+// 一局游戏的统计数据，由游戏事件累积而成；也用于保存历史总计
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunStats {
+    pub shots_fired: u32,
+    pub shells_fired: u32, // 直射炮弹，用于计算命中率
+    pub shells_hit: u32,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub kills: [u32; 3], // 按敌人武器分类，顺序与 Weapon::BASIC 一致
+    pub powerups_collected: u32,
+    pub walls_destroyed: u32,
+    pub challenges_answered: u32,
+    pub challenges_correct: u32,
+    pub time_alive: f64,
+    pub distance: f32,
+}
+
+impl RunStats {
+    pub fn handle_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ShotFired { weapon, from_player: true, bullets, .. } => {
+                self.shots_fired += bullets;
+                if weapon.fires_shells() {
+                    self.shells_fired += bullets;
+                }
+            }
+            GameEvent::BulletHit { target: HitTarget::Enemy(_), .. } => self.shells_hit += 1,
+            GameEvent::EnemyDamaged { damage, .. } => self.damage_dealt += damage,
+            GameEvent::PlayerDamaged { damage } => self.damage_taken += damage,
+            GameEvent::TankDestroyed { weapon, by_player: true, .. } => {
+                let index = Weapon::BASIC.iter().position(|w| w == weapon).unwrap_or(0);
+                self.kills[index] += 1;
+            }
+            GameEvent::PowerUpCollected { .. } => self.powerups_collected += 1,
+            GameEvent::ObstacleDestroyed { explosive: false, .. } => self.walls_destroyed += 1,
+            GameEvent::ChallengeAnswered { correct } => {
+                self.challenges_answered += 1;
+                if *correct {
+                    self.challenges_correct += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    // 每个逻辑帧调用一次，distance 为玩家本帧移动的距离
+    pub fn advance(&mut self, dt: f32, distance: f32) {
+        self.time_alive += dt as f64;
+        self.distance += distance;
+    }
+    
+    pub fn total_kills(&self) -> u32 {
+        self.kills.iter().sum()
+    }
+    
+    pub fn accuracy(&self) -> f32 {
+        if self.shells_fired == 0 {
+            return 0.0;
+        }
+        (self.shells_hit as f32 / self.shells_fired as f32).min(1.0)
+    }
+    
+    // 累加另一局的统计，用于历史总计
+    pub fn add(&mut self, other: &RunStats) {
+        self.shots_fired += other.shots_fired;
+        self.shells_fired += other.shells_fired;
+        self.shells_hit += other.shells_hit;
+        self.damage_dealt += other.damage_dealt;
+        self.damage_taken += other.damage_taken;
+        for (total, kills) in self.kills.iter_mut().zip(other.kills) {
+            *total += kills;
+        }
+        self.powerups_collected += other.powerups_collected;
+        self.walls_destroyed += other.walls_destroyed;
+        self.challenges_answered += other.challenges_answered;
+        self.challenges_correct += other.challenges_correct;
+        self.time_alive += other.time_alive;
+        self.distance += other.distance;
+    }
+    
+    // 结算界面中显示的各行：(名称, 数值)
+    pub fn summary_lines(&self) -> Vec<(String, String)> {
+        let mut lines = vec![
+            (tr(Text::StatTimeAlive).to_string(), format_duration(self.time_alive)),
+            (tr(Text::StatShotsFired).to_string(), format_number(self.shots_fired as i64)),
+            (tr(Text::StatAccuracy).to_string(), format!("{:.0}%", self.accuracy() * 100.0)),
+            (tr(Text::StatDamageDealt).to_string(), format_number(self.damage_dealt as i64)),
+            (tr(Text::StatDamageTaken).to_string(), format_number(self.damage_taken as i64)),
+            (tr(Text::StatKills).to_string(), self.total_kills().to_string()),
+        ];
+        for (weapon, kills) in Weapon::BASIC.iter().zip(self.kills) {
+            lines.push((format!("  {}", trf(Text::StatWeaponKills, &[&weapon.name()])), kills.to_string()));
+        }
+        lines.extend([
+            (tr(Text::StatPowerUps).to_string(), self.powerups_collected.to_string()),
+            (tr(Text::StatWallsDestroyed).to_string(), self.walls_destroyed.to_string()),
+            (
+                tr(Text::StatMathAnswered).to_string(),
+                format!("{}/{}", self.challenges_correct, self.challenges_answered),
+            ),
+            (
+                tr(Text::StatDistance).to_string(),
+                trf(Text::DistanceMeters, &[&format_number((self.distance / 10.0) as i64)]),
+            ),
+        ]);
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Arena, EntityId, Position};
+    
+    fn test_id() -> EntityId {
+        Arena::new().insert(())
+    }
+    
+    fn shots(weapon: Weapon, bullets: u32) -> GameEvent {
+        GameEvent::ShotFired {
+            weapon,
+            position: Position::new(0.0, 0.0),
+            muzzle: Position::new(0.0, 0.0),
+            angle: 0.0,
+            from_player: true,
+            bullets,
+        }
+    }
+    
+    fn enemy_hit() -> GameEvent {
+        GameEvent::BulletHit {
+            position: Position::new(0.0, 0.0),
+            target: HitTarget::Enemy(test_id()),
+            damage: 25,
+        }
+    }
+    
+    fn kill(weapon: Weapon) -> GameEvent {
+        GameEvent::TankDestroyed {
+            id: test_id(),
+            position: Position::new(0.0, 0.0),
+            weapon,
+            volley: None,
+            by_player: true,
+        }
+    }
+    
+    #[test]
+    fn accuracy_counts_only_shells_and_is_capped() {
+        let mut stats = RunStats::default();
+        assert_eq!(stats.accuracy(), 0.0);
+        
+        stats.handle_event(&shots(Weapon::Cannon, 4));
+        stats.handle_event(&shots(Weapon::Mortar, 3));
+        assert_eq!(stats.shots_fired, 7);
+        assert_eq!(stats.shells_fired, 4);
+        stats.handle_event(&enemy_hit());
+        assert_eq!(stats.accuracy(), 0.25);
+        
+        // 穿透和爆炸让命中次数超过开火次数时命中率不超过 100%
+        for _ in 0..9 {
+            stats.handle_event(&enemy_hit());
+        }
+        assert_eq!(stats.accuracy(), 1.0);
+    }
+    
+    #[test]
+    fn enemy_shots_are_not_counted() {
+        let mut stats = RunStats::default();
+        stats.handle_event(&GameEvent::ShotFired {
+            weapon: Weapon::Cannon,
+            position: Position::new(0.0, 0.0),
+            muzzle: Position::new(0.0, 0.0),
+            angle: 0.0,
+            from_player: false,
+            bullets: 1,
+        });
+        assert_eq!(stats.shots_fired, 0);
+        assert_eq!(stats.shells_fired, 0);
+    }
+    
+    #[test]
+    fn kills_are_bucketed_by_enemy_weapon() {
+        let mut stats = RunStats::default();
+        stats.handle_event(&kill(Weapon::Cannon));
+        stats.handle_event(&kill(Weapon::Mortar));
+        stats.handle_event(&kill(Weapon::Mortar));
+        stats.handle_event(&kill(Weapon::MineLayer));
+        // 基础武器以外的武器计入第一栏
+        stats.handle_event(&kill(Weapon::HeavyCannon));
+        assert_eq!(stats.kills, [2, 2, 1]);
+        assert_eq!(stats.total_kills(), 5);
+        
+        // 敌人的爆炸击毁的坦克不算作玩家的击杀
+        stats.handle_event(&GameEvent::TankDestroyed {
+            id: test_id(),
+            position: Position::new(0.0, 0.0),
+            weapon: Weapon::Cannon,
+            volley: None,
+            by_player: false,
+        });
+        assert_eq!(stats.total_kills(), 5);
+    }
+    
+    #[test]
+    fn totals_accumulate_every_field() {
+        let run = RunStats {
+            shots_fired: 10,
+            shells_fired: 8,
+            shells_hit: 4,
+            damage_dealt: 120,
+            damage_taken: 30,
+            kills: [3, 1, 0],
+            powerups_collected: 2,
+            walls_destroyed: 5,
+            challenges_answered: 2,
+            challenges_correct: 1,
+            time_alive: 90.0,
+            distance: 400.0,
+        };
+        let mut totals = RunStats::default();
+        totals.add(&run);
+        totals.add(&run);
+        assert_eq!(totals.shots_fired, 20);
+        assert_eq!(totals.shells_fired, 16);
+        assert_eq!(totals.shells_hit, 8);
+        assert_eq!(totals.damage_dealt, 240);
+        assert_eq!(totals.damage_taken, 60);
+        assert_eq!(totals.kills, [6, 2, 0]);
+        assert_eq!(totals.powerups_collected, 4);
+        assert_eq!(totals.walls_destroyed, 10);
+        assert_eq!((totals.challenges_correct, totals.challenges_answered), (2, 4));
+        assert_eq!(totals.time_alive, 180.0);
+        assert_eq!(totals.distance, 800.0);
+        assert_eq!(totals.accuracy(), 0.5);
+    }
+}
\ No newline at end of file
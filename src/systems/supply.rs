@@ -0,0 +1,103 @@
+use super::{Binding, PadButton};
+use crate::entities::{Position, Tank};
+use crate::i18n::{tr, Text};
+use crate::math_challenge::{MathChallenge, Topic};
+use ::rand::seq::SliceRandom;
+use ::rand::thread_rng;
+use macroquad::prelude::KeyCode;
+use serde::{Deserialize, Serialize};
+
+// 补给箱题目出现时游戏速度的倍率
+pub const SLOW_MOTION: f32 = 0.35;
+// 答题时间（真实时间，秒）
+pub const SUPPLY_TIME: f32 = 8.0;
+// 补给箱出现在道具中的概率
+pub const SUPPLY_DROP_CHANCE: f64 = 0.25;
+// 答题时用来选择答案的输入，补给箱打开期间不再控制坦克
+pub const ANSWER_INPUTS: [Binding; 12] = [
+    Binding::Key(KeyCode::Key1),
+    Binding::Key(KeyCode::Key2),
+    Binding::Key(KeyCode::Key3),
+    Binding::Key(KeyCode::Key4),
+    Binding::Key(KeyCode::Kp1),
+    Binding::Key(KeyCode::Kp2),
+    Binding::Key(KeyCode::Kp3),
+    Binding::Key(KeyCode::Kp4),
+    Binding::Pad(PadButton::DPadUp),
+    Binding::Pad(PadButton::DPadRight),
+    Binding::Pad(PadButton::DPadDown),
+    Binding::Pad(PadButton::DPadLeft),
+];
+
+// 答对补给箱题目后获得的强化效果，比普通道具更强
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SupplyReward {
+    Repair,   // 回满生命值
+    Fortress, // 60 秒护盾
+    Arsenal,  // 散弹并提升速度
+}
+
+impl SupplyReward {
+    pub const ALL: [SupplyReward; 3] = [SupplyReward::Repair, SupplyReward::Fortress, SupplyReward::Arsenal];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            SupplyReward::Repair => tr(Text::RewardRepair),
+            SupplyReward::Fortress => tr(Text::RewardFortress),
+            SupplyReward::Arsenal => tr(Text::RewardArsenal),
+        }
+    }
+    
+    pub fn description(&self) -> &'static str {
+        match self {
+            SupplyReward::Repair => tr(Text::RewardRepairDescription),
+            SupplyReward::Fortress => tr(Text::RewardFortressDescription),
+            SupplyReward::Arsenal => tr(Text::RewardArsenalDescription),
+        }
+    }
+    
+    pub fn apply(&self, tank: &mut Tank) {
+        match self {
+            SupplyReward::Repair => tank.heal(tank.max_health),
+            SupplyReward::Fortress => tank.add_shield(60.0),
+            SupplyReward::Arsenal => {
+                tank.scatter_shot = true;
+                tank.speed = (tank.speed * 1.5).min(300.0);
+            }
+        }
+    }
+}
+
+// 拾取补给箱后出现的快速选择题，答题期间游戏放慢
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SupplyDrop {
+    pub challenge: MathChallenge,
+    pub reward: SupplyReward,
+    pub position: Position, // 拾取的位置，用于显示加分
+    pub time_left: f32,
+}
+
+impl SupplyDrop {
+    pub fn new(topics: &[Topic], tier: u32, position: Position) -> Self {
+        let mut rng = thread_rng();
+        let mut challenge = MathChallenge::generate(topics, tier);
+        // 游戏仍在进行，只用数字键或十字键选择答案
+        challenge.offer_choices(&mut rng);
+        Self {
+            challenge,
+            reward: *SupplyReward::ALL.choose(&mut rng).unwrap_or(&SupplyReward::Repair),
+            position,
+            time_left: SUPPLY_TIME,
+        }
+    }
+    
+    // 推进计时，超时返回 true
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.time_left = (self.time_left - dt).max(0.0);
+        self.time_left == 0.0
+    }
+    
+    pub fn elapsed(&self) -> f32 {
+        SUPPLY_TIME - self.time_left
+    }
+}
\ No newline at end of file
@@ -0,0 +1,252 @@
+use super::SoundEffect;
+
+// 程序生成的音效和背景音乐，游戏不需要附带音频文件
+pub const SAMPLE_RATE: u32 = 44100;
+
+// 音乐循环的长度：4 小节，每分钟 120 拍
+const MUSIC_BEAT: f32 = 0.5;
+const MUSIC_BEATS: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    Triangle,
+    Saw,
+    Noise,
+}
+
+// 一个音符：频率从 freq_start 线性滑到 freq_end，包络为线性起音加平方衰减
+#[derive(Clone, Copy, Debug)]
+pub struct Tone {
+    pub waveform: Waveform,
+    pub start: f32, // 相对于音效开始的时间（秒）
+    pub duration: f32,
+    pub freq_start: f32,
+    pub freq_end: f32,
+    pub attack: f32,
+    pub volume: f32,
+    pub lowpass: f32, // 一阶低通滤波系数，1.0 表示不滤波
+}
+
+impl Tone {
+    pub fn new(waveform: Waveform, start: f32, duration: f32, freq: f32, volume: f32) -> Self {
+        Self {
+            waveform,
+            start,
+            duration,
+            freq_start: freq,
+            freq_end: freq,
+            attack: 0.005,
+            volume,
+            lowpass: 1.0,
+        }
+    }
+    
+    pub fn slide_to(mut self, freq: f32) -> Self {
+        self.freq_end = freq;
+        self
+    }
+    
+    pub fn attack(mut self, attack: f32) -> Self {
+        self.attack = attack;
+        self
+    }
+    
+    pub fn lowpass(mut self, lowpass: f32) -> Self {
+        self.lowpass = lowpass;
+        self
+    }
+}
+
+// 以 A4（440 Hz）为基准的半音偏移
+pub fn note(semitones: i32) -> f32 {
+    440.0 * 2f32.powf(semitones as f32 / 12.0)
+}
+
+// 把所有音符混合成单声道采样，最后归一化到 peak 的峰值
+pub fn render(tones: &[Tone], length: Option<f32>, peak: f32) -> Vec<f32> {
+    let length = length.unwrap_or_else(|| tones.iter().map(|t| t.start + t.duration).fold(0.0, f32::max));
+    let mut samples = vec![0.0; (length * SAMPLE_RATE as f32) as usize];
+    // 固定种子的线性同余噪声，每次生成的音效都相同
+    let mut seed: u32 = 0x1234_5678;
+    
+    for tone in tones {
+        let first = (tone.start * SAMPLE_RATE as f32) as usize;
+        let count = (tone.duration * SAMPLE_RATE as f32) as usize;
+        let mut phase = 0.0f32;
+        let mut filtered = 0.0f32;
+        
+        for i in 0..count {
+            let Some(sample) = samples.get_mut(first + i) else {
+                break;
+            };
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let progress = t / tone.duration;
+            let freq = tone.freq_start + (tone.freq_end - tone.freq_start) * progress;
+            phase = (phase + freq / SAMPLE_RATE as f32).fract();
+            
+            let raw = match tone.waveform {
+                Waveform::Sine => (phase * std::f32::consts::TAU).sin(),
+                Waveform::Square => if phase < 0.5 { 1.0 } else { -1.0 },
+                Waveform::Triangle => 4.0 * (phase - 0.5).abs() - 1.0,
+                Waveform::Saw => 2.0 * phase - 1.0,
+                Waveform::Noise => {
+                    seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    (seed >> 8) as f32 / (1u32 << 23) as f32 - 1.0
+                }
+            };
+            filtered += (raw - filtered) * tone.lowpass;
+            
+            let envelope = if t < tone.attack {
+                t / tone.attack
+            } else {
+                let decay = 1.0 - (t - tone.attack) / (tone.duration - tone.attack).max(0.001);
+                decay * decay
+            };
+            *sample += filtered * envelope * tone.volume;
+        }
+    }
+    
+    let max = samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+    if max > 0.0 {
+        let gain = peak / max;
+        samples.iter_mut().for_each(|s| *s *= gain);
+    }
+    samples
+}
+
+// 编码为 16 位立体声 WAV；pan 为 -1.0（左）到 1.0（右），使用等功率声像
+pub fn encode_wav(samples: &[f32], pan: f32) -> Vec<u8> {
+    let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+    let (left_gain, right_gain) = (angle.cos(), angle.sin());
+    let data_len = (samples.len() * 4) as u32;
+    
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // 声道数
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 4).to_le_bytes());
+    bytes.extend_from_slice(&4u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    
+    for sample in samples {
+        for gain in [left_gain, right_gain] {
+            let value = (sample * gain).clamp(-1.0, 1.0) * i16::MAX as f32;
+            bytes.extend_from_slice(&(value as i16).to_le_bytes());
+        }
+    }
+    bytes
+}
+
+// 各个音效的音符组成
+pub fn effect(effect: SoundEffect) -> Vec<f32> {
+    use Waveform::*;
+    let tones = match effect {
+        SoundEffect::Cannon => vec![
+            Tone::new(Square, 0.0, 0.12, 520.0, 0.5).slide_to(140.0),
+            Tone::new(Noise, 0.0, 0.1, 0.0, 0.6).lowpass(0.35),
+        ],
+        SoundEffect::Mortar => vec![
+            Tone::new(Sine, 0.0, 0.3, 180.0, 1.0).slide_to(55.0),
+            Tone::new(Noise, 0.0, 0.15, 0.0, 0.4).lowpass(0.15),
+        ],
+        SoundEffect::MineDrop => vec![
+            Tone::new(Triangle, 0.0, 0.05, 900.0, 0.6),
+            Tone::new(Triangle, 0.07, 0.05, 1200.0, 0.5),
+        ],
+        SoundEffect::Hit => vec![
+            Tone::new(Noise, 0.0, 0.08, 0.0, 0.8).lowpass(0.6),
+            Tone::new(Square, 0.0, 0.06, 1400.0, 0.3).slide_to(700.0),
+        ],
+        SoundEffect::WallBreak => vec![
+            Tone::new(Noise, 0.0, 0.35, 0.0, 1.0).lowpass(0.2),
+            Tone::new(Triangle, 0.0, 0.25, 120.0, 0.6).slide_to(60.0),
+            Tone::new(Noise, 0.08, 0.2, 0.0, 0.5).lowpass(0.5),
+        ],
+        SoundEffect::Explosion => vec![
+            Tone::new(Noise, 0.0, 0.9, 0.0, 1.0).lowpass(0.08),
+            Tone::new(Sine, 0.0, 0.7, 90.0, 0.9).slide_to(30.0),
+            Tone::new(Noise, 0.0, 0.25, 0.0, 0.5).lowpass(0.4),
+        ],
+        SoundEffect::PowerUp => [0, 4, 7, 12]
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| Tone::new(Sine, i as f32 * 0.07, 0.12, note(n + 3), 0.7))
+            .collect(),
+        SoundEffect::WaveStart => vec![
+            Tone::new(Square, 0.0, 0.18, note(-2), 0.4),
+            Tone::new(Square, 0.2, 0.18, note(-2), 0.4),
+            Tone::new(Square, 0.4, 0.45, note(5), 0.45).attack(0.02),
+        ],
+        SoundEffect::WaveClear => [0, 4, 7, 12, 16]
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| Tone::new(Triangle, i as f32 * 0.09, 0.3, note(n), 0.6))
+            .chain(std::iter::once(Tone::new(Sine, 0.45, 0.6, note(12), 0.4).attack(0.05)))
+            .collect(),
+        SoundEffect::MathPrompt => vec![
+            Tone::new(Sine, 0.0, 0.8, note(12), 0.6),
+            Tone::new(Sine, 0.0, 0.8, note(19), 0.3),
+            Tone::new(Sine, 0.25, 0.8, note(7), 0.5),
+        ],
+        SoundEffect::GameOver => [7, 3, 0, -5]
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| Tone::new(Saw, i as f32 * 0.3, 0.45, note(n - 12), 0.5).lowpass(0.3))
+            .collect(),
+    };
+    render(&tones, None, 0.9)
+}
+
+// 平静的背景音乐：慢速低音和长音和弦
+pub fn calm_music() -> Vec<f32> {
+    use Waveform::*;
+    let progression = [-24, -28, -21, -26]; // A、F、C、G
+    let mut tones = Vec::new();
+    for (bar, &root) in progression.iter().enumerate() {
+        let start = bar as f32 * 4.0 * MUSIC_BEAT;
+        for beat in [0, 2] {
+            let time = start + beat as f32 * MUSIC_BEAT;
+            tones.push(Tone::new(Triangle, time, MUSIC_BEAT * 1.9, note(root), 0.6).attack(0.02));
+        }
+        for interval in [12, 16, 19] {
+            tones.push(Tone::new(Sine, start, 4.0 * MUSIC_BEAT, note(root + interval), 0.15).attack(0.4));
+        }
+    }
+    render(&tones, Some(MUSIC_BEATS as f32 * MUSIC_BEAT), 0.6)
+}
+
+// 紧张的背景音乐：与平静音乐同速同调，加入八分音符低音、鼓点和琶音
+pub fn intense_music() -> Vec<f32> {
+    use Waveform::*;
+    let progression = [-24, -28, -21, -26];
+    let mut tones = Vec::new();
+    for (bar, &root) in progression.iter().enumerate() {
+        let start = bar as f32 * 4.0 * MUSIC_BEAT;
+        for step in 0..8 {
+            let time = start + step as f32 * MUSIC_BEAT / 2.0;
+            let octave = if step % 2 == 1 { 12 } else { 0 };
+            tones.push(Tone::new(Square, time, MUSIC_BEAT / 2.0, note(root + octave), 0.25).lowpass(0.2));
+            // 反拍踩镲
+            if step % 2 == 1 {
+                tones.push(Tone::new(Noise, time, 0.05, 0.0, 0.25).lowpass(0.9));
+            }
+        }
+        for beat in 0..4 {
+            let time = start + beat as f32 * MUSIC_BEAT;
+            tones.push(Tone::new(Sine, time, 0.18, 150.0, 0.9).slide_to(40.0));
+        }
+        for (step, interval) in [12, 19, 24, 19, 16, 19, 24, 28].iter().enumerate() {
+            let time = start + step as f32 * MUSIC_BEAT / 2.0;
+            tones.push(Tone::new(Saw, time, MUSIC_BEAT / 2.0, note(root + interval), 0.12).lowpass(0.25));
+        }
+    }
+    render(&tones, Some(MUSIC_BEATS as f32 * MUSIC_BEAT), 0.7)
+}
\ No newline at end of file
@@ -0,0 +1,777 @@
+use crate::entities::{WORLD_HEIGHT, WORLD_WIDTH};
+use macroquad::prelude::*;
+
+// 界面按这个参考分辨率设计，窗口更大或更小时整体按比例缩放
+pub const REFERENCE_WIDTH: f32 = 800.0;
+pub const REFERENCE_HEIGHT: f32 = 600.0;
+pub const MIN_SCALE: f32 = 0.6;
+pub const MAX_SCALE: f32 = 3.0;
+
+// 文字的基线在行高的 4/5 处，行高等于字号时下伸部分正好留在行内
+const BASELINE: f32 = 0.8;
+
+// 矩形在区域中的停靠位置
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // 锚点在区域中的相对位置，(0, 0) 为左上角，(1, 1) 为右下角
+    fn factors(&self) -> (f32, f32) {
+        match self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::Top => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::Left => (0.0, 0.5),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::Right => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+            Anchor::Bottom => (0.5, 1.0),
+            Anchor::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+// 文字在一行中的水平对齐方式
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+// 一块界面区域和它的缩放比例；尺寸和偏移都以参考分辨率下的像素给出，放置时乘以缩放比例
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layout {
+    pub area: Rect,
+    pub scale: f32,
+}
+
+impl Layout {
+    // 整个窗口：按宽和高中较紧的一边缩放，保证参考分辨率下的界面完整放得下
+    pub fn screen(width: f32, height: f32) -> Self {
+        let fit = (width / REFERENCE_WIDTH).min(height / REFERENCE_HEIGHT);
+        Self {
+            area: Rect::new(0.0, 0.0, width, height),
+            scale: fit.clamp(MIN_SCALE, MAX_SCALE),
+        }
+    }
+    
+    pub fn current() -> Self {
+        Self::screen(screen_width(), screen_height())
+    }
+    
+    // 额外乘上一个缩放比例，例如设置中的 HUD 缩放
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self {
+            area: self.area,
+            scale: self.scale * factor,
+        }
+    }
+    
+    // 参考分辨率下的长度换算成屏幕像素
+    pub fn px(&self, value: f32) -> f32 {
+        value * self.scale
+    }
+    
+    pub fn center(&self) -> Vec2 {
+        self.area.center()
+    }
+    
+    // 区域在参考分辨率下的大小
+    pub fn size(&self) -> Vec2 {
+        vec2(self.area.w, self.area.h) / self.scale
+    }
+    
+    // 四周留出内边距后的区域
+    pub fn inset(&self, padding: f32) -> Self {
+        let p = self.px(padding).min(self.area.w / 2.0).min(self.area.h / 2.0);
+        self.within(Rect::new(self.area.x + p, self.area.y + p, self.area.w - 2.0 * p, self.area.h - 2.0 * p))
+    }
+    
+    // 同样缩放比例的子区域
+    pub fn within(&self, area: Rect) -> Self {
+        Self { area, scale: self.scale }
+    }
+    
+    // 把 width × height 的矩形停靠在锚点上，再移动 offset（向右、向下为正）
+    pub fn place(&self, anchor: Anchor, width: f32, height: f32, offset: Vec2) -> Rect {
+        let (fx, fy) = anchor.factors();
+        let (w, h) = (self.px(width), self.px(height));
+        Rect::new(
+            self.area.x + (self.area.w - w) * fx + self.px(offset.x),
+            self.area.y + (self.area.h - h) * fy + self.px(offset.y),
+            w,
+            h,
+        )
+    }
+    
+    // 从 top 开始自上而下排列的一列，行与行之间隔开 spacing
+    pub fn stack(&self, top: f32, spacing: f32) -> Stack {
+        Stack {
+            x: self.area.x,
+            y: self.area.y + self.px(top),
+            width: self.area.w,
+            spacing: self.px(spacing),
+            scale: self.scale,
+        }
+    }
+    
+    // 一行中从 offset 开始、宽 width、高 height 的一格，在行内垂直居中，用于表格的列
+    pub fn cell(&self, row: Rect, offset: f32, width: f32, height: f32) -> Rect {
+        let h = self.px(height);
+        Rect::new(row.x + self.px(offset), row.y + (row.h - h) / 2.0, self.px(width), h)
+    }
+}
+
+// 自上而下依次排列的行
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stack {
+    x: f32,
+    y: f32,
+    width: f32,
+    spacing: f32,
+    scale: f32,
+}
+
+impl Stack {
+    // 下一行，高度以参考分辨率下的像素给出
+    pub fn next(&mut self, height: f32) -> Rect {
+        self.next_px(height * self.scale)
+    }
+    
+    // 下一行，高度已经是屏幕像素
+    pub fn next_px(&mut self, height: f32) -> Rect {
+        let rect = Rect::new(self.x, self.y, self.width, height);
+        self.y += height + self.spacing;
+        rect
+    }
+    
+    // 额外空出一段距离
+    pub fn skip(&mut self, height: f32) {
+        self.y += height * self.scale;
+    }
+    
+    // 下一行的顶端
+    pub fn top(&self) -> f32 {
+        self.y
+    }
+}
+
+// 在一行中绘制文字，字号等于行高
+pub fn draw_label(text: &str, rect: Rect, align: Align, color: Color) {
+    let font_size = rect.h;
+    let width = measure_text(text, None, font_size as u16, 1.0).width;
+    let x = match align {
+        Align::Left => rect.x,
+        Align::Center => rect.x + (rect.w - width) / 2.0,
+        Align::Right => rect.x + rect.w - width,
+    };
+    draw_text(text, x, rect.y + rect.h * BASELINE, font_size, color);
+}
+
+// 游戏中的 HUD：左上角的血条和数值，左下角的操作说明，右上角的小地图
+#[derive(Clone, Debug, PartialEq)]
+pub struct HudLayout {
+    pub health_bar: Rect,
+    pub health_text: Rect,
+    pub lines: [Rect; 4], // 得分、波次、难度、武器
+    pub multiplier: Rect,
+    pub combo_bar: Rect,
+    pub combo_text: Rect,
+    pub controls: Vec<Rect>,
+    pub minimap: Rect,
+}
+
+pub fn hud(screen: &Layout, controls: usize) -> HudLayout {
+    let area = screen.inset(10.0);
+    let health_bar = area.place(Anchor::TopLeft, 200.0, 20.0, Vec2::ZERO);
+    let health_text = Rect::new(health_bar.x + area.px(5.0), health_bar.y + area.px(2.0), health_bar.w, area.px(16.0));
+    
+    let mut column = area.stack(30.0, 10.0);
+    let lines = [column.next(20.0), column.next(20.0), column.next(20.0), column.next(20.0)];
+    let multiplier = column.next(20.0);
+    let combo_bar = Rect::new(multiplier.x, multiplier.bottom() + area.px(2.0), area.px(200.0), area.px(8.0));
+    let combo_text = Rect::new(combo_bar.right() + area.px(8.0), combo_bar.bottom() - area.px(14.4), area.px(80.0), area.px(18.0));
+    
+    // 操作说明贴着底边，每行占 20 像素
+    let mut controls_column = area.stack(area.size().y - 20.0 * controls as f32 + 4.0, 4.0);
+    let controls = (0..controls).map(|_| controls_column.next(16.0)).collect();
+    
+    let map_width = 180.0;
+    let minimap = area.place(Anchor::TopRight, map_width, map_width * WORLD_HEIGHT / WORLD_WIDTH, Vec2::ZERO);
+    
+    HudLayout {
+        health_bar,
+        health_text,
+        lines,
+        multiplier,
+        combo_bar,
+        combo_text,
+        controls,
+        minimap,
+    }
+}
+
+// 主菜单和暂停菜单：标题、副标题、选项和底部的说明
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuLayout {
+    pub title: Rect,
+    pub subtitle: Rect,
+    pub items: Vec<Rect>,
+    pub footer: Rect,
+}
+
+pub fn start_menu(screen: &Layout, items: usize) -> MenuLayout {
+    let area = screen.inset(20.0);
+    let footer = area.place(Anchor::Bottom, area.size().x, 20.0, Vec2::ZERO);
+    // 标题在中线上方，窗口较矮时贴近顶端
+    let mut column = area.stack((area.size().y / 2.0 - 200.0).max(0.0), 0.0);
+    let title = column.next(64.0);
+    column.skip(30.0);
+    let subtitle = column.next(20.0);
+    column.skip(14.0);
+    MenuLayout {
+        title,
+        subtitle,
+        items: menu_rows(&area, &mut column, items, footer.y),
+        footer,
+    }
+}
+
+pub fn pause_menu(screen: &Layout, items: usize) -> MenuLayout {
+    let area = screen.inset(20.0);
+    let mut column = area.stack((area.size().y / 2.0 - 110.0).max(0.0), 0.0);
+    let title = column.next(36.0);
+    let subtitle = Rect::new(title.x, title.bottom(), title.w, 0.0);
+    column.skip(24.0);
+    let bottom = area.area.bottom() - area.px(30.0);
+    let items = menu_rows(&area, &mut column, items, bottom);
+    column.skip(16.0);
+    let footer = column.next(16.0);
+    MenuLayout {
+        title,
+        subtitle,
+        items,
+        footer,
+    }
+}
+
+// 菜单选项：每行 30 像素，选项较多时压缩行距，避免和底部的文字重叠
+fn menu_rows(area: &Layout, column: &mut Stack, count: usize, bottom: f32) -> Vec<Rect> {
+    let available = (bottom - column.top()) / count.max(1) as f32;
+    let row = available.clamp(area.px(20.0), area.px(30.0));
+    let font = (row * 2.0 / 3.0).max(area.px(16.0));
+    (0..count)
+        .map(|_| {
+            let rect = column.next_px(row);
+            Rect::new(rect.x, rect.y + (row - font) / 2.0, rect.w, font)
+        })
+        .collect()
+}
+
+// 结算界面：标题、得分、奖励、左右两栏统计、答错的题目和重新开始的提示
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameOverLayout {
+    pub title: Rect,
+    pub score: Rect,
+    pub reward: Rect,
+    pub columns: [Rect; 2],
+    pub column_heading: f32, // 每栏标题的字号
+    pub stat_row: f32,       // 统计的行距
+    pub stat_text: f32,      // 统计的字号
+    pub answer: Rect,
+    pub footer: Rect,
+}
+
+pub fn game_over(screen: &Layout) -> GameOverLayout {
+    let area = screen.inset(20.0);
+    let mut column = area.stack(10.0, 8.0);
+    let title = column.next(48.0);
+    let score = column.next(20.0);
+    let reward = column.next(20.0);
+    column.skip(18.0);
+    
+    let footer = area.place(Anchor::Bottom, area.size().x, 20.0, Vec2::ZERO);
+    let answer = area.place(Anchor::Bottom, area.size().x, 18.0, vec2(0.0, -30.0));
+    let (column_width, gap) = (260.0, 40.0);
+    let top = column.top();
+    let height = answer.y - area.px(8.0) - top;
+    let left = area.place(Anchor::Top, column_width, 0.0, vec2(-(column_width + gap) / 2.0, 0.0));
+    let right = area.place(Anchor::Top, column_width, 0.0, vec2((column_width + gap) / 2.0, 0.0));
+    GameOverLayout {
+        title,
+        score,
+        reward,
+        columns: [Rect::new(left.x, top, left.w, height), Rect::new(right.x, top, right.w, height)],
+        column_heading: area.px(22.0),
+        stat_row: area.px(22.0),
+        stat_text: area.px(18.0),
+        answer,
+        footer,
+    }
+}
+
+// 复活挑战的外框：倒计时、标题、副标题和作答提示，中间的题目区域由挑战自己绘制
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChallengeLayout {
+    pub timer: Rect,
+    pub timer_text: Rect,
+    pub title: Rect,
+    pub subtitle: Rect,
+    pub prompt: Rect,
+}
+
+pub fn challenge(screen: &Layout) -> ChallengeLayout {
+    let timer = screen.place(Anchor::Center, 300.0, 12.0, vec2(0.0, -174.0));
+    let timer_text = Rect::new(timer.right() + screen.px(10.0), timer.y - screen.px(4.0), screen.px(80.0), screen.px(20.0));
+    let mut column = screen.stack((timer.bottom() - screen.area.y) / screen.scale + 24.0, 0.0);
+    let title = column.next(36.0);
+    column.skip(10.0);
+    let subtitle = column.next(18.0);
+    column.skip(12.0);
+    let prompt = column.next(20.0);
+    ChallengeLayout {
+        timer,
+        timer_text,
+        title,
+        subtitle,
+        prompt,
+    }
+}
+
+// 挑战题目下方的三行：作答方式、按键说明和复活预览，footer_y 为挑战绘制后返回的位置
+pub fn challenge_footer(screen: &Layout, footer_y: f32) -> [Rect; 3] {
+    let mut column = screen.within(Rect::new(screen.area.x, footer_y, screen.area.w, 0.0)).stack(0.0, 4.0);
+    let hint = column.next(16.0);
+    let keys = column.next(16.0);
+    column.skip(10.0);
+    [hint, keys, column.next(18.0)]
+}
+
+// 全屏的列表界面（排行榜、成就、升级、学习报告和设置）：标题、副标题、中间的内容区域、两行状态和底部的说明
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageLayout {
+    pub title: Rect,
+    pub subtitle: Rect,
+    pub body: Layout,
+    pub status: [Rect; 2],
+    pub footer: Rect,
+}
+
+pub fn page(screen: &Layout, body_width: f32) -> PageLayout {
+    let mut column = screen.stack(40.0, 0.0);
+    let title = column.next(48.0);
+    column.skip(16.0);
+    let subtitle = column.next(20.0);
+    column.skip(12.0);
+    
+    let width = screen.size().x;
+    let footer = screen.place(Anchor::Bottom, width, 16.0, vec2(0.0, -26.0));
+    let status = [
+        screen.place(Anchor::Bottom, width, 16.0, vec2(0.0, -66.0)),
+        screen.place(Anchor::Bottom, width, 16.0, vec2(0.0, -48.0)),
+    ];
+    let top = column.top();
+    let body_w = screen.px(body_width).min(screen.area.w);
+    let body = Rect::new(screen.center().x - body_w / 2.0, top, body_w, status[0].y - screen.px(6.0) - top);
+    PageLayout {
+        title,
+        subtitle,
+        body: screen.within(body),
+        status,
+        footer,
+    }
+}
+
+// 输入名字的界面：标题、说明、输入框和底部的提示，都以屏幕中心为基准
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryLayout {
+    pub title: Rect,
+    pub subtitle: Rect,
+    pub prompt: Rect,
+    pub input: Rect,
+    pub input_text: Rect,
+    pub footer: Rect,
+}
+
+pub fn entry(screen: &Layout) -> EntryLayout {
+    let width = screen.size().x;
+    let input = screen.place(Anchor::Center, 260.0, 40.0, vec2(0.0, 30.0));
+    EntryLayout {
+        title: screen.place(Anchor::Center, width, 40.0, vec2(0.0, -112.0)),
+        subtitle: screen.place(Anchor::Center, width, 20.0, vec2(0.0, -56.0)),
+        prompt: screen.place(Anchor::Center, width, 20.0, vec2(0.0, -16.0)),
+        input,
+        input_text: Rect::new(input.x + screen.px(10.0), input.y + screen.px(8.0), input.w - screen.px(20.0), screen.px(24.0)),
+        footer: screen.place(Anchor::Center, width, 16.0, vec2(0.0, 75.0)),
+    }
+}
+
+// 波次间隙选择强化：标题、副标题、一排卡片和底部的说明
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntermissionLayout {
+    pub title: Rect,
+    pub subtitle: Rect,
+    pub cards: Vec<Rect>,
+    pub footer: Rect,
+}
+
+pub fn intermission(screen: &Layout, cards: usize) -> IntermissionLayout {
+    let width = screen.size().x;
+    let (card_w, card_h, gap) = (210.0, 190.0, 20.0);
+    let total = cards as f32 * card_w + cards.saturating_sub(1) as f32 * gap;
+    IntermissionLayout {
+        title: screen.place(Anchor::Center, width, 40.0, vec2(0.0, -162.0)),
+        subtitle: screen.place(Anchor::Center, width, 20.0, vec2(0.0, -121.0)),
+        cards: (0..cards)
+            .map(|i| {
+                let x = -total / 2.0 + i as f32 * (card_w + gap) + card_w / 2.0;
+                screen.place(Anchor::Center, card_w, card_h, vec2(x, 5.0))
+            })
+            .collect(),
+        footer: screen.place(Anchor::Center, width, 16.0, vec2(0.0, 135.0)),
+    }
+}
+
+// 补给箱题目面板：贴着屏幕下方，依次是奖励、倒计时、题目、一排选项和提示
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupplyDropLayout {
+    pub panel: Rect,
+    pub title: Rect,
+    pub timer: Rect,
+    pub question: Rect,
+    pub choices: Vec<Rect>,
+    pub hint: Rect,
+}
+
+pub fn supply_drop(screen: &Layout, choices: usize) -> SupplyDropLayout {
+    let panel = screen.place(Anchor::Bottom, 480.0, 118.0, vec2(0.0, -40.0));
+    let margin = screen.px(12.0);
+    let inner = screen.within(Rect::new(panel.x + margin, panel.y, panel.w - 2.0 * margin, panel.h));
+    let mut column = inner.stack(6.0, 4.0);
+    let title = column.next(18.0);
+    let timer = column.next(6.0);
+    column.skip(6.0);
+    let question = column.next(26.0);
+    let row = column.next(20.0);
+    let slot = row.w / choices.max(1) as f32;
+    SupplyDropLayout {
+        panel,
+        title,
+        timer,
+        question,
+        choices: (0..choices).map(|i| Rect::new(row.x + slot * i as f32, row.y, slot, row.h)).collect(),
+        hint: column.next(14.0),
+    }
+}
+
+// 屏幕上方的提示条，slide 从 0 到 1 表示从顶端滑入的进度
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToastLayout {
+    pub card: Rect,
+    pub title: Rect,
+    pub text: Rect,
+}
+
+pub fn toast(screen: &Layout, index: usize, slide: f32) -> ToastLayout {
+    let (width, height) = (320.0, 54.0);
+    let y = 10.0 + index as f32 * (height + 8.0) - (1.0 - slide) * (height + 10.0);
+    let card = screen.place(Anchor::Top, width, height, vec2(0.0, y));
+    let inner = screen.within(card);
+    ToastLayout {
+        card,
+        title: inner.place(Anchor::TopLeft, width - 24.0, 20.0, vec2(12.0, 6.0)),
+        text: inner.place(Anchor::TopLeft, width - 24.0, 18.0, vec2(12.0, 28.0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // 把矩形列表写成便于比较的文本快照，坐标取整到 0.1 像素
+    fn snapshot(rects: &[(&str, Rect)]) -> String {
+        rects
+            .iter()
+            .map(|(name, r)| format!("{} {:.1} {:.1} {:.1} {:.1}", name, r.x, r.y, r.w, r.h))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    
+    #[test]
+    fn screen_scale_fits_the_tighter_side() {
+        assert_eq!(Layout::screen(800.0, 600.0).scale, 1.0);
+        assert_eq!(Layout::screen(1920.0, 1080.0).scale, 1.8);
+        assert_eq!(Layout::screen(1280.0, 720.0).scale, 1.2);
+        assert_eq!(Layout::screen(320.0, 240.0).scale, MIN_SCALE);
+        assert!((Layout::screen(1920.0, 1080.0).scaled(1.5).px(10.0) - 27.0).abs() < 1e-4);
+    }
+    
+    #[test]
+    fn anchors_padding_and_stacks() {
+        let screen = Layout::screen(1600.0, 1200.0).inset(10.0);
+        let mut column = screen.stack(5.0, 10.0);
+        let first = column.next(20.0);
+        let second = column.next(30.0);
+        assert_eq!(
+            snapshot(&[
+                ("top-left", screen.place(Anchor::TopLeft, 100.0, 50.0, Vec2::ZERO)),
+                ("center", screen.place(Anchor::Center, 100.0, 50.0, vec2(10.0, -10.0))),
+                ("bottom-right", screen.place(Anchor::BottomRight, 100.0, 50.0, Vec2::ZERO)),
+                ("first", first),
+                ("second", second),
+            ]),
+            "top-left 20.0 20.0 200.0 100.0\n\
+             center 720.0 530.0 200.0 100.0\n\
+             bottom-right 1380.0 1080.0 200.0 100.0\n\
+             first 20.0 30.0 1560.0 40.0\n\
+             second 20.0 90.0 1560.0 60.0"
+        );
+    }
+    
+    #[test]
+    fn hud_snapshot_scales_with_the_window_and_setting() {
+        let layout = hud(&Layout::screen(800.0, 600.0), 2);
+        assert_eq!(
+            snapshot(&[
+                ("health", layout.health_bar),
+                ("score", layout.lines[0]),
+                ("weapon", layout.lines[3]),
+                ("multiplier", layout.multiplier),
+                ("combo", layout.combo_bar),
+                ("control-1", layout.controls[0]),
+                ("control-2", layout.controls[1]),
+                ("minimap", layout.minimap),
+            ]),
+            "health 10.0 10.0 200.0 20.0\n\
+             score 10.0 40.0 780.0 20.0\n\
+             weapon 10.0 130.0 780.0 20.0\n\
+             multiplier 10.0 160.0 780.0 20.0\n\
+             combo 10.0 182.0 200.0 8.0\n\
+             control-1 10.0 554.0 780.0 16.0\n\
+             control-2 10.0 574.0 780.0 16.0\n\
+             minimap 610.0 10.0 180.0 135.0"
+        );
+        
+        // 1920×1080 下缩放 1.8 倍，再乘以 HUD 缩放 1.25
+        let large = hud(&Layout::screen(1920.0, 1080.0).scaled(1.25), 2);
+        assert_eq!(
+            snapshot(&[("health", large.health_bar), ("score", large.lines[0]), ("minimap", large.minimap)]),
+            "health 22.5 22.5 450.0 45.0\n\
+             score 22.5 90.0 1875.0 45.0\n\
+             minimap 1492.5 22.5 405.0 303.8"
+        );
+        assert_eq!(large.controls[1].bottom(), 1080.0 - 22.5);
+    }
+    
+    #[test]
+    fn menu_snapshots_fit_the_items_above_the_footer() {
+        let menu = start_menu(&Layout::screen(800.0, 600.0), 7);
+        assert_eq!(
+            snapshot(&[
+                ("title", menu.title),
+                ("subtitle", menu.subtitle),
+                ("item-1", menu.items[0]),
+                ("item-7", menu.items[6]),
+                ("footer", menu.footer),
+            ]),
+            "title 20.0 100.0 760.0 64.0\n\
+             subtitle 20.0 194.0 760.0 20.0\n\
+             item-1 20.0 233.0 760.0 20.0\n\
+             item-7 20.0 413.0 760.0 20.0\n\
+             footer 20.0 560.0 760.0 20.0"
+        );
+        
+        // 窗口较矮时压缩行距，选项仍然不会和底部的文字重叠
+        let short = start_menu(&Layout::screen(800.0, 450.0), 9);
+        assert!(short.items.last().unwrap().bottom() <= short.footer.y);
+        
+        let pause = pause_menu(&Layout::screen(1600.0, 900.0), 3);
+        assert_eq!(
+            snapshot(&[
+                ("title", pause.title),
+                ("item-1", pause.items[0]),
+                ("item-3", pause.items[2]),
+                ("footer", pause.footer),
+            ]),
+            "title 30.0 285.0 1540.0 54.0\n\
+             item-1 30.0 382.5 1540.0 30.0\n\
+             item-3 30.0 472.5 1540.0 30.0\n\
+             footer 30.0 534.0 1540.0 24.0"
+        );
+    }
+    
+    #[test]
+    fn game_over_snapshot_keeps_columns_above_the_answer() {
+        let layout = game_over(&Layout::screen(800.0, 600.0));
+        assert_eq!(
+            snapshot(&[
+                ("title", layout.title),
+                ("score", layout.score),
+                ("reward", layout.reward),
+                ("left", layout.columns[0]),
+                ("right", layout.columns[1]),
+                ("answer", layout.answer),
+                ("footer", layout.footer),
+            ]),
+            "title 20.0 30.0 760.0 48.0\n\
+             score 20.0 86.0 760.0 20.0\n\
+             reward 20.0 114.0 760.0 20.0\n\
+             left 120.0 160.0 260.0 364.0\n\
+             right 420.0 160.0 260.0 364.0\n\
+             answer 20.0 532.0 760.0 18.0\n\
+             footer 20.0 560.0 760.0 20.0"
+        );
+    }
+    
+    #[test]
+    fn challenge_snapshot_is_centered_on_the_screen() {
+        let screen = Layout::screen(800.0, 600.0);
+        let layout = challenge(&screen);
+        let footer = challenge_footer(&screen, 420.0);
+        assert_eq!(
+            snapshot(&[
+                ("timer", layout.timer),
+                ("title", layout.title),
+                ("subtitle", layout.subtitle),
+                ("prompt", layout.prompt),
+                ("hint", footer[0]),
+                ("keys", footer[1]),
+                ("info", footer[2]),
+            ]),
+            "timer 250.0 120.0 300.0 12.0\n\
+             title 0.0 156.0 800.0 36.0\n\
+             subtitle 0.0 202.0 800.0 18.0\n\
+             prompt 0.0 232.0 800.0 20.0\n\
+             hint 0.0 420.0 800.0 16.0\n\
+             keys 0.0 440.0 800.0 16.0\n\
+             info 0.0 470.0 800.0 18.0"
+        );
+    }    
+    #[test]
+    fn page_snapshot_leaves_room_for_status_and_footer() {
+        let layout = page(&Layout::screen(800.0, 600.0), 560.0);
+        let mut rows = layout.body.stack(0.0, 8.0);
+        let first = rows.next(56.0);
+        assert_eq!(
+            snapshot(&[
+                ("title", layout.title),
+                ("subtitle", layout.subtitle),
+                ("body", layout.body.area),
+                ("first-row", first),
+                ("cell", layout.body.cell(first, 12.0, 200.0, 22.0)),
+                ("status-1", layout.status[0]),
+                ("status-2", layout.status[1]),
+                ("footer", layout.footer),
+            ]),
+            "title 0.0 40.0 800.0 48.0\n\
+             subtitle 0.0 104.0 800.0 20.0\n\
+             body 120.0 136.0 560.0 376.0\n\
+             first-row 120.0 136.0 560.0 56.0\n\
+             cell 132.0 153.0 200.0 22.0\n\
+             status-1 0.0 518.0 800.0 16.0\n\
+             status-2 0.0 536.0 800.0 16.0\n\
+             footer 0.0 558.0 800.0 16.0"
+        );
+        
+        // 六张成就卡片正好放满内容区域
+        for _ in 1..6 {
+            rows.next(56.0);
+        }
+        assert!(rows.top() - layout.body.px(8.0) <= layout.body.area.bottom());
+        
+        let wide = page(&Layout::screen(1920.0, 1080.0), 560.0);
+        assert_eq!(snapshot(&[("body", wide.body.area)]), "body 456.0 244.8 1008.0 676.8");
+        assert!((wide.footer.bottom() - (1080.0 - 26.0 * 1.8)).abs() < 1e-3);
+    }
+    
+    #[test]
+    fn entry_and_intermission_snapshots_are_centered() {
+        let screen = Layout::screen(800.0, 600.0);
+        let entry = entry(&screen);
+        assert_eq!(
+            snapshot(&[
+                ("title", entry.title),
+                ("subtitle", entry.subtitle),
+                ("prompt", entry.prompt),
+                ("input", entry.input),
+                ("input-text", entry.input_text),
+                ("footer", entry.footer),
+            ]),
+            "title 0.0 168.0 800.0 40.0\n\
+             subtitle 0.0 234.0 800.0 20.0\n\
+             prompt 0.0 274.0 800.0 20.0\n\
+             input 270.0 310.0 260.0 40.0\n\
+             input-text 280.0 318.0 240.0 24.0\n\
+             footer 0.0 367.0 800.0 16.0"
+        );
+        
+        let layout = intermission(&screen, 3);
+        assert_eq!(
+            snapshot(&[
+                ("title", layout.title),
+                ("subtitle", layout.subtitle),
+                ("card-1", layout.cards[0]),
+                ("card-3", layout.cards[2]),
+                ("footer", layout.footer),
+            ]),
+            "title 0.0 118.0 800.0 40.0\n\
+             subtitle 0.0 169.0 800.0 20.0\n\
+             card-1 65.0 210.0 210.0 190.0\n\
+             card-3 525.0 210.0 210.0 190.0\n\
+             footer 0.0 427.0 800.0 16.0"
+        );
+        assert_eq!(intermission(&screen, 1).cards[0].center().x, 400.0);
+    }
+    
+    #[test]
+    fn supply_drop_and_toast_snapshots() {
+        let screen = Layout::screen(800.0, 600.0);
+        let layout = supply_drop(&screen, 3);
+        assert_eq!(
+            snapshot(&[
+                ("panel", layout.panel),
+                ("title", layout.title),
+                ("timer", layout.timer),
+                ("question", layout.question),
+                ("choice-1", layout.choices[0]),
+                ("choice-3", layout.choices[2]),
+                ("hint", layout.hint),
+            ]),
+            "panel 160.0 442.0 480.0 118.0\n\
+             title 172.0 448.0 456.0 18.0\n\
+             timer 172.0 470.0 456.0 6.0\n\
+             question 172.0 486.0 456.0 26.0\n\
+             choice-1 172.0 516.0 152.0 20.0\n\
+             choice-3 476.0 516.0 152.0 20.0\n\
+             hint 172.0 540.0 456.0 14.0"
+        );
+        assert!(layout.hint.bottom() <= layout.panel.bottom());
+        
+        let second = toast(&screen, 1, 1.0);
+        let sliding = toast(&screen, 0, 0.5);
+        assert_eq!(
+            snapshot(&[
+                ("card", second.card),
+                ("title", second.title),
+                ("text", second.text),
+                ("sliding", sliding.card),
+            ]),
+            "card 240.0 72.0 320.0 54.0\n\
+             title 252.0 78.0 296.0 20.0\n\
+             text 252.0 100.0 296.0 18.0\n\
+             sliding 240.0 -22.0 320.0 54.0"
+        );
+    }
+}
\ No newline at end of file
@@ -1,318 +1,957 @@
+pub mod layout;
+pub mod palette;
+
+pub use layout::{Align, Anchor, Layout};
+pub use palette::*;
+
+use layout::{draw_label, EntryLayout, GameOverLayout, HudLayout};
 use macroquad::prelude::*;
-use crate::math_challenge::MathChallenge;
+use crate::entities::{Arena, Enemy, Obstacle, PowerUp, Tank, WORLD_HEIGHT, WORLD_WIDTH};
+use crate::challenges::{ChallengeKind, ChallengeSession};
+use crate::i18n::{format_duration, format_number, tr, trf, Text};
+use crate::math_challenge::Topic;
+use crate::storage::{ColorblindMode, LearningData, Progression, ScoreEntry, Settings, SettingsItem, StatsHistory, Upgrade};
+use crate::systems::{
+    AchievementTracker, Perk, Rarity, RunStats, ScorePopup, ScoreTracker, SupplyDrop, POPUP_LIFETIME, SUPPLY_TIME,
+};
+
+// 成就解锁提示显示的时间（秒）
+pub const TOAST_LIFETIME: f32 = 4.0;
+// 成就界面每页显示的数量
+pub const ACHIEVEMENTS_PER_PAGE: usize = 6;
+
+// 屏幕上方的提示条
+pub struct Toast {
+    pub title: String,
+    pub text: String,
+    pub age: f32,
+}
 
 pub struct GameUI {
-    pub font_size: f32,
+    pub hud_scale: f32,
+    pub palette: Palette,
+    pub toasts: Vec<Toast>,
 }
 
 impl GameUI {
     pub fn new() -> Self {
         Self {
-            font_size: 20.0,
+            hud_scale: 1.0,
+            palette: Palette::new(ColorblindMode::Off),
+            toasts: Vec::new(),
         }
     }
     
-    pub fn draw_hud(&self, player_health: i32, max_health: i32, score: i32, wave: i32, difficulty: f32) {
-        let margin = 10.0;
-        
-        // Health Bar
-        let health_bar_width = 200.0;
-        let health_bar_height = 20.0;
+    pub fn push_toast(&mut self, title: &str, text: &str) {
+        self.toasts.push(Toast {
+            title: title.to_string(),
+            text: text.to_string(),
+            age: 0.0,
+        });
+    }
+    
+    // 按真实时间推进，暂停和菜单中提示也会消失
+    pub fn update_toasts(&mut self, dt: f32) {
+        for toast in &mut self.toasts {
+            toast.age += dt;
+        }
+        self.toasts.retain(|toast| toast.age < TOAST_LIFETIME);
+    }
+    
+    // 提示条从屏幕顶端滑入，结束前淡出
+    pub fn draw_toasts(&self) {
+        let screen = Layout::current();
+        for (i, toast) in self.toasts.iter().enumerate() {
+            let slide = (toast.age / 0.25).min(1.0);
+            let alpha = ((TOAST_LIFETIME - toast.age) / 0.5).clamp(0.0, 1.0);
+            let layout = layout::toast(&screen, i, slide);
+            let card = layout.card;
+            
+            draw_rectangle(card.x, card.y, card.w, card.h, Color::new(0.1, 0.1, 0.1, 0.85 * alpha));
+            draw_rectangle_lines(card.x, card.y, card.w, card.h, 2.0, Color::new(1.0, 0.84, 0.0, alpha));
+            draw_label(&toast.title, layout.title, Align::Left, Color::new(1.0, 0.84, 0.0, alpha));
+            draw_label(&toast.text, layout.text, Align::Left, Color::new(1.0, 1.0, 1.0, alpha));
+        }
+    }
+    
+    // HUD 按窗口大小和设置中的 HUD 缩放一起缩放
+    fn hud_screen(&self) -> Layout {
+        Layout::current().scaled(self.hud_scale)
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_hud(
+        &self,
+        player_health: i32,
+        max_health: i32,
+        score: i32,
+        wave: i32,
+        difficulty: f32,
+        weapon: &str,
+        scoring: &ScoreTracker,
+        controls: &[String],
+    ) {
+        let hud = layout::hud(&self.hud_screen(), controls.len());
+        let bar = hud.health_bar;
         let health_ratio = player_health as f32 / max_health as f32;
         
-        // Health Bar Background
-        draw_rectangle(margin, margin, health_bar_width, health_bar_height, DARKGRAY);
-        
         // Health Bar
-        let health_color = if health_ratio > 0.6 {
-            GREEN
-        } else if health_ratio > 0.3 {
-            YELLOW
-        } else {
-            RED
-        };
-        
-        draw_rectangle(
-            margin,
-            margin,
-            health_bar_width * health_ratio,
-            health_bar_height,
-            health_color,
-        );
-        
-        // Health Bar Border
-        draw_rectangle_lines(margin, margin, health_bar_width, health_bar_height, 2.0, WHITE);
+        draw_rectangle(bar.x, bar.y, bar.w, bar.h, DARKGRAY);
+        draw_rectangle(bar.x, bar.y, bar.w * health_ratio, bar.h, self.palette.health_color(health_ratio));
+        draw_rectangle_lines(bar.x, bar.y, bar.w, bar.h, 2.0, WHITE);
         
         // Health Text
-        let health_text = format!("Health: {}/{}", player_health, max_health);
-        draw_text(&health_text, margin + 5.0, margin + 15.0, 16.0, WHITE);
+        let health_text = trf(Text::HudHealth, &[&player_health, &max_health]);
+        draw_label(&health_text, hud.health_text, Align::Left, WHITE);
+        
+        // Score, wave, difficulty and weapon
+        let lines = [
+            trf(Text::HudScore, &[&format_number(score as i64)]),
+            trf(Text::HudWave, &[&wave]),
+            trf(Text::HudDifficulty, &[&format!("{:.1}", difficulty)]),
+            trf(Text::HudWeapon, &[&weapon]),
+        ];
+        for (text, rect) in lines.iter().zip(hud.lines) {
+            draw_label(text, rect, Align::Left, WHITE);
+        }
         
-        // Score
-        let score_text = format!("Score: {}", score);
-        draw_text(&score_text, margin, margin + 50.0, self.font_size, WHITE);
+        // Multiplier and combo meter
+        self.draw_combo_meter(scoring, &hud);
         
-        // Wave
-        let wave_text = format!("Wave: {}", wave);
-        draw_text(&wave_text, margin, margin + 80.0, self.font_size, WHITE);
+        // Controls
+        for (control, rect) in controls.iter().zip(&hud.controls) {
+            draw_label(control, *rect, Align::Left, LIGHTGRAY);
+        }
+    }
+    
+    // 当前得分倍率；连击时在下方显示剩余连击时间
+    fn draw_combo_meter(&self, scoring: &ScoreTracker, hud: &HudLayout) {
+        let multiplier = scoring.multiplier();
+        let color = if multiplier > 1.0 { GOLD } else { LIGHTGRAY };
+        let multiplier_text = trf(Text::HudMultiplier, &[&format!("{:.2}", multiplier)]);
+        draw_label(&multiplier_text, hud.multiplier, Align::Left, color);
+        
+        if scoring.combo < 2 {
+            return;
+        }
+        let bar = hud.combo_bar;
+        draw_rectangle(bar.x, bar.y, bar.w, bar.h, DARKGRAY);
+        draw_rectangle(bar.x, bar.y, bar.w * scoring.combo_time_ratio(), bar.h, ORANGE);
+        draw_rectangle_lines(bar.x, bar.y, bar.w, bar.h, 1.0, WHITE);
+        let combo_text = trf(Text::HudCombo, &[&scoring.combo]);
+        draw_label(&combo_text, hud.combo_text, Align::Left, ORANGE);
+    }
+    
+    // 击杀和拾取道具的得分飘字，在世界坐标中绘制
+    pub fn draw_score_popups(&self, popups: &[ScorePopup]) {
+        for popup in popups {
+            let progress = popup.age / POPUP_LIFETIME;
+            let alpha = 1.0 - progress * progress;
+            let text = if popup.multiplier > 1.0 {
+                format!("+{} x{:.1}", popup.points, popup.multiplier)
+            } else {
+                format!("+{}", popup.points)
+            };
+            let size = if popup.multiplier > 1.0 { 24.0 } else { 20.0 };
+            let dims = measure_text(&text, None, size as u16, 1.0);
+            let x = popup.position.x - dims.width / 2.0;
+            let y = popup.position.y - 20.0 - popup.age * 40.0;
+            draw_text(&text, x + 1.0, y + 1.0, size, Color::new(0.0, 0.0, 0.0, alpha * 0.6));
+            draw_text(&text, x, y, size, Color::new(1.0, 0.85, 0.2, alpha));
+        }
+    }
+    
+    pub fn draw_minimap(
+        &self,
+        player: &Tank,
+        enemies: &Arena<Enemy>,
+        obstacles: &[Obstacle],
+        powerups: &[PowerUp],
+        view: Rect,
+    ) {
+        let screen = self.hud_screen();
+        let map = layout::hud(&screen, 0).minimap;
+        let (map_x, map_y, map_width, map_height) = (map.x, map.y, map.w, map.h);
+        let scale = map_width / WORLD_WIDTH;
+        let dot = screen.px(1.0);
         
-        // Difficulty
-        let difficulty_text = format!("Difficulty: {:.1}", difficulty);
-        draw_text(&difficulty_text, margin, margin + 110.0, self.font_size, WHITE);
+        // Background
+        draw_rectangle(map_x, map_y, map_width, map_height, Color::new(0.0, 0.0, 0.0, 0.6));
+        
+        // Obstacles
+        for obstacle in obstacles {
+            let color = if obstacle.explosive {
+                Color::new(0.8, 0.1, 0.1, 0.8)
+            } else if obstacle.destructible {
+                Color::new(0.6, 0.3, 0.1, 0.8)
+            } else {
+                Color::new(0.5, 0.5, 0.5, 0.8)
+            };
+            draw_rectangle(
+                map_x + obstacle.position.x * scale,
+                map_y + obstacle.position.y * scale,
+                (obstacle.width * scale).max(1.0),
+                (obstacle.height * scale).max(1.0),
+                color,
+            );
+        }
         
-        // Controls
-        let controls = [
-            "WASD/Arrow Keys: Move",
-            "Mouse: Aim",
-            "Left Click/Space: Shoot",
-            "ESC: Pause",
-        ];
+        // Power-ups
+        for powerup in powerups {
+            draw_circle(
+                map_x + powerup.position.x * scale,
+                map_y + powerup.position.y * scale,
+                2.0 * dot,
+                powerup.get_color(),
+            );
+        }
         
-        let start_y = screen_height() - 100.0;
-        for (i, control) in controls.iter().enumerate() {
-            draw_text(control, margin, start_y + i as f32 * 20.0, 16.0, LIGHTGRAY);
+        // Enemies
+        for enemy in enemies.values().map(|enemy| &enemy.tank) {
+            draw_circle(
+                map_x + enemy.position.x * scale,
+                map_y + enemy.position.y * scale,
+                2.5 * dot,
+                self.palette.tank_color(enemy),
+            );
         }
+        
+        // Player
+        draw_circle(
+            map_x + player.position.x * scale,
+            map_y + player.position.y * scale,
+            3.0 * dot,
+            if self.palette.mode == ColorblindMode::Off { SKYBLUE } else { self.palette.player },
+        );
+        
+        // Camera View
+        draw_rectangle_lines(
+            map_x + view.x.max(0.0) * scale,
+            map_y + view.y.max(0.0) * scale,
+            view.w.min(WORLD_WIDTH) * scale,
+            view.h.min(WORLD_HEIGHT) * scale,
+            1.0,
+            Color::new(1.0, 1.0, 1.0, 0.5),
+        );
+        
+        // Border
+        draw_rectangle_lines(map_x, map_y, map_width, map_height, 2.0, WHITE);
     }
     
-    pub fn draw_game_over(&self, score: i32, wave: i32, high_score: i32) {
-        let screen_w = screen_width();
-        let screen_h = screen_height();
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_game_over(
+        &self,
+        score: i32,
+        wave: i32,
+        high_score: i32,
+        rank: Option<usize>,
+        missed_answer: Option<&str>,
+        scrap: u32,
+        stats: &RunStats,
+        history: &StatsHistory,
+    ) {
+        let screen = Layout::current();
+        let layout = layout::game_over(&screen);
         
         // Semi-transparent background
-        draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.7));
+        draw_rectangle(0.0, 0.0, screen.area.w, screen.area.h, Color::new(0.0, 0.0, 0.0, 0.8));
         
         // Game Over Title
-        let title = "GAME OVER";
-        let title_size = 48.0;
-        let title_dims = measure_text(title, None, title_size as u16, 1.0);
-        draw_text(
-            title,
-            screen_w / 2.0 - title_dims.width / 2.0,
-            screen_h / 2.0 - 100.0,
-            title_size,
-            RED,
-        );
+        draw_label(tr(Text::GameOver), layout.title, Align::Center, RED);
         
         // Score Information
-        let score_text = format!("Final Score: {}", score);
-        let score_dims = measure_text(&score_text, None, self.font_size as u16, 1.0);
-        draw_text(
-            &score_text,
-            screen_w / 2.0 - score_dims.width / 2.0,
-            screen_h / 2.0 - 40.0,
-            self.font_size,
-            WHITE,
+        let score_text = trf(
+            Text::FinalScoreLine,
+            &[&format_number(score as i64), &wave, &format_number(high_score as i64)],
         );
+        draw_label(&score_text, layout.score, Align::Center, WHITE);
         
-        let wave_text = format!("Waves Reached: {}", wave);
-        let wave_dims = measure_text(&wave_text, None, self.font_size as u16, 1.0);
-        draw_text(
-            &wave_text,
-            screen_w / 2.0 - wave_dims.width / 2.0,
-            screen_h / 2.0 - 10.0,
-            self.font_size,
-            WHITE,
-        );
+        // Scrap Earned and Leaderboard Rank
+        let mut reward_text = trf(Text::ScrapEarned, &[&format_number(scrap as i64)]);
+        if let Some(rank) = rank {
+            reward_text.push_str(&format!("   {}", trf(Text::NewLeaderboardEntry, &[&(rank + 1)])));
+        }
+        draw_label(&reward_text, layout.reward, Align::Center, GREEN);
         
-        let high_score_text = format!("High Score: {}", high_score);
-        let high_score_dims = measure_text(&high_score_text, None, self.font_size as u16, 1.0);
-        draw_text(
-            &high_score_text,
-            screen_w / 2.0 - high_score_dims.width / 2.0,
-            screen_h / 2.0 + 20.0,
-            self.font_size,
-            GOLD,
-        );
+        // Run Statistics and Lifetime Totals
+        self.draw_stat_column(tr(Text::ThisRun), &stats.summary_lines(), &layout, layout.columns[0]);
+        self.draw_stat_column(tr(Text::Lifetime), &history.summary_lines(), &layout, layout.columns[1]);
+        
+        // Correct answer of the failed revive challenge
+        if let Some(answer) = missed_answer {
+            let answer_text = trf(Text::CorrectAnswer, &[&answer]);
+            draw_label(&answer_text, layout.answer, Align::Center, ORANGE);
+        }
         
         // Restart Prompt
-        let restart_text = "Press R to Restart";
-        let restart_dims = measure_text(restart_text, None, self.font_size as u16, 1.0);
-        draw_text(
-            restart_text,
-            screen_w / 2.0 - restart_dims.width / 2.0,
-            screen_h / 2.0 + 80.0,
-            self.font_size,
-            YELLOW,
-        );
+        draw_label(tr(Text::PressRToRestart), layout.footer, Align::Center, YELLOW);
+    }
+    
+    // 结算界面中的一列统计：标题下每行左侧为名称，右侧为右对齐的数值；窗口太矮时放不下的行不显示
+    fn draw_stat_column(&self, heading: &str, lines: &[(String, String)], layout: &GameOverLayout, column: Rect) {
+        let heading_rect = Rect::new(column.x, column.y, column.w, layout.column_heading);
+        draw_label(heading, heading_rect, Align::Left, GOLD);
+        let line_y = heading_rect.bottom() + layout.stat_row - layout.stat_text;
+        draw_line(column.x, line_y, column.right(), line_y, 1.0, GRAY);
+        
+        for (i, (label, value)) in lines.iter().enumerate() {
+            let top = line_y + layout.stat_row * (i + 1) as f32 - layout.stat_text;
+            let row = Rect::new(column.x, top, column.w, layout.stat_text);
+            if row.bottom() > column.bottom() {
+                break;
+            }
+            draw_label(label, row, Align::Left, LIGHTGRAY);
+            draw_label(value, row, Align::Right, WHITE);
+        }
     }
     
-    pub fn draw_pause_menu(&self) {
-        let screen_w = screen_width();
-        let screen_h = screen_height();
+    pub fn draw_pause_menu(&self, items: &[&str], selected: usize) {
+        let screen = Layout::current();
+        let layout = layout::pause_menu(&screen, items.len());
         
         // Semi-transparent background
-        draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.5));
+        draw_rectangle(0.0, 0.0, screen.area.w, screen.area.h, Color::new(0.0, 0.0, 0.0, 0.5));
         
         // Pause Title
-        let title = "PAUSED";
-        let title_size = 36.0;
-        let title_dims = measure_text(title, None, title_size as u16, 1.0);
-        draw_text(
-            title,
-            screen_w / 2.0 - title_dims.width / 2.0,
-            screen_h / 2.0 - 50.0,
-            title_size,
-            WHITE,
-        );
+        draw_label(tr(Text::Paused), layout.title, Align::Center, WHITE);
+        
+        // Menu Items
+        self.draw_menu_items(items, selected, &layout.items);
         
         // Continue Prompt
-        let continue_text = "Press ESC to Continue";
-        let continue_dims = measure_text(continue_text, None, self.font_size as u16, 1.0);
-        draw_text(
-            continue_text,
-            screen_w / 2.0 - continue_dims.width / 2.0,
-            screen_h / 2.0 + 20.0,
-            self.font_size,
-            YELLOW,
-        );
+        draw_label(tr(Text::PauseHelp), layout.footer, Align::Center, LIGHTGRAY);
     }
     
-    pub fn draw_start_menu(&self, high_score: i32) {
-        let screen_w = screen_width();
-        let screen_h = screen_height();
+    // 居中绘制的菜单选项，当前选中的选项高亮显示
+    fn draw_menu_items<S: AsRef<str>>(&self, items: &[S], selected: usize, rects: &[Rect]) {
+        for (i, (item, rect)) in items.iter().zip(rects).enumerate() {
+            let is_selected = i == selected;
+            let text = if is_selected {
+                format!("> {} <", item.as_ref())
+            } else {
+                item.as_ref().to_string()
+            };
+            draw_label(&text, *rect, Align::Center, if is_selected { YELLOW } else { LIGHTGRAY });
+        }
+    }
+    
+    pub fn draw_start_menu(&self, high_score: i32, items: &[String], selected: usize) {
+        let layout = layout::start_menu(&Layout::current(), items.len());
         
         // Background
         clear_background(BLACK);
         
         // Game Title
-        let title = "TANK BATTLE";
-        let title_size = 64.0;
-        let title_dims = measure_text(title, None, title_size as u16, 1.0);
-        draw_text(
-            title,
-            screen_w / 2.0 - title_dims.width / 2.0,
-            screen_h / 2.0 - 150.0,
-            title_size,
-            GOLD,
-        );
+        draw_label(tr(Text::GameTitle), layout.title, Align::Center, GOLD);
         
         // Difficulty Selection
-        let difficulty_text = "Select Difficulty:";
-        let diff_dims = measure_text(difficulty_text, None, self.font_size as u16, 1.0);
-        draw_text(
-            difficulty_text,
-            screen_w / 2.0 - diff_dims.width / 2.0,
-            screen_h / 2.0 - 50.0,
-            self.font_size,
-            WHITE,
-        );
+        draw_label(tr(Text::SelectDifficulty), layout.subtitle, Align::Center, WHITE);
         
-        let difficulties = ["1 - Easy", "2 - Normal", "3 - Hard"];
-        for (i, diff) in difficulties.iter().enumerate() {
-            let diff_dims = measure_text(diff, None, self.font_size as u16, 1.0);
-            draw_text(
-                diff,
-                screen_w / 2.0 - diff_dims.width / 2.0,
-                screen_h / 2.0 - 10.0 + i as f32 * 30.0,
-                self.font_size,
-                LIGHTGRAY,
-            );
-        }
+        // Menu Items
+        self.draw_menu_items(items, selected, &layout.items);
         
         // High Score
-        let high_score_text = format!("High Score: {}", high_score);
-        let high_dims = measure_text(&high_score_text, None, self.font_size as u16, 1.0);
-        draw_text(
-            &high_score_text,
-            screen_w / 2.0 - high_dims.width / 2.0,
-            screen_h / 2.0 + 120.0,
-            self.font_size,
-            GOLD,
-        );
+        let high_score_text = trf(Text::HighScore, &[&format_number(high_score as i64)]);
+        draw_label(&high_score_text, layout.footer, Align::Center, GOLD);
     }
     
-    pub fn draw_math_challenge(&self, challenge: &MathChallenge) {
-        let screen_w = screen_width();
-        let screen_h = screen_height();
+    pub fn draw_name_entry(&self, name: &str, score: i32, wave: i32) {
+        let screen = Layout::current();
+        let layout = layout::entry(&screen);
         
         // Semi-transparent background
-        draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::new(0.0, 0.0, 0.0, 0.8));
+        draw_rectangle(0.0, 0.0, screen.area.w, screen.area.h, Color::new(0.0, 0.0, 0.0, 0.8));
         
         // Title
-        let title = "MATH CHALLENGE - REVIVE!";
-        let title_size = 36.0;
-        let title_dims = measure_text(title, None, title_size as u16, 1.0);
-        draw_text(
-            title,
-            screen_w / 2.0 - title_dims.width / 2.0,
-            screen_h / 2.0 - 120.0,
-            title_size,
-            GOLD,
-        );
+        draw_label(tr(Text::NewHighScore), layout.title, Align::Center, GOLD);
+        
+        // Score
+        let score_text = trf(Text::ScoreAndWave, &[&format_number(score as i64), &wave]);
+        draw_label(&score_text, layout.subtitle, Align::Center, WHITE);
+        
+        // Prompt
+        draw_label(tr(Text::EnterName), layout.prompt, Align::Center, LIGHTGRAY);
+        
+        // Input Box
+        self.draw_input_box(name, &layout);
         
         // Instructions
-        let instruction = "Solve the math problem to revive:";
-        let inst_dims = measure_text(instruction, None, self.font_size as u16, 1.0);
-        draw_text(
-            instruction,
-            screen_w / 2.0 - inst_dims.width / 2.0,
-            screen_h / 2.0 - 60.0,
-            self.font_size,
-            WHITE,
+        draw_label(tr(Text::PressEnterToSave), layout.footer, Align::Center, LIGHTGRAY);
+    }
+    
+    // 名字输入框和跟在文字后面的光标
+    fn draw_input_box(&self, name: &str, layout: &EntryLayout) {
+        let input = layout.input;
+        draw_rectangle(input.x, input.y, input.w, input.h, DARKGRAY);
+        draw_rectangle_lines(input.x, input.y, input.w, input.h, 2.0, WHITE);
+        
+        let text = layout.input_text;
+        draw_label(name, text, Align::Left, WHITE);
+        
+        // Cursor
+        let cursor_x = text.x + measure_text(name, None, text.h as u16, 1.0).width;
+        draw_line(cursor_x, text.y, cursor_x, text.bottom(), 2.0, WHITE);
+    }
+    
+    pub fn draw_leaderboard(&self, title: &str, entries: &[ScoreEntry], page: usize, pages: usize) {
+        let layout = layout::page(&Layout::current(), 560.0);
+        let body = &layout.body;
+        
+        clear_background(BLACK);
+        
+        // Title
+        draw_label(tr(Text::LeaderboardTitle), layout.title, Align::Center, GOLD);
+        
+        // Board Title
+        let board_text = format!("< {} >  ({}/{})", title, page + 1, pages);
+        draw_label(&board_text, layout.subtitle, Align::Center, WHITE);
+        
+        // Table
+        let columns = [(0.0, 50.0), (50.0, 180.0), (230.0, 100.0), (330.0, 70.0), (400.0, 70.0), (470.0, 90.0)];
+        let headers = [
+            "#",
+            tr(Text::ColumnName),
+            tr(Text::ColumnScore),
+            tr(Text::ColumnWave),
+            tr(Text::ColumnTime),
+            tr(Text::ColumnDate),
+        ];
+        let mut rows = body.stack(18.0, 10.0);
+        let header = rows.next(18.0);
+        for (text, (offset, width)) in headers.iter().zip(columns) {
+            draw_label(text, body.cell(header, offset, width, 18.0), Align::Left, LIGHTGRAY);
+        }
+        let line_y = header.bottom() + body.px(4.0);
+        draw_line(header.x, line_y, header.right(), line_y, 1.0, GRAY);
+        rows.skip(7.0);
+        
+        if entries.is_empty() {
+            draw_label(tr(Text::NoScoresYet), rows.next(20.0), Align::Center, GRAY);
+        }
+        
+        for (i, entry) in entries.iter().enumerate() {
+            let row = rows.next(18.0);
+            let color = if i == 0 { GOLD } else { WHITE };
+            let cells = [
+                format!("{}", i + 1),
+                entry.name.clone(),
+                format_number(entry.score as i64),
+                format!("{}", entry.wave),
+                format_duration(entry.duration_secs),
+                entry.date.clone(),
+            ];
+            for (cell, (offset, width)) in cells.iter().zip(columns) {
+                draw_label(cell, body.cell(row, offset, width, 18.0), Align::Left, color);
+            }
+        }
+        
+        // Instructions
+        draw_label(tr(Text::LeaderboardHelp), layout.footer, Align::Center, LIGHTGRAY);
+    }
+    
+    pub fn draw_achievements(&self, achievements: &AchievementTracker, page: usize) {
+        let layout = layout::page(&Layout::current(), 560.0);
+        
+        clear_background(BLACK);
+        
+        // Title
+        draw_label(tr(Text::AchievementsTitle), layout.title, Align::Center, GOLD);
+        
+        // Unlocked Count
+        let total = achievements.definitions.len();
+        let pages = total.div_ceil(ACHIEVEMENTS_PER_PAGE).max(1);
+        let page = page.min(pages - 1);
+        let count_text = trf(
+            Text::AchievementsUnlocked,
+            &[&achievements.unlocked_count(), &total, &(page + 1), &pages],
         );
+        draw_label(&count_text, layout.subtitle, Align::Center, WHITE);
+        
+        // Achievement List
+        let mut rows = layout.body.stack(0.0, 8.0);
+        let entries = achievements.definitions.iter().skip(page * ACHIEVEMENTS_PER_PAGE).take(ACHIEVEMENTS_PER_PAGE);
+        for achievement in entries {
+            let card = layout.body.within(rows.next(56.0));
+            let area = card.area;
+            let unlocked = achievements.progress.unlocked.get(&achievement.id);
+            let (border, title_color) = if unlocked.is_some() { (GOLD, GOLD) } else { (DARKGRAY, LIGHTGRAY) };
+            draw_rectangle(area.x, area.y, area.w, area.h, Color::new(0.12, 0.12, 0.12, 1.0));
+            draw_rectangle_lines(area.x, area.y, area.w, area.h, 2.0, border);
+            draw_label(achievement.title(), card.place(Anchor::TopLeft, 400.0, 22.0, vec2(12.0, 5.0)), Align::Left, title_color);
+            draw_label(achievement.summary(), card.place(Anchor::TopLeft, 536.0, 18.0, vec2(12.0, 30.0)), Align::Left, GRAY);
+            
+            // 已解锁显示日期，未解锁且有累计进度时显示进度条
+            let corner = card.place(Anchor::TopRight, 200.0, 16.0, vec2(-12.0, 9.0));
+            if let Some(date) = unlocked {
+                draw_label(date, corner, Align::Right, GREEN);
+            } else if let Some((current, target)) = achievements.progress_of(achievement.goal) {
+                let bar = card.place(Anchor::TopRight, 140.0, 10.0, vec2(-12.0, 12.0));
+                let ratio = current as f32 / target.max(1) as f32;
+                draw_rectangle(bar.x, bar.y, bar.w, bar.h, DARKGRAY);
+                draw_rectangle(bar.x, bar.y, bar.w * ratio, bar.h, SKYBLUE);
+                let progress = card.place(Anchor::TopRight, 140.0, 16.0, vec2(-12.0, 27.0));
+                draw_label(&format!("{}/{}", current, target), progress, Align::Right, LIGHTGRAY);
+            } else {
+                draw_label(tr(Text::Locked), corner, Align::Right, DARKGRAY);
+            }
+        }
+        
+        // Instructions
+        draw_label(tr(Text::PageHelp), layout.footer, Align::Center, LIGHTGRAY);
+    }
+    
+    pub fn draw_learning_report(&self, learning: &LearningData, status: Option<&str>) {
+        let layout = layout::page(&Layout::current(), 600.0);
+        let body = &layout.body;
+        let profile = learning.profile();
         
-        // Math Problem
-        let problem = challenge.get_question_text();
-        let problem_size = 32.0;
-        let problem_dims = measure_text(&problem, None, problem_size as u16, 1.0);
-        draw_text(
-            &problem,
-            screen_w / 2.0 - problem_dims.width / 2.0,
-            screen_h / 2.0 - 10.0,
-            problem_size,
-            YELLOW,
+        clear_background(BLACK);
+        
+        // Title
+        draw_label(tr(Text::LearningTitle), layout.title, Align::Center, GOLD);
+        
+        // Profile
+        let profile_text = trf(Text::ProfileLine, &[&learning.active, &learning.profiles.len()]);
+        draw_label(&profile_text, layout.subtitle, Align::Center, SKYBLUE);
+        
+        // Summary
+        let summary = profile.summary();
+        let summary_text = trf(
+            Text::LearningSummary,
+            &[
+                &summary.attempts,
+                &format!("{:.0}", summary.accuracy() * 100.0),
+                &format!("{:.1}", summary.average_seconds()),
+                &profile.review.len(),
+            ],
         );
+        let mut rows = body.stack(0.0, 4.0);
+        draw_label(&summary_text, rows.next(18.0), Align::Center, WHITE);
+        
+        // Revive Challenges: enabled kinds are highlighted
+        let enabled = profile.revive_challenges();
+        let labels: Vec<String> = ChallengeKind::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, kind)| format!("{}:{}", i + 1, kind.name()))
+            .collect();
+        let kinds = rows.next(16.0);
+        let gap = body.px(14.0);
+        let widths: Vec<f32> = labels.iter().map(|label| measure_text(label, None, kinds.h as u16, 1.0).width).collect();
+        let mut x = kinds.center().x - (widths.iter().sum::<f32>() + gap * labels.len() as f32) / 2.0;
+        for ((label, width), kind) in labels.iter().zip(widths).zip(ChallengeKind::ALL) {
+            let color = if enabled.contains(&kind) { GREEN } else { DARKGRAY };
+            draw_label(label, Rect::new(x, kinds.y, width, kinds.h), Align::Left, color);
+            x += width + gap;
+        }
+        rows.skip(4.0);
+        
+        // Topic Table
+        let header = rows.next(18.0);
+        draw_label(tr(Text::ColumnTopic), body.cell(header, 0.0, 220.0, 18.0), Align::Left, GRAY);
+        draw_label(tr(Text::ColumnQuestions), body.cell(header, 230.0, 100.0, 18.0), Align::Left, GRAY);
+        draw_label(tr(Text::ColumnAccuracy), body.cell(header, 340.0, 170.0, 18.0), Align::Left, GRAY);
+        draw_label(tr(Text::ColumnAvgTime), body.cell(header, 520.0, 80.0, 18.0), Align::Left, GRAY);
+        for topic in Topic::ALL {
+            let row = rows.next(16.0);
+            let stats = profile.topic_summary(topic, None);
+            let color = if stats.attempts == 0 { DARKGRAY } else { WHITE };
+            draw_label(topic.name(), body.cell(row, 0.0, 220.0, 16.0), Align::Left, color);
+            draw_label(&stats.attempts.to_string(), body.cell(row, 230.0, 100.0, 16.0), Align::Left, color);
+            if stats.attempts > 0 {
+                let accuracy = stats.accuracy();
+                let bar_color = if accuracy >= 0.8 {
+                    GREEN
+                } else if accuracy >= 0.5 {
+                    ORANGE
+                } else {
+                    RED
+                };
+                let bar = body.cell(row, 340.0, 120.0, 12.0);
+                draw_rectangle(bar.x, bar.y, bar.w, bar.h, DARKGRAY);
+                draw_rectangle(bar.x, bar.y, bar.w * accuracy, bar.h, bar_color);
+                let percent = format!("{:.0}%", accuracy * 100.0);
+                draw_label(&percent, body.cell(row, 466.0, 50.0, 16.0), Align::Left, LIGHTGRAY);
+                let seconds = trf(Text::Seconds, &[&format!("{:.1}", stats.average_seconds())]);
+                draw_label(&seconds, body.cell(row, 520.0, 80.0, 16.0), Align::Left, color);
+            }
+        }
+        rows.skip(6.0);
+        
+        // Recent Days: accuracy per day
+        let chart_title = rows.next(18.0);
+        draw_label(tr(Text::RecentDays), body.cell(chart_title, 0.0, 120.0, 18.0), Align::Left, GRAY);
+        let days = profile.daily_summaries(8);
+        if days.is_empty() {
+            draw_label(tr(Text::NoQuestionsYet), body.cell(chart_title, 130.0, 470.0, 18.0), Align::Left, DARKGRAY);
+        }
+        let bars = rows.next(50.0);
+        let labels = rows.next(14.0);
+        let slot = bars.w / 8.0;
+        let margin = body.px(10.0);
+        for (i, (date, day)) in days.iter().enumerate() {
+            let x = bars.x + i as f32 * slot;
+            let height = bars.h * day.accuracy();
+            draw_rectangle(x + margin, bars.y, slot - 2.0 * margin, bars.h, Color::new(0.15, 0.15, 0.15, 1.0));
+            draw_rectangle(x + margin, bars.bottom() - height, slot - 2.0 * margin, height, SKYBLUE);
+            let label = format!("{} ({})", date.get(5..).unwrap_or(date), day.attempts);
+            draw_label(&label, Rect::new(x, labels.y, slot, labels.h), Align::Center, LIGHTGRAY);
+        }
+        
+        // Status
+        if let Some(status) = status {
+            draw_label(status, layout.status[1], Align::Center, GREEN);
+        }
+        
+        // Instructions
+        draw_label(tr(Text::LearningHelp), layout.footer, Align::Center, LIGHTGRAY);
+    }
+    
+    pub fn draw_profile_entry(&self, name: &str) {
+        let layout = layout::entry(&Layout::current());
+        
+        clear_background(BLACK);
+        
+        // Prompt
+        draw_label(tr(Text::NewProfileName), layout.prompt, Align::Center, LIGHTGRAY);
         
         // Input Box
-        let input_box_width = 200.0;
-        let input_box_height = 40.0;
-        let input_box_x = screen_w / 2.0 - input_box_width / 2.0;
-        let input_box_y = screen_h / 2.0 + 30.0;
-        
-        draw_rectangle(input_box_x, input_box_y, input_box_width, input_box_height, DARKGRAY);
-        draw_rectangle_lines(input_box_x, input_box_y, input_box_width, input_box_height, 2.0, WHITE);
-        
-        // User Input
-        let input_text = challenge.get_user_answer();
-        let input_dims = measure_text(&input_text, None, 24, 1.0);
-        draw_text(
-            &input_text,
-            input_box_x + 10.0,
-            input_box_y + 28.0,
-            24.0,
-            WHITE,
-        );
+        self.draw_input_box(name, &layout);
         
-        // Cursor
-        let cursor_x = input_box_x + 10.0 + input_dims.width;
-        draw_line(cursor_x, input_box_y + 8.0, cursor_x, input_box_y + 32.0, 2.0, WHITE);
+        // Instructions
+        draw_label(tr(Text::ProfileEntryHelp), layout.footer, Align::Center, LIGHTGRAY);
+    }
+    
+    pub fn draw_shop(&self, progression: &Progression, selected: usize) {
+        let layout = layout::page(&Layout::current(), 560.0);
+        
+        clear_background(BLACK);
+        
+        // Title
+        draw_label(tr(Text::UpgradesTitle), layout.title, Align::Center, GOLD);
+        
+        // Scrap and Vanilla Mode
+        let scrap_text = trf(Text::ScrapAmount, &[&format_number(progression.scrap as i64)]);
+        draw_label(&scrap_text, layout.subtitle, Align::Center, WHITE);
+        let (vanilla_text, vanilla_color) = if progression.vanilla {
+            (tr(Text::VanillaOn), GREEN)
+        } else {
+            (tr(Text::VanillaOff), LIGHTGRAY)
+        };
+        let mut rows = layout.body.stack(0.0, 8.0);
+        draw_label(vanilla_text, rows.next(16.0), Align::Center, vanilla_color);
+        
+        // Upgrade List
+        for (i, upgrade) in Upgrade::ALL.iter().enumerate() {
+            let card = layout.body.within(rows.next(50.0));
+            let area = card.area;
+            let is_selected = i == selected;
+            let level = progression.level(*upgrade);
+            let border = if is_selected { YELLOW } else { DARKGRAY };
+            draw_rectangle(area.x, area.y, area.w, area.h, Color::new(0.12, 0.12, 0.12, 1.0));
+            draw_rectangle_lines(area.x, area.y, area.w, area.h, 2.0, border);
+            
+            let name_text = format!("{}  {}/{}", upgrade.name(), level, upgrade.max_level());
+            let name_color = if is_selected { YELLOW } else { WHITE };
+            draw_label(&name_text, card.place(Anchor::TopLeft, 400.0, 20.0, vec2(12.0, 4.0)), Align::Left, name_color);
+            draw_label(upgrade.description(), card.place(Anchor::TopLeft, 536.0, 16.0, vec2(12.0, 27.0)), Align::Left, GRAY);
+            
+            let (cost_text, cost_color) = match progression.next_cost(*upgrade) {
+                Some(cost) if cost <= progression.scrap => (trf(Text::ScrapCost, &[&format_number(cost as i64)]), GOLD),
+                Some(cost) => (trf(Text::ScrapCost, &[&format_number(cost as i64)]), DARKGRAY),
+                None => (tr(Text::MaxLevel).to_string(), GREEN),
+            };
+            draw_label(&cost_text, card.place(Anchor::TopRight, 200.0, 18.0, vec2(-12.0, 6.0)), Align::Right, cost_color);
+        }
         
         // Instructions
-        let submit_text = "Press ENTER to submit, ESC to give up";
-        let submit_dims = measure_text(submit_text, None, 16, 1.0);
-        draw_text(
-            submit_text,
-            screen_w / 2.0 - submit_dims.width / 2.0,
-            screen_h / 2.0 + 100.0,
-            16.0,
-            LIGHTGRAY,
-        );
+        draw_label(tr(Text::ShopHelp), layout.footer, Align::Center, LIGHTGRAY);
+    }
+    
+    pub fn draw_settings(&self, settings: &Settings, selected: usize, rebinding: bool, notice: Option<(String, bool)>) {
+        let screen = Layout::current();
+        let layout = layout::page(&screen, 480.0);
+        let body = &layout.body;
+        
+        // Semi-transparent background
+        draw_rectangle(0.0, 0.0, screen.area.w, screen.area.h, Color::new(0.0, 0.0, 0.0, 0.85));
+        
+        // Title
+        draw_label(tr(Text::SettingsTitle), layout.title, Align::Center, GOLD);
+        
+        // Settings Rows
+        let items = SettingsItem::all();
+        
+        // 行数超过内容区域的高度时滚动，保证选中的行可见
+        let (row_height, spacing) = (30.0, 4.0);
+        let visible = ((body.area.h + body.px(spacing)) / body.px(row_height + spacing)) as usize;
+        let visible = visible.max(1);
+        let first = (selected + 1).saturating_sub(visible);
+        let mut rows = body.stack(0.0, spacing);
+        for (i, item) in items.iter().enumerate().skip(first).take(visible) {
+            let row = rows.next(row_height);
+            let is_selected = i == selected;
+            if is_selected {
+                let margin = body.px(10.0);
+                draw_rectangle(row.x - margin, row.y, row.w + 2.0 * margin, row.h, Color::new(1.0, 1.0, 1.0, 0.1));
+            }
+            let color = if is_selected { YELLOW } else { WHITE };
+            draw_label(item.label(), body.cell(row, 0.0, 250.0, 20.0), Align::Left, color);
+            
+            // 音量等数值项显示为滑块
+            if let Some(ratio) = item.slider_value(settings) {
+                let slider = body.cell(row, 260.0, 150.0, 8.0);
+                draw_rectangle(slider.x, slider.y, slider.w, slider.h, DARKGRAY);
+                draw_rectangle(slider.x, slider.y, slider.w * ratio, slider.h, color);
+                draw_label(&item.value_text(settings), body.cell(row, 422.0, 58.0, 18.0), Align::Left, color);
+            } else if let SettingsItem::Bind(_) = item {
+                let value = if is_selected && rebinding {
+                    tr(Text::PressAKey).to_string()
+                } else {
+                    item.value_text(settings)
+                };
+                draw_label(&value, body.cell(row, 260.0, 220.0, 18.0), Align::Left, color);
+            } else if *item != SettingsItem::ResetControls {
+                let value = format!("< {} >", item.value_text(settings));
+                draw_label(&value, body.cell(row, 260.0, 220.0, 20.0), Align::Left, color);
+            }
+        }
+        
+        // Notice for the selected item, shown in orange when it reports a problem
+        if let Some((notice, is_error)) = notice {
+            let color = if is_error { ORANGE } else { SKYBLUE };
+            let [first_line, _] = layout.status;
+            let lines = wrap_text(&notice, first_line.w - screen.px(60.0), first_line.h);
+            for (line, rect) in lines.iter().zip(layout.status) {
+                draw_label(line, rect, Align::Center, color);
+            }
+        }
+        
+        // Instructions
+        let help_text = if rebinding {
+            tr(Text::RebindHelp)
+        } else if matches!(items.get(selected), Some(SettingsItem::Bind(_)) | Some(SettingsItem::ResetControls)) {
+            tr(Text::SettingsBindHelp)
+        } else {
+            tr(Text::SettingsHelp)
+        };
+        draw_label(help_text, layout.footer, Align::Center, LIGHTGRAY);
+    }
+    
+    pub fn draw_intermission(&self, wave: i32, offers: &[Perk], selected: usize) {
+        let screen = Layout::current();
+        let layout = layout::intermission(&screen, offers.len());
+        
+        // Semi-transparent background
+        draw_rectangle(0.0, 0.0, screen.area.w, screen.area.h, Color::new(0.0, 0.0, 0.0, 0.7));
+        
+        // Title
+        draw_label(&trf(Text::WaveIncoming, &[&wave]), layout.title, Align::Center, GOLD);
+        draw_label(tr(Text::ChooseUpgrade), layout.subtitle, Align::Center, WHITE);
+        
+        // Upgrade Cards
+        for (i, (perk, card)) in offers.iter().zip(&layout.cards).enumerate() {
+            let is_selected = i == selected;
+            let rarity_color = match perk.rarity() {
+                Rarity::Common => LIGHTGRAY,
+                Rarity::Rare => SKYBLUE,
+                Rarity::Epic => VIOLET,
+            };
+            // 选中的卡片向上浮起一点
+            let lift = if is_selected { screen.px(8.0) } else { 0.0 };
+            let card = screen.within(Rect::new(card.x, card.y - lift, card.w, card.h));
+            let area = card.area;
+            
+            draw_rectangle(area.x, area.y, area.w, area.h, Color::new(0.1, 0.1, 0.12, 0.95));
+            draw_rectangle_lines(area.x, area.y, area.w, area.h, if is_selected { 4.0 } else { 2.0 }, rarity_color);
+            
+            let number = card.place(Anchor::TopLeft, 40.0, 22.0, vec2(10.0, 6.0));
+            draw_label(&(i + 1).to_string(), number, Align::Left, GRAY);
+            let mut column = card.inset(12.0).stack(30.0, 4.0);
+            draw_label(perk.name(), column.next(22.0), Align::Center, WHITE);
+            draw_label(perk.rarity().name(), column.next(16.0), Align::Center, rarity_color);
+            column.skip(18.0);
+            
+            let inner = card.inset(12.0).area;
+            for line in wrap_text(perk.description(), inner.w, card.px(16.0)) {
+                let rect = column.next(16.0);
+                if rect.bottom() > inner.bottom() {
+                    break;
+                }
+                draw_label(&line, rect, Align::Center, LIGHTGRAY);
+            }
+        }
+        
+        // Instructions
+        draw_label(tr(Text::IntermissionHelp), layout.footer, Align::Center, LIGHTGRAY);
+    }
+    
+    // 补给箱题目面板：画在屏幕下方，不挡住战场中央
+    pub fn draw_supply_drop(&self, drop: &SupplyDrop) {
+        let layout = layout::supply_drop(&Layout::current(), drop.challenge.choices.len());
+        let panel = layout.panel;
+        
+        draw_rectangle(panel.x, panel.y, panel.w, panel.h, Color::new(0.0, 0.0, 0.0, 0.75));
+        draw_rectangle_lines(panel.x, panel.y, panel.w, panel.h, 2.0, GOLD);
+        
+        // Reward and countdown
+        let title = trf(Text::SupplyDropTitle, &[&drop.reward.name(), &drop.reward.description()]);
+        draw_label(&title, layout.title, Align::Left, GOLD);
+        let ratio = drop.time_left / SUPPLY_TIME;
+        let bar_color = if ratio > 0.5 {
+            GREEN
+        } else if ratio > 0.25 {
+            ORANGE
+        } else {
+            RED
+        };
+        let bar = layout.timer;
+        draw_rectangle(bar.x, bar.y, bar.w, bar.h, DARKGRAY);
+        draw_rectangle(bar.x, bar.y, bar.w * ratio, bar.h, bar_color);
+        
+        // Question
+        draw_label(drop.challenge.get_question_text(), layout.question, Align::Center, YELLOW);
         
-        // Show result if completed
-        if challenge.is_completed {
-            let result_text = if challenge.is_correct {
-                "Correct! You are revived!"
+        // Choices in one row
+        for (i, (choice, rect)) in drop.challenge.choices.iter().zip(&layout.choices).enumerate() {
+            draw_label(&format!("{}) {}", i + 1, choice), *rect, Align::Center, WHITE);
+        }
+        
+        draw_label(tr(Text::SupplyDropHint), layout.hint, Align::Center, GRAY);
+    }
+    
+    pub fn draw_challenge(&self, session: &ChallengeSession, streak: u32, revives_left: Option<u32>) {
+        let screen = Layout::current();
+        let layout = layout::challenge(&screen);
+        let challenge = &session.challenge;
+        
+        // Semi-transparent background
+        draw_rectangle(0.0, 0.0, screen.area.w, screen.area.h, Color::new(0.0, 0.0, 0.0, 0.8));
+        
+        // Title
+        let title = trf(Text::ReviveTitle, &[&challenge.title().to_uppercase()]);
+        draw_label(&title, layout.title, Align::Center, GOLD);
+        
+        // Countdown
+        if let (Some(limit), Some(left)) = (session.time_limit, session.time_left()) {
+            let bar = layout.timer;
+            let ratio = if limit > 0.0 { left / limit } else { 0.0 };
+            let bar_color = if ratio > 0.5 {
+                GREEN
+            } else if ratio > 0.25 {
+                ORANGE
             } else {
-                "Wrong answer. Game Over!"
+                RED
             };
-            let result_color = if challenge.is_correct { GREEN } else { RED };
-            let result_dims = measure_text(result_text, None, self.font_size as u16, 1.0);
-            draw_text(
-                result_text,
-                screen_w / 2.0 - result_dims.width / 2.0,
-                screen_h / 2.0 + 130.0,
-                self.font_size,
-                result_color,
-            );
+            draw_rectangle(bar.x, bar.y, bar.w, bar.h, DARKGRAY);
+            draw_rectangle(bar.x, bar.y, bar.w * ratio, bar.h, bar_color);
+            draw_rectangle_lines(bar.x, bar.y, bar.w, bar.h, 1.0, WHITE);
+            let seconds = trf(Text::Seconds, &[&format!("{:.1}", left)]);
+            draw_label(&seconds, layout.timer_text, Align::Left, bar_color);
+        }
+        
+        draw_label(&challenge.subtitle(), layout.subtitle, Align::Center, LIGHTGRAY);
+        
+        // Instructions
+        draw_label(challenge.prompt(), layout.prompt, Align::Center, WHITE);
+        
+        // Question and answer area, drawn by the challenge itself
+        let [hint, keys, info_rect] = layout::challenge_footer(&screen, challenge.draw());
+        
+        draw_label(challenge.hint(), hint, Align::Center, GRAY);
+        draw_label(tr(Text::ChallengeAnswerHelp), keys, Align::Center, LIGHTGRAY);
+        
+        // Revive preview: faster answers and streaks restore more health
+        let mut info = trf(Text::ReviveHealth, &[&format!("{:.0}", session.revive_fraction(streak) * 100.0)]);
+        if streak > 0 {
+            info.push_str(&format!("   {}", trf(Text::ReviveStreak, &[&streak])));
+        }
+        if let Some(left) = revives_left {
+            info.push_str(&format!("   {}", trf(Text::RevivesLeft, &[&left])));
+        }
+        draw_label(&info, info_rect, Align::Center, SKYBLUE);
+    }
+}
+
+// 按宽度把文字折成多行：英文在空格处换行，中日韩文字在任意两个字之间都可以换行
+pub fn wrap_text(text: &str, max_width: f32, font_size: f32) -> Vec<String> {
+    wrap_with(text, max_width, |line| measure_text(line, None, font_size as u16, 1.0).width)
+}
+
+fn wrap_with(text: &str, max_width: f32, width: impl Fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        for (i, piece) in break_units(word).into_iter().enumerate() {
+            let separator = if i == 0 && !current.is_empty() { " " } else { "" };
+            let candidate = format!("{}{}{}", current, separator, piece);
+            if !current.is_empty() && width(&candidate) > max_width {
+                lines.push(std::mem::replace(&mut current, piece.to_string()));
+            } else {
+                current = candidate;
+            }
         }
     }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+// 不能出现在行首和行尾的标点
+const NO_BREAK_BEFORE: &str = "，。、！？；：）」』》〉…,.!?;:)%";
+const NO_BREAK_AFTER: &str = "（「『《〈(";
+
+// 把一个词拆成可以在其间换行的片段：中日韩文字逐字拆开，标点跟着相邻的字
+fn break_units(word: &str) -> Vec<&str> {
+    let mut units = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    for (i, c) in word.char_indices() {
+        if let Some(p) = prev {
+            if (is_cjk(c) || is_cjk(p)) && !NO_BREAK_BEFORE.contains(c) && !NO_BREAK_AFTER.contains(p) {
+                units.push(&word[start..i]);
+                start = i;
+            }
+        }
+        prev = Some(c);
+    }
+    units.push(&word[start..]);
+    units
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{2E80}'..='\u{9FFF}' // 部首、标点、假名、汉字
+        | '\u{AC00}'..='\u{D7AF}' // 韩文
+        | '\u{F900}'..='\u{FAFF}' // 兼容汉字
+        | '\u{FF00}'..='\u{FFEF}' // 全角字符
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // 每个字符宽 1，便于验证换行位置
+    fn wrap(text: &str, max_width: f32) -> Vec<String> {
+        wrap_with(text, max_width, |line| line.chars().count() as f32)
+    }
+    
+    #[test]
+    fn latin_text_wraps_at_spaces() {
+        assert_eq!(wrap("one two three", 8.0), vec!["one two", "three"]);
+        assert_eq!(wrap("  spaced   out  ", 20.0), vec!["spaced out"]);
+        // 放不下的长词单独占一行
+        assert_eq!(wrap("a extraordinary b", 5.0), vec!["a", "extraordinary", "b"]);
+    }
+    
+    #[test]
+    fn cjk_text_wraps_between_characters() {
+        assert_eq!(wrap("答对可以复活坦克", 3.0), vec!["答对可", "以复活", "坦克"]);
+        // 标点不出现在行首，英文单词不被拆开
+        assert_eq!(wrap("答对了，继续战斗", 3.0), vec!["答对", "了，继", "续战斗"]);
+        assert_eq!(wrap("使用Cannon击毁", 7.0), vec!["使用", "Cannon击", "毁"]);
+        assert_eq!(wrap("《坦克》", 2.0), vec!["《坦", "克》"]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,78 @@
+use crate::entities::{Tank, Weapon};
+use crate::storage::ColorblindMode;
+use macroquad::prelude::*;
+
+// 界面和坦克使用的配色；色盲模式下使用 Okabe-Ito 等易于区分的颜色代替红绿
+#[derive(Clone, Copy, Debug)]
+pub struct Palette {
+    pub mode: ColorblindMode,
+    pub player: Color,
+    pub enemies: [Color; 3], // 依次对应 Weapon::BASIC
+    pub health_high: Color,
+    pub health_mid: Color,
+    pub health_low: Color,
+}
+
+impl Palette {
+    pub fn new(mode: ColorblindMode) -> Self {
+        match mode {
+            ColorblindMode::Off => Self {
+                mode,
+                player: BLUE,
+                enemies: [
+                    Weapon::Cannon.enemy_color(),
+                    Weapon::Mortar.enemy_color(),
+                    Weapon::MineLayer.enemy_color(),
+                ],
+                health_high: GREEN,
+                health_mid: YELLOW,
+                health_low: RED,
+            },
+            ColorblindMode::Deuteranopia | ColorblindMode::Protanopia => Self {
+                mode,
+                player: Color::new(0.0, 0.45, 0.7, 1.0),
+                enemies: [
+                    Color::new(0.9, 0.6, 0.0, 1.0),
+                    Color::new(0.8, 0.4, 0.0, 1.0),
+                    Color::new(0.95, 0.9, 0.25, 1.0),
+                ],
+                health_high: Color::new(0.35, 0.7, 0.9, 1.0),
+                health_mid: Color::new(0.95, 0.9, 0.25, 1.0),
+                health_low: Color::new(0.8, 0.4, 0.0, 1.0),
+            },
+            ColorblindMode::Tritanopia => Self {
+                mode,
+                player: Color::new(0.0, 0.62, 0.45, 1.0),
+                enemies: [
+                    Color::new(0.84, 0.37, 0.0, 1.0),
+                    Color::new(0.8, 0.47, 0.65, 1.0),
+                    Color::new(0.9, 0.1, 0.3, 1.0),
+                ],
+                health_high: Color::new(0.0, 0.62, 0.45, 1.0),
+                health_mid: Color::new(0.8, 0.47, 0.65, 1.0),
+                health_low: Color::new(0.9, 0.1, 0.3, 1.0),
+            },
+        }
+    }
+    
+    pub fn tank_color(&self, tank: &Tank) -> Color {
+        if self.mode == ColorblindMode::Off {
+            return tank.color;
+        }
+        if tank.is_player {
+            return self.player;
+        }
+        let index = Weapon::BASIC.iter().position(|w| *w == tank.weapon).unwrap_or(0);
+        self.enemies[index]
+    }
+    
+    pub fn health_color(&self, health_ratio: f32) -> Color {
+        if health_ratio > 0.6 {
+            self.health_high
+        } else if health_ratio > 0.3 {
+            self.health_mid
+        } else {
+            self.health_low
+        }
+    }
+}
\ No newline at end of file